@@ -2,10 +2,28 @@
 
 set -ex
 
+# The dev-dependencies are not pinned, and their latest versions routinely
+# require a newer Rust than this crate does. On the minimum supported Rust
+# version, pick dependency versions with a newer Cargo that respects the
+# `rust-version` in Cargo.toml, and then build and test as usual.
+if [ "$TRAVIS_RUST_VERSION" = "1.65.0" ]; then
+    rustup toolchain install stable --profile minimal
+    CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS=fallback \
+        cargo +stable generate-lockfile
+fi
+
 cargo doc --verbose
 cargo build --verbose
 cargo test --verbose --lib
 cargo test --verbose --doc
+cargo test --verbose --doc --features measure
+cargo test --verbose --lib --features conformance
+cargo test --verbose --doc --features conformance
+cargo test --verbose --lib --features parallel
+cargo test --verbose --lib --features sample
+cargo test --verbose --doc --features sample
+cargo test --verbose --lib --features serde
+cargo test --verbose --lib --features capi
 
 cargo doc --verbose --no-default-features
 cargo build --verbose --no-default-features
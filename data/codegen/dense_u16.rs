@@ -0,0 +1,54 @@
+// This file was generated by regex-automata.
+// It is a dense DFA with 16 states and 25 byte classes.
+
+pub static DENSE_U16: ::regex_automata::DenseDFA<&'static [u16], u16> = unsafe {
+    ::regex_automata::DenseDFA::from_parts(
+        true, // premultiplied
+        false, // anchored
+        375, // start state
+        16, // state count
+        25, // max match state
+        DENSE_U16_BYTE_CLASSES,
+        &DENSE_U16_TRANS,
+    )
+};
+
+const DENSE_U16_BYTE_CLASSES: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 4, 5,
+    5, 5, 6, 7, 7, 7, 7, 7, 7, 7, 7, 8, 9, 9, 10, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12,
+    12, 12, 12, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13,
+    14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14,
+    14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 15, 15, 16, 16, 16, 16,
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16,
+    16, 16, 16, 16, 16, 16, 16, 17, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18,
+    18, 19, 20, 20, 21, 22, 22, 22, 23, 24, 24, 24, 24, 24, 24, 24, 24, 24, 24,
+    24,
+];
+
+static DENSE_U16_TRANS: [u16; 400] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    375, 375, 375, 375, 375, 375, 50, 375, 250, 375, 375, 375, 0, 0, 0, 0, 75,
+    100, 125, 150, 125, 175, 200, 225, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    375, 375, 375, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 75, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 75, 75, 75, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 75, 75, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 125, 125, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 125, 125, 125, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 125, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 375, 375,
+    375, 375, 375, 375, 50, 375, 275, 375, 375, 375, 0, 0, 0, 0, 75, 100, 125,
+    150, 125, 175, 200, 225, 0, 375, 300, 375, 375, 375, 375, 50, 375, 375,
+    375, 375, 375, 0, 0, 0, 0, 75, 100, 125, 150, 125, 175, 200, 225, 0, 375,
+    300, 375, 375, 325, 375, 50, 375, 375, 375, 375, 375, 0, 0, 0, 0, 75, 100,
+    125, 150, 125, 175, 200, 225, 0, 375, 375, 375, 350, 375, 375, 50, 375,
+    375, 375, 375, 375, 0, 0, 0, 0, 75, 100, 125, 150, 125, 175, 200, 225, 0,
+    375, 375, 375, 375, 375, 375, 50, 375, 375, 375, 25, 375, 0, 0, 0, 0, 75,
+    100, 125, 150, 125, 175, 200, 225, 0, 375, 375, 375, 375, 375, 375, 50,
+    375, 375, 375, 375, 375, 0, 0, 0, 0, 75, 100, 125, 150, 125, 175, 200, 225,
+    0,
+];
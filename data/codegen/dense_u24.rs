@@ -0,0 +1,87 @@
+// This file was generated by regex-automata.
+// It is a dense DFA with 9 states and 12 byte classes.
+
+pub static DENSE_U24: ::regex_automata::DenseDFA<&'static [::regex_automata::U24], ::regex_automata::U24> = unsafe {
+    ::regex_automata::DenseDFA::from_parts(
+        false, // premultiplied
+        true, // anchored
+        ::regex_automata::U24::new(8), // start state
+        9, // state count
+        ::regex_automata::U24::new(1), // max match state
+        DENSE_U24_BYTE_CLASSES,
+        &DENSE_U24_TRANS,
+    )
+};
+
+const DENSE_U24_BYTE_CLASSES: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 4, 5,
+    5, 5, 6, 7, 7, 7, 7, 7, 7, 7, 7, 8, 9, 9, 10, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11, 11,
+    11,
+];
+
+static DENSE_U24_TRANS: [::regex_automata::U24; 108] = [
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(3), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(4), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(5),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(5),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(6), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(7),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(1), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(2), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+    ::regex_automata::U24::new(0), ::regex_automata::U24::new(0),
+];
@@ -0,0 +1,79 @@
+// This file was generated by regex-automata.
+// It is a sparse DFA with 16 states and 25 byte classes.
+
+pub static SPARSE_U16: ::regex_automata::SparseDFA<&'static [u8], u16> = unsafe {
+    ::regex_automata::SparseDFA::from_parts(
+        false, // anchored
+        378, // start state
+        16, // state count
+        2, // max match state
+        SPARSE_U16_BYTE_CLASSES,
+        &SPARSE_U16_TRANS,
+    )
+};
+
+const SPARSE_U16_BYTE_CLASSES: [u8; 256] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+    1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+    2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 4, 5,
+    5, 5, 6, 7, 7, 7, 7, 7, 7, 7, 7, 8, 9, 9, 10, 11, 11, 11, 11, 11, 11, 11,
+    11, 11, 11, 11, 11, 11, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12, 12,
+    12, 12, 12, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13, 13,
+    14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14,
+    14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 14, 15, 15, 16, 16, 16, 16,
+    16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16, 16,
+    16, 16, 16, 16, 16, 16, 16, 17, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18, 18,
+    18, 19, 20, 20, 21, 22, 22, 22, 23, 24, 24, 24, 24, 24, 24, 24, 24, 24, 24,
+    24,
+];
+
+#[cfg(target_endian = "little")]
+static SPARSE_U16_TRANS: [u8; 424] = [
+    0, 0, 0, 0, 13, 0, 0, 5, 6, 6, 7, 7, 8, 8, 9, 11, 16, 16, 17, 17, 18, 18,
+    19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 122, 1, 4, 0, 122, 1, 100, 0, 122,
+    1, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94, 0, 1, 0, 12, 14,
+    122, 1, 1, 0, 14, 14, 58, 0, 1, 0, 12, 14, 58, 0, 1, 0, 12, 13, 58, 0, 1,
+    0, 13, 14, 70, 0, 1, 0, 12, 14, 70, 0, 1, 0, 12, 12, 70, 0, 13, 0, 0, 5, 6,
+    6, 7, 7, 8, 8, 9, 11, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22,
+    22, 23, 23, 122, 1, 4, 0, 122, 1, 154, 0, 122, 1, 58, 0, 64, 0, 70, 0, 76,
+    0, 70, 0, 82, 0, 88, 0, 94, 0, 13, 0, 0, 0, 1, 1, 2, 5, 6, 6, 7, 11, 16,
+    16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 122, 1, 208, 0,
+    122, 1, 4, 0, 122, 1, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94,
+    0, 15, 0, 0, 0, 1, 1, 2, 3, 4, 4, 5, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18,
+    18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 122, 1, 208, 0, 122, 1, 14, 1,
+    122, 1, 4, 0, 122, 1, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94,
+    0, 13, 0, 0, 2, 3, 3, 4, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18, 18, 19, 19,
+    20, 20, 21, 21, 22, 22, 23, 23, 122, 1, 68, 1, 122, 1, 4, 0, 122, 1, 58, 0,
+    64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94, 0, 13, 0, 0, 5, 6, 6, 7, 9,
+    10, 10, 11, 11, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23,
+    23, 122, 1, 4, 0, 122, 1, 2, 0, 122, 1, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0,
+    82, 0, 88, 0, 94, 0, 11, 0, 0, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18, 18, 19,
+    19, 20, 20, 21, 21, 22, 22, 23, 23, 122, 1, 4, 0, 122, 1, 58, 0, 64, 0, 70,
+    0, 76, 0, 70, 0, 82, 0, 88, 0, 94, 0,
+];
+
+#[cfg(target_endian = "big")]
+static SPARSE_U16_TRANS: [u8; 424] = [
+    0, 0, 0, 0, 0, 13, 0, 5, 6, 6, 7, 7, 8, 8, 9, 11, 16, 16, 17, 17, 18, 18,
+    19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 1, 122, 0, 4, 1, 122, 0, 100, 1,
+    122, 0, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94, 0, 1, 12, 14,
+    1, 122, 0, 1, 14, 14, 0, 58, 0, 1, 12, 14, 0, 58, 0, 1, 12, 13, 0, 58, 0,
+    1, 13, 14, 0, 70, 0, 1, 12, 14, 0, 70, 0, 1, 12, 12, 0, 70, 0, 13, 0, 5, 6,
+    6, 7, 7, 8, 8, 9, 11, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22,
+    22, 23, 23, 1, 122, 0, 4, 1, 122, 0, 154, 1, 122, 0, 58, 0, 64, 0, 70, 0,
+    76, 0, 70, 0, 82, 0, 88, 0, 94, 0, 13, 0, 0, 1, 1, 2, 5, 6, 6, 7, 11, 16,
+    16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 1, 122, 0, 208,
+    1, 122, 0, 4, 1, 122, 0, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0,
+    94, 0, 15, 0, 0, 1, 1, 2, 3, 4, 4, 5, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18,
+    18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 1, 122, 0, 208, 1, 122, 1, 14,
+    1, 122, 0, 4, 1, 122, 0, 58, 0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0,
+    94, 0, 13, 0, 2, 3, 3, 4, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18, 18, 19, 19,
+    20, 20, 21, 21, 22, 22, 23, 23, 1, 122, 1, 68, 1, 122, 0, 4, 1, 122, 0, 58,
+    0, 64, 0, 70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94, 0, 13, 0, 5, 6, 6, 7, 9,
+    10, 10, 11, 11, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23,
+    23, 1, 122, 0, 4, 1, 122, 0, 2, 1, 122, 0, 58, 0, 64, 0, 70, 0, 76, 0, 70,
+    0, 82, 0, 88, 0, 94, 0, 11, 0, 5, 6, 6, 7, 11, 16, 16, 17, 17, 18, 18, 19,
+    19, 20, 20, 21, 21, 22, 22, 23, 23, 1, 122, 0, 4, 1, 122, 0, 58, 0, 64, 0,
+    70, 0, 76, 0, 70, 0, 82, 0, 88, 0, 94,
+];
@@ -0,0 +1,127 @@
+/*
+ * The C API of regex-automata, built with the `capi` feature.
+ *
+ * See the documentation of the `capi` module for details. The functions
+ * declared here correspond one to one with the functions of that module.
+ *
+ * Every regex is made of a forward and a reverse DFA, which are either dense
+ * or sparse. Functions that can fail take an `ra_error *`, which may be
+ * NULL. On failure, they return NULL, false or RA_ERROR and, if the error is
+ * not NULL, record a message in it that can be read with `ra_error_message`.
+ * Otherwise, searches return RA_MATCH or RA_NO_MATCH.
+ *
+ * Panics never unwind into C: they are reported as failures. Searches
+ * starting past the end of the haystack never match.
+ */
+
+#ifndef REGEX_AUTOMATA_H
+#define REGEX_AUTOMATA_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Flags for `ra_regex_compile`, which may be combined with `|`. */
+#define RA_FLAG_ANCHORED (1u << 0)
+#define RA_FLAG_CASEI (1u << 1)
+#define RA_FLAG_IGNORE_WHITESPACE (1u << 2)
+#define RA_FLAG_DOT_MATCHES_NEW_LINE (1u << 3)
+#define RA_FLAG_SWAP_GREED (1u << 4)
+#define RA_FLAG_NO_UNICODE (1u << 5)
+#define RA_FLAG_ALLOW_INVALID_UTF8 (1u << 6)
+#define RA_FLAG_LEFTMOST_LONGEST (1u << 7)
+#define RA_FLAG_MINIMIZE (1u << 8)
+#define RA_FLAG_SPARSE (1u << 9)
+
+/* The statuses returned by searches. */
+#define RA_MATCH 1
+#define RA_NO_MATCH 0
+#define RA_ERROR (-1)
+
+/* A compiled regex. */
+typedef struct ra_regex ra_regex;
+
+/* The error of a failed call. */
+typedef struct ra_error ra_error;
+
+/* The offsets of a match, corresponding to the range [start, end). */
+typedef struct ra_match {
+    size_t start;
+    size_t end;
+} ra_match;
+
+/* Bytes owned by this library, which must be freed with `ra_bytes_free`. */
+typedef struct ra_bytes {
+    uint8_t *data;
+    size_t len;
+} ra_bytes;
+
+ra_error *ra_error_new(void);
+void ra_error_free(ra_error *err);
+/* The message is valid until `err` is freed or given to another call. */
+const char *ra_error_message(const ra_error *err);
+
+ra_regex *ra_regex_compile(
+    const uint8_t *pattern,
+    size_t len,
+    uint32_t flags,
+    ra_error *err);
+void ra_regex_free(ra_regex *re);
+
+int ra_regex_is_match(
+    const ra_regex *re,
+    const uint8_t *haystack,
+    size_t len,
+    ra_error *err);
+int ra_regex_is_match_at(
+    const ra_regex *re,
+    const uint8_t *haystack,
+    size_t len,
+    size_t start,
+    ra_error *err);
+int ra_regex_shortest_match_at(
+    const ra_regex *re,
+    const uint8_t *haystack,
+    size_t len,
+    size_t start,
+    size_t *end,
+    ra_error *err);
+int ra_regex_find(
+    const ra_regex *re,
+    const uint8_t *haystack,
+    size_t len,
+    ra_match *m,
+    ra_error *err);
+int ra_regex_find_at(
+    const ra_regex *re,
+    const uint8_t *haystack,
+    size_t len,
+    size_t start,
+    ra_match *m,
+    ra_error *err);
+
+/* Serialize the DFAs of `re` in native endianness. */
+bool ra_regex_serialize(
+    const ra_regex *re,
+    ra_bytes *fwd,
+    ra_bytes *rev,
+    ra_error *err);
+/* Validate and copy serialized DFAs into a new regex, rejecting DFAs that
+ * disagree on their shortest matches. */
+ra_regex *ra_regex_deserialize(
+    const uint8_t *fwd,
+    size_t fwd_len,
+    const uint8_t *rev,
+    size_t rev_len,
+    ra_error *err);
+void ra_bytes_free(ra_bytes bytes);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
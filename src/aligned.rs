@@ -0,0 +1,102 @@
+use core::ops::Deref;
+
+/// A container of bytes that are aligned to the alignment of `A`.
+///
+/// Dense DFAs borrow their transition table directly from the bytes they are
+/// deserialized from, which requires those bytes to be aligned to the
+/// alignment of the DFA's state identifier representation. Bytes embedded in
+/// a binary with `include_bytes!` have no alignment guarantees, so whether
+/// deserializing them succeeds depends on where the linker happens to place
+/// them. Wrapping them in this type fixes their alignment.
+///
+/// `A` is only used for its alignment. It is typically the state identifier
+/// representation of the DFA stored in the bytes, or `u64` to satisfy every
+/// representation provided by this crate.
+///
+/// The
+/// [`include_aligned_bytes!`](macro.include_aligned_bytes.html)
+/// macro provides a convenient way to embed aligned bytes from a file.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::AlignedBytes;
+///
+/// static BYTES: &AlignedBytes<u64, [u8]> = &AlignedBytes::new([1, 2, 3]);
+///
+/// assert_eq!(0, BYTES.as_ptr() as usize % 8);
+/// assert_eq!(&[1, 2, 3], &BYTES[..]);
+/// ```
+#[repr(C)]
+pub struct AlignedBytes<A, B: ?Sized> {
+    _align: [A; 0],
+    bytes: B,
+}
+
+impl<A, B> AlignedBytes<A, B> {
+    /// Wrap the given bytes, which are usually an array, such that they are
+    /// aligned to the alignment of `A`.
+    ///
+    /// Since this is a `const fn`, it can be used to initialize a `static`.
+    /// A reference to the result can then be coerced to a reference to
+    /// `AlignedBytes<A, [u8]>`, which does not depend on the number of
+    /// bytes.
+    pub const fn new(bytes: B) -> AlignedBytes<A, B> {
+        AlignedBytes { _align: [], bytes }
+    }
+}
+
+impl<A, B: ?Sized> AlignedBytes<A, B> {
+    /// Return the aligned bytes.
+    ///
+    /// This is equivalent to dereferencing, but can be used in constant
+    /// expressions.
+    pub const fn as_bytes(&self) -> &B {
+        &self.bytes
+    }
+}
+
+impl<A, B: ?Sized> Deref for AlignedBytes<A, B> {
+    type Target = B;
+
+    fn deref(&self) -> &B {
+        &self.bytes
+    }
+}
+
+/// Include the contents of a file as a `&'static [u8]` that is aligned to
+/// the alignment of the given type.
+///
+/// This works just like `include_bytes!`, with the path interpreted relative
+/// to the file containing the invocation, except that the bytes are
+/// guaranteed to be aligned, which makes it possible to embed a serialized
+/// dense DFA in a binary and deserialize it without copying. See
+/// [`AlignedBytes`](struct.AlignedBytes.html) for details.
+///
+/// # Example
+///
+/// This includes a DFA that was serialized with `u16` state identifiers by
+/// a build script.
+///
+/// ```ignore
+/// #[macro_use]
+/// extern crate regex_automata;
+///
+/// use regex_automata::{DFA, DenseDFA};
+///
+/// static BYTES: &[u8] = include_aligned_bytes!(u16, "foo.dfa");
+///
+/// fn main() {
+///     let dfa: DenseDFA<&[u16], u16> =
+///         DenseDFA::try_from_bytes(BYTES).unwrap();
+///     assert_eq!(Some(8), dfa.find(b"foo12345"));
+/// }
+/// ```
+#[macro_export]
+macro_rules! include_aligned_bytes {
+    ($align:ty, $path:expr) => {{
+        static ALIGNED: &$crate::AlignedBytes<$align, [u8]> =
+            &$crate::AlignedBytes::new(*include_bytes!($path));
+        ALIGNED.as_bytes()
+    }};
+}
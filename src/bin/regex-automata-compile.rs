@@ -0,0 +1,351 @@
+// A command line tool for compiling regexes into serialized DFAs.
+//
+// This builds a regex with the options given on the command line, which
+// mirror the options of `RegexBuilder`, and writes its forward and reverse
+// DFAs to files that can be deserialized with `DenseDFA::from_bytes` or
+// `SparseDFA::from_bytes`. This tool is only built when the `compile`
+// feature is enabled.
+
+#[macro_use]
+extern crate clap;
+extern crate regex_automata;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::result;
+use std::time::Duration;
+
+use regex_automata::dense::MatchKind;
+use regex_automata::{DenseDFA, ErrorKind, Regex, RegexBuilder, StateID, U24};
+
+type Result<T> = result::Result<T, Box<dyn Error>>;
+
+macro_rules! err {
+    ($($tt:tt)*) => { Err(From::from(format!($($tt)*))) }
+}
+
+/// The state identifier sizes tried, in order, when no size is given.
+const AUTO_SIZES: &[&str] = &["u8", "u16", "u32", "u64"];
+
+fn main() {
+    if let Err(err) = try_main() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn try_main() -> Result<()> {
+    let args = Args::parse()?;
+    let re = args.builder().build(&args.pattern)?;
+
+    let files = match args.state_size {
+        Some(ref size) => args.serialize(&re, size)?,
+        None => {
+            let mut files = None;
+            for size in AUTO_SIZES {
+                match args.serialize(&re, size) {
+                    Ok(f) => {
+                        files = Some(f);
+                        break;
+                    }
+                    Err(err) => match *err.kind() {
+                        ErrorKind::StateIDOverflow { .. }
+                        | ErrorKind::PremultiplyOverflow { .. } => continue,
+                        _ => return Err(From::from(err)),
+                    },
+                }
+            }
+            match files {
+                Some(files) => files,
+                None => return err!("regex is too big to serialize"),
+            }
+        }
+    };
+
+    fs::create_dir_all(&args.output)?;
+    let mut stdout = io::stdout();
+    for (name, bytes) in files {
+        let path = args.output.join(name);
+        fs::write(&path, &bytes)?;
+        writeln!(stdout, "{}", path.display())?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct Args {
+    pattern: String,
+    output: PathBuf,
+    name: String,
+    sparse: bool,
+    forward_only: bool,
+    state_size: Option<String>,
+    endians: Vec<&'static str>,
+    anchored: bool,
+    match_kind: MatchKind,
+    case_insensitive: bool,
+    ignore_whitespace: bool,
+    dot_matches_new_line: bool,
+    swap_greed: bool,
+    unicode: bool,
+    allow_invalid_utf8: bool,
+    nest_limit: Option<u32>,
+    minimize: bool,
+    premultiply: bool,
+    byte_classes: bool,
+    dfa_size_limit: Option<usize>,
+    nfa_size_limit: Option<usize>,
+    time_limit: Option<Duration>,
+}
+
+impl Args {
+    fn parse() -> Result<Args> {
+        use clap::{App, AppSettings, Arg};
+
+        let flag = |name: &'static str, help: &'static str| {
+            Arg::with_name(name).long(name).help(help)
+        };
+        let option = |name: &'static str, help: &'static str| {
+            flag(name, help).takes_value(true)
+        };
+        let parsed = App::new("regex-automata-compile")
+            .author(crate_authors!())
+            .version(crate_version!())
+            .about(
+                "Compile a regex into serialized forward and reverse DFAs.\n\n\
+                 Multiple patterns are joined into a single alternation, \
+                 and options apply to every pattern. \
+                 Each DFA is written to OUTPUT/NAME.{fwd,rev}.ENDIAN.dfa \
+                 and the paths written are printed.",
+            )
+            .setting(AppSettings::UnifiedHelpMessage)
+            .max_term_width(100)
+            .arg(Arg::with_name("pattern").multiple(true))
+            .arg(
+                option(
+                    "file",
+                    "Read patterns from a file, one per line. \
+                     Blank lines are skipped.",
+                )
+                .short("f")
+                .multiple(true)
+                .number_of_values(1),
+            )
+            .arg(
+                option("output", "The directory to write DFAs to.")
+                    .short("o")
+                    .required(true),
+            )
+            .arg(
+                option("name", "The prefix of each file name.")
+                    .short("n")
+                    .default_value("regex"),
+            )
+            .arg(flag("sparse", "Write sparse DFAs instead of dense DFAs."))
+            .arg(flag("forward-only", "Only write the forward DFA."))
+            .arg(
+                option(
+                    "state-size",
+                    "The state identifier representation. By default, \
+                     the smallest of u8, u16, u32 and u64 that fits.",
+                )
+                .possible_values(&["u8", "u16", "u24", "u32", "u64"]),
+            )
+            .arg(
+                option("endian", "Only write DFAs with this endianness.")
+                    .possible_values(&["little", "big"]),
+            )
+            .arg(flag("anchored", "Only match at the start of the input."))
+            .arg(
+                option("match-kind", "The match semantics of the regex.")
+                    .possible_values(&[
+                        "leftmost-first",
+                        "leftmost-longest",
+                        "all",
+                    ])
+                    .default_value("leftmost-first"),
+            )
+            .arg(
+                flag("case-insensitive", "Match case insensitively.")
+                    .short("i"),
+            )
+            .arg(flag("ignore-whitespace", "Enable verbose mode.").short("x"))
+            .arg(flag("dot-matches-new-line", "Allow `.` to match `\\n`."))
+            .arg(flag("swap-greed", "Swap the greediness of repetitions."))
+            .arg(flag("no-unicode", "Disable Unicode mode."))
+            .arg(flag("allow-invalid-utf8", "Allow matching invalid UTF-8."))
+            .arg(option("nest-limit", "The nesting limit of the parser."))
+            .arg(flag("minimize", "Minimize the DFAs."))
+            .arg(flag("no-premultiply", "Disable premultiplication."))
+            .arg(flag("no-byte-classes", "Disable byte classes."))
+            .arg(option(
+                "dfa-size-limit",
+                "Fail if a transition table exceeds this many bytes.",
+            ))
+            .arg(option(
+                "nfa-size-limit",
+                "Fail if an NFA exceeds this many bytes.",
+            ))
+            .arg(option(
+                "time-limit",
+                "Fail if building a DFA takes more than this many seconds.",
+            ))
+            .get_matches();
+
+        let mut patterns: Vec<String> = parsed
+            .values_of("pattern")
+            .map(|ps| ps.map(|p| p.to_string()).collect())
+            .unwrap_or_default();
+        for path in parsed.values_of_os("file").into_iter().flatten() {
+            let contents = fs::read_to_string(Path::new(path))?;
+            patterns.extend(
+                contents
+                    .lines()
+                    .filter(|p| !p.trim().is_empty())
+                    .map(|p| p.to_string()),
+            );
+        }
+        if patterns.is_empty() {
+            return err!("no patterns found");
+        }
+
+        let endians = match parsed.value_of("endian") {
+            None => vec!["littleendian", "bigendian"],
+            Some("little") => vec!["littleendian"],
+            Some(_) => vec!["bigendian"],
+        };
+        let match_kind = match parsed.value_of("match-kind") {
+            Some("leftmost-longest") => MatchKind::LeftmostLongest,
+            Some("all") => MatchKind::All,
+            _ => MatchKind::LeftmostFirst,
+        };
+        // In verbose mode, a pattern may end with a comment, which would
+        // swallow the closing parenthesis if it were on the same line.
+        let ignore_whitespace = parsed.is_present("ignore-whitespace");
+        let close = if ignore_whitespace { "\n)" } else { ")" };
+        Ok(Args {
+            pattern: patterns
+                .iter()
+                .map(|p| format!("(?:{}{}", p, close))
+                .collect::<Vec<_>>()
+                .join("|"),
+            output: PathBuf::from(parsed.value_of_os("output").unwrap()),
+            name: parsed.value_of("name").unwrap().to_string(),
+            sparse: parsed.is_present("sparse"),
+            forward_only: parsed.is_present("forward-only"),
+            state_size: parsed.value_of("state-size").map(|s| s.to_string()),
+            endians,
+            anchored: parsed.is_present("anchored"),
+            match_kind,
+            case_insensitive: parsed.is_present("case-insensitive"),
+            ignore_whitespace,
+            dot_matches_new_line: parsed.is_present("dot-matches-new-line"),
+            swap_greed: parsed.is_present("swap-greed"),
+            unicode: !parsed.is_present("no-unicode"),
+            allow_invalid_utf8: parsed.is_present("allow-invalid-utf8"),
+            nest_limit: parse_number(parsed.value_of("nest-limit"))?,
+            minimize: parsed.is_present("minimize"),
+            premultiply: !parsed.is_present("no-premultiply"),
+            byte_classes: !parsed.is_present("no-byte-classes"),
+            dfa_size_limit: parse_number(parsed.value_of("dfa-size-limit"))?,
+            nfa_size_limit: parse_number(parsed.value_of("nfa-size-limit"))?,
+            time_limit: parse_number(parsed.value_of("time-limit"))?
+                .map(Duration::from_secs),
+        })
+    }
+
+    fn builder(&self) -> RegexBuilder {
+        let mut builder = RegexBuilder::new();
+        builder
+            .anchored(self.anchored)
+            .match_kind(self.match_kind)
+            .case_insensitive(self.case_insensitive)
+            .ignore_whitespace(self.ignore_whitespace)
+            .dot_matches_new_line(self.dot_matches_new_line)
+            .swap_greed(self.swap_greed)
+            .unicode(self.unicode)
+            .allow_invalid_utf8(self.allow_invalid_utf8)
+            .minimize(self.minimize)
+            .premultiply(self.premultiply)
+            .byte_classes(self.byte_classes)
+            .dfa_size_limit(self.dfa_size_limit)
+            .nfa_size_limit(self.nfa_size_limit)
+            .time_limit(self.time_limit);
+        if let Some(limit) = self.nest_limit {
+            builder.nest_limit(limit);
+        }
+        builder
+    }
+
+    /// Serialize the DFAs of the given regex using the state identifier
+    /// representation with the given name, and return the name and contents
+    /// of each file to write.
+    fn serialize(
+        &self,
+        re: &Regex,
+        size: &str,
+    ) -> result::Result<Vec<(String, Vec<u8>)>, regex_automata::Error> {
+        match size {
+            "u8" => self.serialize_sized::<u8>(re),
+            "u16" => self.serialize_sized::<u16>(re),
+            "u24" => self.serialize_sized::<U24>(re),
+            "u32" => self.serialize_sized::<u32>(re),
+            _ => self.serialize_sized::<u64>(re),
+        }
+    }
+
+    fn serialize_sized<S: StateID>(
+        &self,
+        re: &Regex,
+    ) -> result::Result<Vec<(String, Vec<u8>)>, regex_automata::Error> {
+        let mut dfas = vec![("fwd", re.forward())];
+        if !self.forward_only {
+            dfas.push(("rev", re.reverse()));
+        }
+        let mut files = vec![];
+        for (kind, dfa) in dfas {
+            for &endian in &self.endians {
+                let name = format!("{}.{}.{}.dfa", self.name, kind, endian);
+                files.push((name, self.to_bytes::<S>(dfa, endian)?));
+            }
+        }
+        Ok(files)
+    }
+
+    fn to_bytes<S: StateID>(
+        &self,
+        dfa: &DenseDFA<Vec<usize>, usize>,
+        endian: &str,
+    ) -> result::Result<Vec<u8>, regex_automata::Error> {
+        let little = endian == "littleendian";
+        if self.sparse {
+            let dfa = dfa.to_sparse_sized::<S>()?;
+            if little {
+                dfa.to_bytes_little_endian()
+            } else {
+                dfa.to_bytes_big_endian()
+            }
+        } else {
+            let dfa = dfa.to_sized::<S>()?;
+            if little {
+                dfa.to_bytes_little_endian()
+            } else {
+                dfa.to_bytes_big_endian()
+            }
+        }
+    }
+}
+
+fn parse_number<N>(value: Option<&str>) -> Result<Option<N>>
+where
+    N: std::str::FromStr,
+    N::Err: Error + 'static,
+{
+    match value {
+        None => Ok(None),
+        Some(value) => Ok(Some(value.parse()?)),
+    }
+}
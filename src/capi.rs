@@ -0,0 +1,875 @@
+/*!
+A C API for compiling, serializing, deserializing and searching with
+regexes.
+
+This module exposes `extern "C"` functions that work with opaque regex
+handles, so that regexes built by this crate can be used from C, or from any
+language that can call C functions, without reimplementing the serialization
+format. The declarations of every function and type in this module, along
+with their documentation, are in `include/regex_automata.h`.
+
+This module is only available when the `capi` feature is enabled. To build a
+shared or static library that C programs can link against, run:
+
+```text
+cargo rustc --release --features capi --crate-type cdylib
+cargo rustc --release --features capi --crate-type staticlib
+```
+
+Each regex is made of a forward and a reverse DFA that use `u32` state
+identifiers, and which are either dense or sparse. Serializing a regex writes
+each of its DFAs in native endianness, and deserializing a regex fully
+validates both DFAs, so that untrusted bytes can be given to
+`ra_regex_deserialize`. Dense DFAs serialized with either endianness may be
+deserialized, but sparse DFAs must have been serialized with the endianness of
+the current target.
+
+Functions that can fail take a pointer to an `ra_error`, which may be null.
+When such a function fails, it returns null, `false` or `RA_ERROR` and, if
+the error pointer is not null, records a description of the failure in it.
+Searches return `RA_MATCH` or `RA_NO_MATCH` otherwise, so that a failed
+search is never mistaken for a miss.
+
+`ra_regex_deserialize` rejects a forward and a reverse DFA that disagree on
+the shortest strings they match, which catches DFAs taken from two different
+regexes. Checking that they match exactly the same strings is as expensive
+as building them, so a pair that passes this check may still disagree. Such
+a pair may report incorrect matches, and a search fails when it finds a match
+with the forward DFA for which the reverse DFA finds no start.
+
+Panics never unwind into C. A panic in any function is reported as a
+failure. (If this library is built with `panic = "abort"`, then a panic
+aborts the process instead.)
+
+# Example
+
+This example is written in Rust, but the C code that does the same is a
+line-by-line translation.
+
+```
+use regex_automata::capi::*;
+
+let pattern = b"foo[0-9]+";
+let err = ra_error_new();
+let re = unsafe {
+    ra_regex_compile(pattern.as_ptr(), pattern.len(), RA_FLAG_SPARSE, err)
+};
+assert!(!re.is_null());
+
+let haystack = b"xfoo123";
+let mut m = RaMatch { start: 0, end: 0 };
+unsafe {
+    let (data, len) = (haystack.as_ptr(), haystack.len());
+    assert_eq!(RA_MATCH, ra_regex_find(re, data, len, &mut m, err));
+    ra_regex_free(re);
+    ra_error_free(err);
+}
+assert_eq!((1, 7), (m.start, m.end));
+```
+*/
+
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::str;
+
+use classes::ByteClasses;
+use dense::{DenseDFA, MatchKind};
+use dfa::DFA;
+use error::Error;
+use language::Graph;
+use regex::{Regex, RegexBuilder};
+use sparse::SparseDFA;
+
+/// Only report matches that start at the beginning of the haystack.
+pub const RA_FLAG_ANCHORED: u32 = 1 << 0;
+/// Match letters case insensitively.
+pub const RA_FLAG_CASEI: u32 = 1 << 1;
+/// Ignore whitespace and allow comments in the pattern.
+pub const RA_FLAG_IGNORE_WHITESPACE: u32 = 1 << 2;
+/// Permit `.` to match `\n`.
+pub const RA_FLAG_DOT_MATCHES_NEW_LINE: u32 = 1 << 3;
+/// Swap the greediness of repetitions.
+pub const RA_FLAG_SWAP_GREED: u32 = 1 << 4;
+/// Disable Unicode mode.
+pub const RA_FLAG_NO_UNICODE: u32 = 1 << 5;
+/// Permit the regex to match invalid UTF-8.
+pub const RA_FLAG_ALLOW_INVALID_UTF8: u32 = 1 << 6;
+/// Report leftmost-longest matches instead of leftmost-first matches.
+pub const RA_FLAG_LEFTMOST_LONGEST: u32 = 1 << 7;
+/// Minimize the DFAs of the regex.
+pub const RA_FLAG_MINIMIZE: u32 = 1 << 8;
+/// Use sparse DFAs instead of dense DFAs.
+pub const RA_FLAG_SPARSE: u32 = 1 << 9;
+
+/// Returned by a search that found a match.
+pub const RA_MATCH: c_int = 1;
+/// Returned by a search that found no match.
+pub const RA_NO_MATCH: c_int = 0;
+/// Returned by a search that failed.
+pub const RA_ERROR: c_int = -1;
+
+/// A compiled regex, known as `ra_regex` in C.
+pub struct RaRegex(Kind);
+
+enum Kind {
+    Dense(Regex<DenseDFA<Vec<u32>, u32>>),
+    Sparse(Regex<SparseDFA<Vec<u8>, u32>>),
+}
+
+/// The error of a failed call, known as `ra_error` in C.
+#[derive(Debug, Default)]
+pub struct RaError {
+    message: Option<CString>,
+}
+
+/// The start and end offsets of a match, known as `ra_match` in C.
+///
+/// The match corresponds to the bytes in the half open range
+/// `[start, end)` of the haystack.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RaMatch {
+    /// The offset of the first byte of the match.
+    pub start: usize,
+    /// The offset one past the last byte of the match.
+    pub end: usize,
+}
+
+/// A sequence of bytes owned by this library, known as `ra_bytes` in C.
+///
+/// It must be freed with `ra_bytes_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RaBytes {
+    /// A pointer to the first byte.
+    pub data: *mut u8,
+    /// The number of bytes.
+    pub len: usize,
+}
+
+impl RaRegex {
+    fn is_match_at(&self, haystack: &[u8], start: usize) -> bool {
+        if start > haystack.len() {
+            return false;
+        }
+        match self.0 {
+            Kind::Dense(ref re) => re.is_match_at(haystack, start),
+            Kind::Sparse(ref re) => re.is_match_at(haystack, start),
+        }
+    }
+
+    fn shortest_match_at(
+        &self,
+        haystack: &[u8],
+        start: usize,
+    ) -> Option<usize> {
+        if start > haystack.len() {
+            return None;
+        }
+        match self.0 {
+            Kind::Dense(ref re) => re.shortest_match_at(haystack, start),
+            Kind::Sparse(ref re) => re.shortest_match_at(haystack, start),
+        }
+    }
+
+    fn find_at(
+        &self,
+        haystack: &[u8],
+        start: usize,
+    ) -> Result<Option<(usize, usize)>, String> {
+        if start > haystack.len() {
+            return Ok(None);
+        }
+        match self.0 {
+            Kind::Dense(ref re) => find_at(re, haystack, start),
+            Kind::Sparse(ref re) => find_at(re, haystack, start),
+        }
+    }
+
+    fn to_bytes(&self) -> Result<(Vec<u8>, Vec<u8>), Error> {
+        match self.0 {
+            Kind::Dense(ref re) => Ok((
+                re.forward().to_bytes_native_endian()?,
+                re.reverse().to_bytes_native_endian()?,
+            )),
+            Kind::Sparse(ref re) => Ok((
+                re.forward().to_bytes_native_endian()?,
+                re.reverse().to_bytes_native_endian()?,
+            )),
+        }
+    }
+
+    fn from_bytes(fwd: &[u8], rev: &[u8]) -> Result<RaRegex, Error> {
+        let sparse = b"rust-regex-automata-sparse-dfa\x00";
+        if fwd.starts_with(sparse) != rev.starts_with(sparse) {
+            return Err(Error::deserialize(
+                "the forward and reverse DFAs must both be dense or sparse",
+            ));
+        }
+        if fwd.starts_with(sparse) {
+            let fwd = SparseDFA::<&[u8], u32>::try_from_bytes(fwd)?;
+            let rev = SparseDFA::<&[u8], u32>::try_from_bytes(rev)?;
+            check_pair(&fwd, &rev)?;
+            let re = Regex::from_dfas(fwd.to_owned(), rev.to_owned());
+            Ok(RaRegex(Kind::Sparse(re)))
+        } else {
+            let fwd = DenseDFA::from_bytes_any_endian(fwd)?;
+            let rev = DenseDFA::from_bytes_any_endian(rev)?;
+            check_pair(&fwd, &rev)?;
+            Ok(RaRegex(Kind::Dense(Regex::from_dfas(fwd, rev))))
+        }
+    }
+}
+
+/// Return an error if the given DFAs could not be the forward and reverse
+/// DFAs of the same regex.
+///
+/// The reverse DFA must be anchored, and each DFA must agree with the other
+/// on the shortest string it matches.
+fn check_pair<F: DFA, R: DFA>(fwd: &F, rev: &R) -> Result<(), Error> {
+    if !rev.is_anchored() {
+        return Err(Error::deserialize("the reverse DFA must be anchored"));
+    }
+    let singletons = ByteClasses::singletons();
+    let fwd_agrees = match Graph::new(fwd, &singletons).shortest_match() {
+        None => true,
+        Some(string) => match fwd.find(&string) {
+            None => false,
+            Some(end) => rev.rfind(&string[..end]).is_some(),
+        },
+    };
+    let rev_agrees = match Graph::new(rev, &singletons).shortest_match() {
+        None => true,
+        Some(mut string) => {
+            // The reverse DFA reads its input backwards.
+            string.reverse();
+            fwd.is_match(&string)
+        }
+    };
+    if !fwd_agrees || !rev_agrees {
+        return Err(Error::deserialize(
+            "the forward and reverse DFAs do not match the same strings",
+        ));
+    }
+    Ok(())
+}
+
+/// Search for the leftmost match at or after `start`, failing if the
+/// reverse DFA finds no start for a match found by the forward DFA.
+fn find_at<D: DFA>(
+    re: &Regex<D>,
+    haystack: &[u8],
+    start: usize,
+) -> Result<Option<(usize, usize)>, String> {
+    let end = match re.forward().find_at(haystack, start) {
+        None => return Ok(None),
+        Some(end) => end,
+    };
+    match re.reverse().rfind(&haystack[start..end]) {
+        None => Err("the reverse DFA found no start for a match".to_string()),
+        Some(i) => Ok(Some((start + i, end))),
+    }
+}
+
+/// Convert the result of a search to its status, recording the error in
+/// `err` if the search failed. The match, if any, is given to `found`.
+unsafe fn status<T, F: FnOnce(T)>(
+    result: Result<Option<T>, String>,
+    err: *mut RaError,
+    found: F,
+) -> c_int {
+    match result {
+        Ok(None) => RA_NO_MATCH,
+        Ok(Some(m)) => {
+            found(m);
+            RA_MATCH
+        }
+        Err(message) => {
+            set_error(err, &message);
+            RA_ERROR
+        }
+    }
+}
+
+/// Run the given closure, converting a panic into an error so that it does
+/// not unwind into C.
+fn catch<T, F: FnOnce() -> Result<T, String>>(f: F) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = match payload.downcast_ref::<&str>() {
+                Some(message) => message.to_string(),
+                None => match payload.downcast_ref::<String>() {
+                    Some(message) => message.clone(),
+                    None => "unknown panic".to_string(),
+                },
+            };
+            Err(format!("regex-automata panicked: {}", message))
+        }
+    }
+}
+
+/// Return the `len` bytes starting at `data`, which may be null when `len`
+/// is zero.
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+/// Record the given error message in `err`, if it is not null.
+unsafe fn set_error(err: *mut RaError, message: &str) {
+    if !err.is_null() {
+        let message = message.replace('\0', "\\0");
+        (*err).message = CString::new(message).ok();
+    }
+}
+
+/// Convert the given bytes to a `RaBytes` that owns them.
+fn into_raw_bytes(bytes: Vec<u8>) -> RaBytes {
+    let len = bytes.len();
+    let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+    RaBytes { data, len }
+}
+
+/// Allocate a new error, which must be freed with `ra_error_free`.
+#[no_mangle]
+pub extern "C" fn ra_error_new() -> *mut RaError {
+    Box::into_raw(Box::new(RaError::default()))
+}
+
+/// Free an error allocated by `ra_error_new`.
+///
+/// # Safety
+///
+/// `err` must be null or have been returned by `ra_error_new`, and must not
+/// be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn ra_error_free(err: *mut RaError) {
+    if !err.is_null() {
+        drop(Box::from_raw(err));
+    }
+}
+
+/// Return the message of the last failure recorded in `err` as a NUL
+/// terminated string, or an empty string if no failure has been recorded.
+///
+/// The string returned is valid until `err` is freed or given to another
+/// call.
+///
+/// # Safety
+///
+/// `err` must have been returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_error_message(
+    err: *const RaError,
+) -> *const c_char {
+    match (*err).message {
+        Some(ref message) => message.as_ptr(),
+        None => b"\0".as_ptr() as *const c_char,
+    }
+}
+
+/// Compile the given UTF-8 pattern with the given `RA_FLAG_*` flags.
+///
+/// On success, this returns a regex that must be freed with
+/// `ra_regex_free`. On failure, this returns null.
+///
+/// # Safety
+///
+/// `pattern` must point to `len` readable bytes and `err` must be null or
+/// have been returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_compile(
+    pattern: *const u8,
+    len: usize,
+    flags: u32,
+    err: *mut RaError,
+) -> *mut RaRegex {
+    let result = catch(|| {
+        let pattern = str::from_utf8(bytes(pattern, len))
+            .map_err(|e| format!("pattern is not valid UTF-8: {}", e))?;
+        compile(pattern, flags).map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(re) => Box::into_raw(Box::new(re)),
+        Err(message) => {
+            set_error(err, &message);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Compile the given pattern with the given `RA_FLAG_*` flags.
+fn compile(pattern: &str, flags: u32) -> Result<RaRegex, Error> {
+    let has = |flag: u32| flags & flag != 0;
+    let mut builder = RegexBuilder::new();
+    builder
+        .anchored(has(RA_FLAG_ANCHORED))
+        .case_insensitive(has(RA_FLAG_CASEI))
+        .ignore_whitespace(has(RA_FLAG_IGNORE_WHITESPACE))
+        .dot_matches_new_line(has(RA_FLAG_DOT_MATCHES_NEW_LINE))
+        .swap_greed(has(RA_FLAG_SWAP_GREED))
+        .unicode(!has(RA_FLAG_NO_UNICODE))
+        .allow_invalid_utf8(has(RA_FLAG_ALLOW_INVALID_UTF8))
+        .minimize(has(RA_FLAG_MINIMIZE));
+    if has(RA_FLAG_LEFTMOST_LONGEST) {
+        builder.match_kind(MatchKind::LeftmostLongest);
+    }
+    let kind = if has(RA_FLAG_SPARSE) {
+        Kind::Sparse(builder.build_with_size_sparse(pattern)?)
+    } else {
+        Kind::Dense(builder.build_with_size(pattern)?)
+    };
+    Ok(RaRegex(kind))
+}
+
+/// Free a regex returned by `ra_regex_compile` or `ra_regex_deserialize`.
+///
+/// # Safety
+///
+/// `re` must be null or have been returned by `ra_regex_compile` or
+/// `ra_regex_deserialize`, and must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_free(re: *mut RaRegex) {
+    if !re.is_null() {
+        drop(Box::from_raw(re));
+    }
+}
+
+/// Search for whether the regex matches anywhere in the haystack at or
+/// after `start`.
+///
+/// This returns `RA_MATCH` if there is a match, `RA_NO_MATCH` if there is
+/// none, including when `start` is greater than `len`, and `RA_ERROR` if the
+/// search failed. As with `Regex::is_match_at`, the search takes the bytes
+/// before `start` into account, so an anchored regex can only match when
+/// `start` is zero.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `haystack` must point to `len` readable
+/// bytes and `err` must be null or have been returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_is_match_at(
+    re: *const RaRegex,
+    haystack: *const u8,
+    len: usize,
+    start: usize,
+    err: *mut RaError,
+) -> c_int {
+    let result = catch(|| {
+        if (*re).is_match_at(bytes(haystack, len), start) {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    });
+    status(result, err, |()| {})
+}
+
+/// Like `ra_regex_is_match_at`, but search from the start of the haystack.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `haystack` must point to `len` readable
+/// bytes and `err` must be null or have been returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_is_match(
+    re: *const RaRegex,
+    haystack: *const u8,
+    len: usize,
+    err: *mut RaError,
+) -> c_int {
+    ra_regex_is_match_at(re, haystack, len, 0, err)
+}
+
+/// Search for the end of the shortest match at or after `start`, without
+/// finding where it starts.
+///
+/// If a match is found, then this writes its end to `end` and returns
+/// `RA_MATCH`. Otherwise, this returns `RA_NO_MATCH`, including when `start`
+/// is greater than `len`, or `RA_ERROR` if the search failed, and `end` is
+/// not modified.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `haystack` must point to `len` readable
+/// bytes, `end` must be writable and `err` must be null or have been
+/// returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_shortest_match_at(
+    re: *const RaRegex,
+    haystack: *const u8,
+    len: usize,
+    start: usize,
+    end: *mut usize,
+    err: *mut RaError,
+) -> c_int {
+    let result =
+        catch(|| Ok((*re).shortest_match_at(bytes(haystack, len), start)));
+    status(result, err, |e| *end = e)
+}
+
+/// Search for the leftmost match at or after `start`.
+///
+/// If a match is found, then this writes its offsets to `m` and returns
+/// `RA_MATCH`. Otherwise, this returns `RA_NO_MATCH`, including when `start`
+/// is greater than `len`, or `RA_ERROR` if the search failed, and `m` is not
+/// modified. Matches are reported using offsets into the entire haystack, so
+/// all of the matches in a haystack can be found by repeatedly searching
+/// from the end of the previous match, taking care to advance past empty
+/// matches.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `haystack` must point to `len` readable
+/// bytes, `m` must be writable and `err` must be null or have been returned
+/// by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_find_at(
+    re: *const RaRegex,
+    haystack: *const u8,
+    len: usize,
+    start: usize,
+    m: *mut RaMatch,
+    err: *mut RaError,
+) -> c_int {
+    let result = catch(|| (*re).find_at(bytes(haystack, len), start));
+    status(result, err, |(s, e)| *m = RaMatch { start: s, end: e })
+}
+
+/// Like `ra_regex_find_at`, but search from the start of the haystack.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `haystack` must point to `len` readable
+/// bytes, `m` must be writable and `err` must be null or have been returned
+/// by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_find(
+    re: *const RaRegex,
+    haystack: *const u8,
+    len: usize,
+    m: *mut RaMatch,
+    err: *mut RaError,
+) -> c_int {
+    ra_regex_find_at(re, haystack, len, 0, m, err)
+}
+
+/// Serialize the forward and reverse DFAs of the regex in native
+/// endianness, writing them to `fwd` and `rev`.
+///
+/// On success, this returns true, and both `fwd` and `rev` must be freed
+/// with `ra_bytes_free`. On failure, this returns false and neither is
+/// modified.
+///
+/// # Safety
+///
+/// `re` must be a valid regex, `fwd` and `rev` must be writable and `err`
+/// must be null or have been returned by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_serialize(
+    re: *const RaRegex,
+    fwd: *mut RaBytes,
+    rev: *mut RaBytes,
+    err: *mut RaError,
+) -> bool {
+    match catch(|| (*re).to_bytes().map_err(|e| e.to_string())) {
+        Ok((f, r)) => {
+            *fwd = into_raw_bytes(f);
+            *rev = into_raw_bytes(r);
+            true
+        }
+        Err(message) => {
+            set_error(err, &message);
+            false
+        }
+    }
+}
+
+/// Deserialize a regex from the serialized forward and reverse DFAs given,
+/// which must both be dense or both be sparse.
+///
+/// The reverse DFA must be anchored, as the reverse DFA of every regex is,
+/// and the two DFAs must agree on the shortest strings they match. This
+/// does not prove that they were built from the same regex, so searching
+/// with DFAs that otherwise disagree may report incorrect matches or fail.
+///
+/// The bytes given are fully validated and copied, so they may be freed
+/// once this returns. On success, this returns a regex that must be freed
+/// with `ra_regex_free`. On failure, this returns null.
+///
+/// # Safety
+///
+/// `fwd` must point to `fwd_len` readable bytes, `rev` must point to
+/// `rev_len` readable bytes and `err` must be null or have been returned
+/// by `ra_error_new`.
+#[no_mangle]
+pub unsafe extern "C" fn ra_regex_deserialize(
+    fwd: *const u8,
+    fwd_len: usize,
+    rev: *const u8,
+    rev_len: usize,
+    err: *mut RaError,
+) -> *mut RaRegex {
+    let result = catch(|| {
+        RaRegex::from_bytes(bytes(fwd, fwd_len), bytes(rev, rev_len))
+            .map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(re) => Box::into_raw(Box::new(re)),
+        Err(message) => {
+            set_error(err, &message);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free bytes returned by `ra_regex_serialize`.
+///
+/// # Safety
+///
+/// `bytes` must have been returned by `ra_regex_serialize` and must not be
+/// used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn ra_bytes_free(bytes: RaBytes) {
+    let slice = ptr::slice_from_raw_parts_mut(bytes.data, bytes.len);
+    drop(Box::from_raw(slice));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    unsafe fn compile(pattern: &str, flags: u32) -> *mut RaRegex {
+        let re = ra_regex_compile(
+            pattern.as_ptr(),
+            pattern.len(),
+            flags,
+            ptr::null_mut(),
+        );
+        assert!(!re.is_null());
+        re
+    }
+
+    unsafe fn find(re: *const RaRegex, haystack: &[u8]) -> Option<RaMatch> {
+        let mut m = RaMatch::default();
+        let (data, len) = (haystack.as_ptr(), haystack.len());
+        match ra_regex_find(re, data, len, &mut m, ptr::null_mut()) {
+            RA_MATCH => Some(m),
+            RA_NO_MATCH => None,
+            status => panic!("unexpected status: {}", status),
+        }
+    }
+
+    #[test]
+    fn search() {
+        unsafe {
+            let re = compile("Sam|Samwise", RA_FLAG_CASEI);
+            let m = find(re, b"xSAMWISE");
+            assert_eq!(Some(RaMatch { start: 1, end: 4 }), m);
+            assert_eq!(None, find(re, b"xyz"));
+            let err = ptr::null_mut();
+            let sam = b"sam".as_ptr();
+            assert_eq!(RA_MATCH, ra_regex_is_match(re, sam, 3, err));
+            assert_eq!(RA_NO_MATCH, ra_regex_is_match_at(re, sam, 3, 1, err));
+            ra_regex_free(re);
+
+            let re = compile("Sam|Samwise", RA_FLAG_LEFTMOST_LONGEST);
+            let m = find(re, b"Samwise");
+            assert_eq!(Some(RaMatch { start: 0, end: 7 }), m);
+            let mut end = 0;
+            let status = ra_regex_shortest_match_at(
+                re,
+                b"Samwise".as_ptr(),
+                7,
+                0,
+                &mut end,
+                err,
+            );
+            assert_eq!(RA_MATCH, status);
+            assert_eq!(3, end);
+            ra_regex_free(re);
+
+            let re = compile("a+", RA_FLAG_ANCHORED | RA_FLAG_SPARSE);
+            assert_eq!(None, find(re, b"baa"));
+            assert_eq!(Some(RaMatch { start: 0, end: 2 }), find(re, b"aab"));
+            let mut m = RaMatch::default();
+            let status =
+                ra_regex_find_at(re, b"baa".as_ptr(), 3, 1, &mut m, err);
+            assert_eq!(RA_NO_MATCH, status);
+            ra_regex_free(re);
+
+            let re = compile("a+", 0);
+            let status =
+                ra_regex_find_at(re, b"aba".as_ptr(), 3, 1, &mut m, err);
+            assert_eq!(RA_MATCH, status);
+            assert_eq!(RaMatch { start: 2, end: 3 }, m);
+            ra_regex_free(re);
+        }
+    }
+
+    #[test]
+    fn serialize_roundtrip() {
+        for &flags in &[0, RA_FLAG_SPARSE, RA_FLAG_MINIMIZE] {
+            unsafe {
+                let re = compile("foo[0-9]+", flags);
+                let mut fwd = RaBytes { data: ptr::null_mut(), len: 0 };
+                let mut rev = RaBytes { data: ptr::null_mut(), len: 0 };
+                let err = ra_error_new();
+                assert!(ra_regex_serialize(re, &mut fwd, &mut rev, err));
+                ra_regex_free(re);
+
+                let re = ra_regex_deserialize(
+                    fwd.data, fwd.len, rev.data, rev.len, err,
+                );
+                assert!(!re.is_null());
+                let m = find(re, b"xfoo123");
+                assert_eq!(Some(RaMatch { start: 1, end: 7 }), m);
+                ra_regex_free(re);
+
+                // Mixing a forward and a reverse DFA of different kinds, or
+                // truncating a DFA, is an error.
+                let re = ra_regex_deserialize(
+                    fwd.data, fwd.len, b"foo".as_ptr(), 3, err,
+                );
+                assert!(re.is_null());
+                let message = CStr::from_ptr(ra_error_message(err));
+                assert!(!message.to_bytes().is_empty());
+                let re = ra_regex_deserialize(
+                    fwd.data, fwd.len - 1, rev.data, rev.len, err,
+                );
+                assert!(re.is_null());
+
+                ra_bytes_free(fwd);
+                ra_bytes_free(rev);
+                ra_error_free(err);
+            }
+        }
+    }
+
+    unsafe fn compile_bytes(pattern: &str, flags: u32) -> (RaBytes, RaBytes) {
+        let re = compile(pattern, flags);
+        let mut fwd = RaBytes { data: ptr::null_mut(), len: 0 };
+        let mut rev = RaBytes { data: ptr::null_mut(), len: 0 };
+        assert!(ra_regex_serialize(re, &mut fwd, &mut rev, ptr::null_mut()));
+        ra_regex_free(re);
+        (fwd, rev)
+    }
+
+    #[test]
+    fn start_out_of_range() {
+        unsafe {
+            for &flags in &[0, RA_FLAG_SPARSE] {
+                let re = compile("a*", flags);
+                let (haystack, len) = (b"aa".as_ptr(), 2);
+                let err = ptr::null_mut();
+                let status = ra_regex_is_match_at(re, haystack, len, 3, err);
+                assert_eq!(RA_NO_MATCH, status);
+                let mut end = 0;
+                let status = ra_regex_shortest_match_at(
+                    re, haystack, len, 3, &mut end, err,
+                );
+                assert_eq!(RA_NO_MATCH, status);
+                let mut m = RaMatch::default();
+                let status =
+                    ra_regex_find_at(re, haystack, len, 3, &mut m, err);
+                assert_eq!(RA_NO_MATCH, status);
+                let status =
+                    ra_regex_find_at(re, haystack, len, 2, &mut m, err);
+                assert_eq!(RA_MATCH, status);
+                assert_eq!(RaMatch { start: 2, end: 2 }, m);
+                ra_regex_free(re);
+            }
+        }
+    }
+
+    #[test]
+    fn mismatched_dfas() {
+        for &flags in &[0, RA_FLAG_SPARSE] {
+            unsafe {
+                let (a_fwd, a_rev) = compile_bytes("a", flags);
+                let (b_fwd, b_rev) = compile_bytes("b", flags);
+                let (ab_fwd, ab_rev) = compile_bytes("a|bc", flags);
+                let err = ra_error_new();
+
+                // DFAs that disagree on their shortest matches are rejected.
+                let re = ra_regex_deserialize(
+                    a_fwd.data, a_fwd.len, b_rev.data, b_rev.len, err,
+                );
+                assert!(re.is_null());
+                let message = CStr::from_ptr(ra_error_message(err));
+                assert!(message.to_str().unwrap().contains("same strings"));
+                let re = ra_regex_deserialize(
+                    b_fwd.data, b_fwd.len, a_rev.data, a_rev.len, err,
+                );
+                assert!(re.is_null());
+
+                // DFAs that only disagree on longer matches can't be
+                // detected, but a search that finds a match without a start
+                // fails instead of reporting no match.
+                let re = ra_regex_deserialize(
+                    ab_fwd.data, ab_fwd.len, a_rev.data, a_rev.len, err,
+                );
+                assert!(!re.is_null());
+                let m = find(re, b"xa");
+                assert_eq!(Some(RaMatch { start: 1, end: 2 }), m);
+                let mut m = RaMatch::default();
+                let status = ra_regex_find(re, b"bc".as_ptr(), 2, &mut m, err);
+                assert_eq!(RA_ERROR, status);
+                let message = CStr::from_ptr(ra_error_message(err));
+                assert!(message.to_str().unwrap().contains("no start"));
+                ra_regex_free(re);
+
+                // An unanchored DFA can never be a reverse DFA.
+                let re = ra_regex_deserialize(
+                    a_fwd.data, a_fwd.len, b_fwd.data, b_fwd.len, err,
+                );
+                assert!(re.is_null());
+                let message = CStr::from_ptr(ra_error_message(err));
+                assert!(message.to_str().unwrap().contains("anchored"));
+
+                ra_bytes_free(a_fwd);
+                ra_bytes_free(a_rev);
+                ra_bytes_free(b_fwd);
+                ra_bytes_free(b_rev);
+                ra_bytes_free(ab_fwd);
+                ra_bytes_free(ab_rev);
+                ra_error_free(err);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        unsafe {
+            let err = ra_error_new();
+            let message = CStr::from_ptr(ra_error_message(err));
+            assert!(message.to_bytes().is_empty());
+
+            let re = ra_regex_compile(b"(".as_ptr(), 1, 0, err);
+            assert!(re.is_null());
+            let message = CStr::from_ptr(ra_error_message(err));
+            assert!(message.to_str().unwrap().contains("unclosed group"));
+
+            let re = ra_regex_compile(b"\xFF".as_ptr(), 1, 0, err);
+            assert!(re.is_null());
+            let message = CStr::from_ptr(ra_error_message(err));
+            assert!(message.to_str().unwrap().contains("UTF-8"));
+
+            // A null error is permitted.
+            let re = ra_regex_compile(ptr::null(), 0, 0, ptr::null_mut());
+            assert!(!re.is_null());
+            ra_regex_free(re);
+            ra_error_free(err);
+        }
+    }
+}
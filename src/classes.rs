@@ -5,19 +5,48 @@ use core::fmt;
 /// This is used in a DFA to reduce the size of the transition table. This can
 /// have a particularly large impact not only on the total size of a dense DFA,
 /// but also on compile times.
+///
+/// Any two bytes in the same class lead to the same state from every state of
+/// the DFA. Each class is a contiguous range of bytes, and classes are
+/// numbered in increasing order of their bytes, starting at `0`. A DFA that
+/// does not use byte classes puts every byte in a class of its own.
+///
+/// The byte classes of a DFA are returned by
+/// [`DenseDFA::byte_classes`](enum.DenseDFA.html#method.byte_classes) and
+/// [`SparseDFA::byte_classes`](enum.SparseDFA.html#method.byte_classes).
+/// Using them, a custom search routine or an external prefilter can
+/// translate bytes into classes exactly the way the DFA does.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::dense;
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new().anchored(true).build("[a-c]+")?;
+/// let classes = dfa.byte_classes();
+/// assert_eq!(3, classes.alphabet_len());
+/// assert_eq!(classes.get(b'a'), classes.get(b'c'));
+/// assert_ne!(classes.get(b'a'), classes.get(b'd'));
+/// assert_eq!((b'a', b'c'), classes.bytes(classes.get(b'b')));
+///
+/// let reps: Vec<u8> = classes.representatives().collect();
+/// assert_eq!(vec![0, b'a', b'd'], reps);
+/// # Ok(()) }; example().unwrap()
+/// ```
 #[derive(Clone, Copy)]
 pub struct ByteClasses([u8; 256]);
 
 impl ByteClasses {
     /// Creates a new set of equivalence classes where all bytes are mapped to
     /// the same class.
-    pub fn empty() -> ByteClasses {
+    pub(crate) fn empty() -> ByteClasses {
         ByteClasses([0; 256])
     }
 
     /// Creates a new set of equivalence classes where each byte belongs to
     /// its own equivalence class.
-    pub fn singletons() -> ByteClasses {
+    pub(crate) fn singletons() -> ByteClasses {
         let mut classes = ByteClasses::empty();
         for i in 0..256 {
             classes.set(i as u8, i as u8);
@@ -25,10 +54,16 @@ impl ByteClasses {
         classes
     }
 
+    /// Creates a set of equivalence classes from a map of every byte to its
+    /// class.
+    pub(crate) const fn from_array(classes: [u8; 256]) -> ByteClasses {
+        ByteClasses(classes)
+    }
+
     /// Copies the byte classes given. The given slice must have length 0 or
     /// length 256. Slices of length 0 are treated as singletons (every byte
     /// is its own class).
-    pub fn from_slice(slice: &[u8]) -> ByteClasses {
+    pub(crate) fn from_slice(slice: &[u8]) -> ByteClasses {
         assert!(slice.is_empty() || slice.len() == 256);
 
         if slice.is_empty() {
@@ -44,11 +79,14 @@ impl ByteClasses {
 
     /// Set the equivalence class for the given byte.
     #[inline]
-    pub fn set(&mut self, byte: u8, class: u8) {
+    pub(crate) fn set(&mut self, byte: u8, class: u8) {
         self.0[byte as usize] = class;
     }
 
     /// Get the equivalence class for the given byte.
+    ///
+    /// The class returned is always less than
+    /// [`alphabet_len`](struct.ByteClasses.html#method.alphabet_len).
     #[inline]
     pub fn get(&self, byte: u8) -> u8 {
         self.0[byte as usize]
@@ -57,7 +95,7 @@ impl ByteClasses {
     /// Get the equivalence class for the given byte while forcefully
     /// eliding bounds checks.
     #[inline]
-    pub unsafe fn get_unchecked(&self, byte: u8) -> u8 {
+    pub(crate) unsafe fn get_unchecked(&self, byte: u8) -> u8 {
         *self.0.get_unchecked(byte as usize)
     }
 
@@ -86,11 +124,28 @@ impl ByteClasses {
     /// hasn't been converted to equivalence classes yet. Picking an arbitrary
     /// byte from each equivalence class then permits a full exploration of
     /// the NFA instead of using every possible byte value.
-    #[cfg(feature = "std")]
-    pub fn representatives(&self) -> ByteClassRepresentatives {
+    ///
+    /// The representatives are yielded in the order of their classes, and
+    /// each one is the smallest byte in its class.
+    pub fn representatives(&self) -> ByteClassRepresentatives<'_> {
         ByteClassRepresentatives { classes: self, byte: 0, last_class: None }
     }
 
+    /// Returns the first and last byte in the given equivalence class.
+    ///
+    /// Since every class is a contiguous range of bytes, this is the
+    /// inclusive range of bytes in the class.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the class is not less than
+    /// [`alphabet_len`](struct.ByteClasses.html#method.alphabet_len).
+    pub fn bytes(&self, class: u8) -> (u8, u8) {
+        let start = (0..256).position(|b| self.get(b as u8) == class).unwrap();
+        let len = self.0[start..].iter().take_while(|&&c| c == class).count();
+        (start as u8, (start + len - 1) as u8)
+    }
+
     /// Returns all of the bytes in the given equivalence class.
     ///
     /// The second element in the tuple indicates the number of elements in
@@ -123,7 +178,9 @@ impl fmt::Debug for ByteClasses {
 }
 
 /// An iterator over representative bytes from each equivalence class.
-#[cfg(feature = "std")]
+///
+/// This is returned by
+/// [`ByteClasses::representatives`](struct.ByteClasses.html#method.representatives).
 #[derive(Debug)]
 pub struct ByteClassRepresentatives<'a> {
     classes: &'a ByteClasses,
@@ -131,7 +188,6 @@ pub struct ByteClassRepresentatives<'a> {
     last_class: Option<u8>,
 }
 
-#[cfg(feature = "std")]
 impl<'a> Iterator for ByteClassRepresentatives<'a> {
     type Item = u8;
 
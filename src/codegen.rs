@@ -1,104 +1,282 @@
-// This module is unused. It was written as an experiment to get a ballpark
-// idea of what state machines look like when translated to Rust code, and
-// in particular, an idea of how much code it generates. The implementation
-// below isn't optimal with respect to size, but the result wasn't exactly
-// small. At some point, we should pursue building this out beyond
-// experimentation, and in particular, probably provide a command line tool
-// and/or a macro. It's a fair bit of work, so I abandoned it for the initial
-// release. ---AG
-
-use std::collections::HashMap;
-use std::io::Write;
-
-use dense::DFA;
+/*!
+Write DFAs as Rust source code.
+
+The functions in this module write a DFA as a Rust source file containing a
+`static` DFA whose transition table is stored in a `static` array. Including
+the generated file with `include!` makes the DFA available without
+deserializing it at runtime, without any unsafe reinterpretation of bytes and
+without making any assumptions about the alignment of bytes. Since the
+generated code only depends on the `const fn` constructors
+[`DenseDFA::from_parts`](../enum.DenseDFA.html#method.from_parts) and
+[`SparseDFA::from_parts`](../enum.SparseDFA.html#method.from_parts), it can
+be used in `no_std` binaries as well.
+
+This is similar in spirit to how
+[`ucd-generate`](https://github.com/BurntSushi/ucd-generate) generates Unicode
+tables. Typically, a DFA is built and written in a separate program or in a
+build script, and the source file is written to disk. The code generation
+routines are only available when the `std` feature is enabled, but the
+generated code works regardless of which features are enabled.
+
+Given a DFA named `NAME`, the generated file defines a public static `NAME`,
+along with private items prefixed with `NAME_` that hold its byte classes and
+transition table. The name should therefore be written in `SCREAMING_CASE`.
+
+The state identifiers of a generated DFA are written as the unsigned integer
+type of the same size as the DFA's state identifier representation, or as
+[`U24`](../struct.U24.html) for 3 byte identifiers. In particular, `NonZero`
+identifiers are written as their integer counterparts, and `usize`
+identifiers are written as either `u32` or `u64`, depending on the size of
+`usize` on the target that generates the code. Dense DFAs are written
+independently of endianness. Since the transition table of a sparse DFA is a
+sequence of bytes in the endianness of the target, it is written twice, once
+for each endianness, and the one used is selected with `cfg` attributes.
+
+# Example
+
+This writes the source code for a DFA. The generated file can then be
+included with `include!("dfa.rs")`, after which the DFA can be used as
+`NUMBERS.find(b"foo123")`.
+
+```
+use regex_automata::{codegen, dense};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let dfa = dense::Builder::new().build("[0-9]+")?.to_u16()?;
+let mut buf = vec![];
+codegen::write_dense("NUMBERS", &dfa, &mut buf).unwrap();
+
+let code = String::from_utf8(buf).unwrap();
+assert!(code.contains(
+    "pub static NUMBERS: ::regex_automata::DenseDFA<&'static [u16], u16>",
+));
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::io;
+use std::mem::size_of;
+
+use classes::ByteClasses;
+use dense::DenseDFA;
+use sparse::SparseDFA;
 use state_id::StateID;
 
-macro_rules! wstr {
-    ($($tt:tt)*) => { write!($($tt)*).unwrap() }
-}
+/// The maximum length of a line of a generated array.
+const LINE_WIDTH: usize = 79;
 
-macro_rules! wstrln {
-    ($($tt:tt)*) => { writeln!($($tt)*).unwrap() }
+/// Write the given dense DFA to `wtr` as Rust source code defining a static
+/// DFA named `name`.
+///
+/// The static written has type `DenseDFA<&'static [S], S>`, where `S` is
+/// the integer type corresponding to the DFA's state identifier
+/// representation. For example, a DFA using `u16` state identifiers is
+/// written as a `DenseDFA<&'static [u16], u16>`.
+///
+/// If `name` is not a valid Rust identifier, or if the size of the state
+/// identifier representation of the DFA is not 1, 2, 3, 4 or 8 bytes, then
+/// this returns an error with kind `InvalidInput`.
+pub fn write_dense<T: AsRef<[S]>, S: StateID, W: io::Write>(
+    name: &str,
+    dfa: &DenseDFA<T, S>,
+    wtr: W,
+) -> io::Result<()> {
+    dfa.write_rust(name, wtr)
 }
 
-pub fn is_match_forward<S: StateID>(dfa: &DFA<S>) -> String {
-    let names = state_variant_names(dfa);
-
-    let mut buf = vec![];
-    wstrln!(buf, "pub fn is_match(input: &[u8])  -> bool {{");
-    if dfa.is_match_state(dfa.start()) {
-        wstrln!(buf, "    return true;");
-        wstrln!(buf, "}}");
-        return String::from_utf8(buf).unwrap();
-    }
+/// Write the given sparse DFA to `wtr` as Rust source code defining a static
+/// DFA named `name`.
+///
+/// The static written has type `SparseDFA<&'static [u8], S>`, where `S` is
+/// the integer type corresponding to the DFA's state identifier
+/// representation.
+///
+/// If `name` is not a valid Rust identifier, or if the size of the state
+/// identifier representation of the DFA is not 1, 2, 3, 4 or 8 bytes, then
+/// this returns an error with kind `InvalidInput`.
+pub fn write_sparse<T: AsRef<[u8]>, S: StateID, W: io::Write>(
+    name: &str,
+    dfa: &SparseDFA<T, S>,
+    wtr: W,
+) -> io::Result<()> {
+    dfa.write_rust(name, wtr)
+}
 
-    wstrln!(buf, "{}", state_enum_def(dfa, &names));
+/// The pieces of a DFA that are written as Rust source code.
+///
+/// Each DFA builds this from its internal representation.
+pub(crate) struct Source<'a> {
+    /// The name of the DFA type, e.g., `DenseDFA`.
+    pub(crate) kind: &'static str,
+    /// A short description of the DFA, written in a comment.
+    pub(crate) summary: String,
+    /// The element type of the transition table.
+    pub(crate) table_type: &'static str,
+    /// The state identifier type, from `id_type`.
+    pub(crate) id_type: &'static str,
+    /// The arguments given to `from_parts` before the byte classes, each
+    /// with a comment naming it.
+    pub(crate) fields: Vec<(String, &'static str)>,
+    /// The byte classes of the DFA.
+    pub(crate) byte_classes: &'a ByteClasses,
+    /// The elements of the transition table. If the table depends on the
+    /// target, then there is one table for each value of the `cfg`
+    /// predicate given along with it.
+    pub(crate) tables: Vec<(Option<&'static str>, Vec<String>)>,
+}
 
-    wstrln!(buf, "    let mut state = {};", names[&dfa.start()]);
-    wstrln!(buf, "    for &b in input.iter() {{");
-    wstrln!(buf, "        state = match state {{");
-    for (id, s) in dfa.iter() {
-        if dfa.is_match_state(id) {
-            continue;
+impl<'a> Source<'a> {
+    /// Write the source code for this DFA as a static named `name`.
+    pub(crate) fn write<W: io::Write>(
+        &self,
+        name: &str,
+        mut wtr: W,
+    ) -> io::Result<()> {
+        check_name(name)?;
+        writeln!(wtr, "// This file was generated by regex-automata.")?;
+        writeln!(wtr, "// It is {}.", self.summary)?;
+        writeln!(wtr)?;
+        writeln!(
+            wtr,
+            "pub static {}: ::regex_automata::{}<&'static [{}], {}> = \
+             unsafe {{",
+            name, self.kind, self.table_type, self.id_type,
+        )?;
+        writeln!(wtr, "    ::regex_automata::{}::from_parts(", self.kind)?;
+        for &(ref value, comment) in &self.fields {
+            writeln!(wtr, "        {}, // {}", value, comment)?;
         }
+        writeln!(wtr, "        {}_BYTE_CLASSES,", name)?;
+        writeln!(wtr, "        &{}_TRANS,", name)?;
+        writeln!(wtr, "    )")?;
+        writeln!(wtr, "}};")?;
 
-        wstrln!(buf, "            {} => {{", &names[&id]);
-        wstrln!(buf, "                match b {{");
-        for (start, end, next_id) in s.sparse_transitions() {
-            if dfa.is_match_state(next_id) {
-                wstrln!(buf, "                    {:?}...{:?} => return true,", start, end);
-            } else {
-                if start == end {
-                    wstrln!(buf, "                    {:?} => {},", start, &names[&next_id]);
-                } else {
-                    wstrln!(buf, "                    {:?}...{:?} => {},", start, end, &names[&next_id]);
-                }
+        let classes: Vec<String> = (0..256)
+            .map(|b| self.byte_classes.get(b as u8).to_string())
+            .collect();
+        writeln!(wtr)?;
+        let decl = format!("const {}_BYTE_CLASSES: [u8; 256]", name);
+        write_array(&mut wtr, &decl, &classes)?;
+        for &(cfg, ref table) in &self.tables {
+            writeln!(wtr)?;
+            if let Some(cfg) = cfg {
+                writeln!(wtr, "#[cfg({})]", cfg)?;
             }
+            let decl = format!(
+                "static {}_TRANS: [{}; {}]",
+                name,
+                self.table_type,
+                table.len(),
+            );
+            write_array(&mut wtr, &decl, table)?;
         }
-        wstrln!(buf, "                    _ => S::S0,");
-        wstrln!(buf, "                }}");
-        wstrln!(buf, "            }}");
+        Ok(())
+    }
+}
+
+/// Returns the type used to write state identifiers of type `S`.
+///
+/// If no such type exists, then this returns an error.
+pub(crate) fn id_type<S: StateID>() -> io::Result<&'static str> {
+    match size_of::<S>() {
+        1 => Ok("u8"),
+        2 => Ok("u16"),
+        3 => Ok("::regex_automata::U24"),
+        4 => Ok("u32"),
+        8 => Ok("u64"),
+        size => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "state size of {} not supported, must be 1, 2, 3, 4 or 8",
+                size,
+            ),
+        )),
+    }
+}
+
+/// Returns a constant expression for the given state identifier, with the
+/// type returned by `id_type`.
+pub(crate) fn id_literal<S: StateID>(id: S) -> String {
+    if size_of::<S>() == 3 {
+        format!("::regex_automata::U24::new({})", id.to_usize())
+    } else {
+        id.to_usize().to_string()
     }
-    wstrln!(buf, "        }};");
-    wstrln!(buf, "    }}");
+}
 
-    wstrln!(buf, "    false");
-    wstrln!(buf, "}}");
-    String::from_utf8(buf).unwrap()
+/// Returns an error if the given name is not a valid Rust identifier.
+fn check_name(name: &str) -> io::Result<()> {
+    let mut chars = name.chars();
+    let valid = match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+            chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        }
+        _ => false,
+    };
+    if !valid || name == "_" {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{}' is not a valid Rust identifier", name),
+        ));
+    }
+    Ok(())
 }
 
-fn state_enum_def<S: StateID>(
-    dfa: &DFA<S>,
-    variant_names: &HashMap<S, String>,
-) -> String {
-    let mut buf = vec![];
-    wstrln!(buf, "    #[derive(Clone, Copy)]");
-    wstr!(buf, "    enum S {{");
-
-    let mut i = 0;
-    for (id, _) in dfa.iter() {
-        if dfa.is_match_state(id) {
-            continue;
+/// Write an array item with the given declaration, wrapping its elements
+/// such that no line is longer than `LINE_WIDTH`, when possible.
+fn write_array<W: io::Write>(
+    mut wtr: W,
+    decl: &str,
+    elements: &[String],
+) -> io::Result<()> {
+    writeln!(wtr, "{} = [", decl)?;
+    let mut line = String::new();
+    for element in elements {
+        if !line.is_empty() && line.len() + element.len() + 2 > LINE_WIDTH {
+            writeln!(wtr, "{}", line)?;
+            line.clear();
         }
-        if i % 10 == 0 {
-            wstr!(buf, "\n       ");
+        if line.is_empty() {
+            line.push_str("   ");
         }
-        let name = format!("S{}", id.to_usize());
-        wstr!(buf, " {},", name);
-        i += 1;
+        line.push(' ');
+        line.push_str(element);
+        line.push(',');
+    }
+    if !line.is_empty() {
+        writeln!(wtr, "{}", line)?;
     }
-    wstr!(buf, "\n");
-    wstrln!(buf, "    }}");
-    String::from_utf8(buf).unwrap()
+    writeln!(wtr, "];")?;
+    Ok(())
 }
 
-fn state_variant_names<S: StateID>(dfa: &DFA<S>) -> HashMap<S, String> {
-    let mut variants = HashMap::new();
-    for (id, _) in dfa.iter() {
-        if dfa.is_match_state(id) {
-            continue;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_arrays() {
+        let elements: Vec<String> = (0..30).map(|n| n.to_string()).collect();
+        let mut buf = vec![];
+        write_array(&mut buf, "static X: [u8; 30]", &elements).unwrap();
+        let got = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            got,
+            "static X: [u8; 30] = [\n    \
+             0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, \
+             18, 19, 20,\n    \
+             21, 22, 23, 24, 25, 26, 27, 28, 29,\n];\n",
+        );
+        assert!(got.lines().all(|line| line.len() <= LINE_WIDTH));
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(check_name("FOO_2").is_ok());
+        assert!(check_name("_foo").is_ok());
+        for &name in &["", "_", "2FOO", "FOO-BAR", "FOO BAR", "FÖÖ"] {
+            let err = check_name(name).unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidInput, err.kind());
         }
-        variants.insert(id, format!("S::S{}", id.to_usize()));
     }
-    variants
 }
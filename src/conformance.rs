@@ -0,0 +1,176 @@
+/*!
+Routines for checking that regexes in this crate agree with the
+[`regex`](https://docs.rs/regex) crate.
+
+These are intended for use in tests. In particular, callers embedding a
+custom configuration of this crate, such as one that disables byte classes
+or converts DFAs to a smaller state identifier representation, can use
+these routines to check that their configuration reports precisely the same
+match spans as the `regex` crate for a corpus of their own patterns and
+haystacks.
+
+Note that the `regex` crate supports features, such as anchors and word
+boundaries, that this crate does not. Patterns using them fail to build
+with this crate and cannot be compared.
+
+This module is only available when the `conformance` feature is enabled.
+
+# Example
+
+```
+use regex_automata::{conformance, RegexBuilder};
+
+let mut builder = RegexBuilder::new();
+builder.minimize(true).premultiply(false);
+let haystacks: &[&[u8]] = &[b"foo123 bar", b"", b"foo foo9"];
+conformance::assert_conforms(&builder, "foo[0-9]*", haystacks);
+```
+*/
+
+use std::error;
+use std::fmt;
+
+use regex_crate::bytes::Regex as Reference;
+
+use dfa::DFA;
+use regex::{Regex, RegexBuilder};
+
+/// A haystack for which a regex in this crate and the `regex` crate report
+/// different matches.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    pattern: String,
+    haystack: Vec<u8>,
+    expected: Vec<(usize, usize)>,
+    got: Vec<(usize, usize)>,
+}
+
+impl Mismatch {
+    /// Returns the pattern of the `regex` crate's regex.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns the haystack that was searched.
+    pub fn haystack(&self) -> &[u8] {
+        &self.haystack
+    }
+
+    /// Returns the matches reported by the `regex` crate.
+    pub fn expected(&self) -> &[(usize, usize)] {
+        &self.expected
+    }
+
+    /// Returns the matches reported by this crate.
+    pub fn got(&self) -> &[(usize, usize)] {
+        &self.got
+    }
+}
+
+impl error::Error for Mismatch {
+    fn description(&self) -> &str {
+        "regex crate conformance mismatch"
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pattern {:?} on haystack {:?}: regex crate found {:?}, \
+             but regex-automata found {:?}",
+            self.pattern,
+            String::from_utf8_lossy(&self.haystack),
+            self.expected,
+            self.got,
+        )
+    }
+}
+
+/// Compare all non-overlapping matches found by the given regex against
+/// those found by the given `regex` crate regex in `haystack`.
+///
+/// This returns a description of the difference when the matches differ.
+pub fn compare<D: DFA>(
+    re: &Regex<D>,
+    reference: &Reference,
+    haystack: &[u8],
+) -> Option<Mismatch> {
+    let expected: Vec<(usize, usize)> = reference
+        .find_iter(haystack)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    let got: Vec<(usize, usize)> = re.find_iter(haystack).collect();
+    if expected == got {
+        return None;
+    }
+    Some(Mismatch {
+        pattern: reference.as_str().to_string(),
+        haystack: haystack.to_vec(),
+        expected,
+        got,
+    })
+}
+
+/// Build the given pattern with both the given builder and the `regex`
+/// crate, and assert that they find the same matches in every haystack.
+///
+/// The `regex` crate's regex is always built with its default options.
+/// Options that change the meaning of a pattern, such as case
+/// insensitivity, should therefore be given as inline flags in the pattern
+/// rather than set on the builder.
+///
+/// # Panics
+///
+/// This panics if the pattern fails to build with either crate, or if the
+/// matches differ for any haystack. The panic message lists every haystack
+/// with differing matches.
+pub fn assert_conforms(
+    builder: &RegexBuilder,
+    pattern: &str,
+    haystacks: &[&[u8]],
+) {
+    let re = match builder.build(pattern) {
+        Ok(re) => re,
+        Err(err) => panic!("failed to build {:?}: {}", pattern, err),
+    };
+    let reference = match Reference::new(pattern) {
+        Ok(re) => re,
+        Err(err) => {
+            panic!("regex crate failed to build {:?}: {}", pattern, err)
+        }
+    };
+    let mismatches: Vec<String> = haystacks
+        .iter()
+        .filter_map(|haystack| compare(&re, &reference, haystack))
+        .map(|mismatch| mismatch.to_string())
+        .collect();
+    if !mismatches.is_empty() {
+        panic!(
+            "{} of {} haystacks disagree with the regex crate:\n{}",
+            mismatches.len(),
+            haystacks.len(),
+            mismatches.join("\n"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_mismatch() {
+        // Leftmost-first semantics must agree...
+        let re = Regex::new("sam|samwise").unwrap();
+        let reference = Reference::new("sam|samwise").unwrap();
+        assert_eq!(None, compare(&re, &reference, b"samwise"));
+
+        // ... but anchored searches do not.
+        let re =
+            RegexBuilder::new().anchored(true).build("sam|samwise").unwrap();
+        let mismatch = compare(&re, &reference, b"sam samwise").unwrap();
+        assert_eq!(&[(0, 3), (4, 7)], mismatch.expected());
+        assert_eq!(&[(0, 3)], mismatch.got());
+    }
+}
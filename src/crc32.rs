@@ -0,0 +1,46 @@
+/// Compute the CRC-32 checksum of the given bytes.
+///
+/// This is the same checksum used by zlib, gzip and PNG, so the checksum
+/// stored in a serialized DFA can be checked with common tools.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let table = table();
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc = table[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Build the table of remainders for each byte, using the reversed
+/// polynomial `0xEDB88320`.
+///
+/// This is cheap enough to build whenever a checksum is computed, which
+/// happens once per serialization or deserialization.
+fn table() -> [u32; 256] {
+    let mut table = [0; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = i as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+        *entry = crc;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crc32;
+
+    #[test]
+    fn check_values() {
+        assert_eq!(0, crc32(b""));
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(0x414F_A339, crc32(fox));
+    }
+}
@@ -0,0 +1,313 @@
+/*!
+Build a minimal DFA for a sorted list of words.
+
+A DFA that matches only a finite set of words is acyclic, and its minimal
+form is often called a DAWG (directed acyclic word graph). Building one with
+[`dense::Builder::build_literals`](../dense/struct.Builder.html#method.build_literals)
+goes through an NFA, determinization and minimization, which is very slow
+and uses a lot of memory for dictionaries with millions of words. When the
+words are given in lexicographic order, a [`Builder`](struct.Builder.html)
+instead constructs the minimal DFA directly, one word at a time, using the
+incremental algorithm of Daciuk et al. Only the minimal DFA for the words
+seen so far, along with the path of the most recent word, is kept in memory.
+
+The DFA built is always anchored, and since it is a
+[`DenseDFA`](../enum.DenseDFA.html), a search reports the end of the
+longest word that begins at the start of the input. It can be converted to
+a sparse DFA or a smaller state identifier representation like any other
+dense DFA.
+
+This module is only available when the `std` feature is enabled.
+
+# Example
+
+```
+use regex_automata::{DFA, dawg};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let mut builder = dawg::Builder::new();
+for word in &["tap", "taps", "top", "tops"] {
+    builder.insert(word)?;
+}
+let dfa = builder.build()?;
+assert_eq!(Some(4), dfa.find(b"tops and"));
+assert_eq!(Some(3), dfa.find(b"tap dance"));
+assert_eq!(None, dfa.find(b"tip"));
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::collections::{HashMap, VecDeque};
+
+use classes::ByteClasses;
+use dense::{self, DenseDFA};
+use error::{Error, Result};
+use state_id::{StateID, dead_id};
+
+/// The identifier of the state that every word begins at.
+const ROOT: usize = 0;
+
+/// A builder for a minimal DFA that matches a lexicographically sorted list
+/// of words.
+///
+/// Words are added with [`insert`](struct.Builder.html#method.insert), and
+/// the DFA is produced by [`build`](struct.Builder.html#method.build).
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// Every state built so far. States that were found to be equivalent to
+    /// another state are reset and their slots reused.
+    states: Vec<State>,
+    /// Every state that is known to be unique, keyed by its contents. These
+    /// states are never modified again.
+    register: HashMap<State, usize>,
+    /// The states along the path of the most recently inserted word, as
+    /// pairs of a parent and its last child. None of these states are in the
+    /// register yet, since later words may still add to them.
+    unchecked: Vec<(usize, usize)>,
+    /// The slots of states that were replaced by an equivalent state.
+    free: Vec<usize>,
+    /// The most recently inserted word.
+    previous: Vec<u8>,
+    /// The number of words given to `insert` so far.
+    count: usize,
+}
+
+/// A state under construction.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+struct State {
+    /// Whether a word ends at this state.
+    is_match: bool,
+    /// The transitions out of this state, sorted by byte.
+    trans: Vec<(u8, usize)>,
+}
+
+impl Builder {
+    /// Create a new builder without any words.
+    pub fn new() -> Builder {
+        Builder {
+            states: vec![State::default()],
+            register: HashMap::new(),
+            unchecked: vec![],
+            free: vec![],
+            previous: vec![],
+            count: 0,
+        }
+    }
+
+    /// Add a word to the DFA.
+    ///
+    /// Every word must be greater than or equal to the word inserted before
+    /// it, when compared as byte strings. Inserting the same word more than
+    /// once has no further effect.
+    ///
+    /// If the word is out of order, then an
+    /// [`ErrorKind::UnsortedWord`](../enum.ErrorKind.html) error is returned
+    /// and the builder is left unchanged.
+    pub fn insert<W: AsRef<[u8]>>(&mut self, word: W) -> Result<()> {
+        let word = word.as_ref();
+        if word < &*self.previous {
+            return Err(Error::unsorted_word(self.count));
+        }
+        self.count += 1;
+
+        let prefix_len = self
+            .previous
+            .iter()
+            .zip(word)
+            .take_while(|&(a, b)| a == b)
+            .count();
+        // Everything past the prefix shared with the previous word can no
+        // longer change, so it is merged with its equivalent states.
+        self.minimize(prefix_len);
+        let mut current = match self.unchecked.last() {
+            None => ROOT,
+            Some(&(_, child)) => child,
+        };
+        for &b in &word[prefix_len..] {
+            let child = self.add_state();
+            self.states[current].trans.push((b, child));
+            self.unchecked.push((current, child));
+            current = child;
+        }
+        self.states[current].is_match = true;
+
+        self.previous.clear();
+        self.previous.extend_from_slice(word);
+        Ok(())
+    }
+
+    /// Build the minimal DFA for the words inserted so far, using `usize`
+    /// for its state identifiers.
+    ///
+    /// The DFA uses byte classes and is premultiplied. If no words were
+    /// inserted, then it never matches.
+    pub fn build(self) -> Result<DenseDFA<Vec<usize>, usize>> {
+        self.build_with_size::<usize>()
+    }
+
+    /// Build the minimal DFA for the words inserted so far, using a specific
+    /// representation for the DFA's state IDs.
+    ///
+    /// An error is returned if the DFA's states do not fit in the chosen
+    /// representation.
+    pub fn build_with_size<S: StateID>(
+        mut self,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        self.minimize(0);
+
+        let mut dfa = dense::Repr::empty_with_byte_classes(self.byte_classes())
+            .anchored(true);
+        // Since no state of the DAWG maps to the dead state, it also marks
+        // states that have not been added to the DFA yet.
+        let mut ids: Vec<S> = vec![dead_id(); self.states.len()];
+        let mut is_match = vec![false];
+        let mut queue = VecDeque::new();
+        ids[ROOT] = dfa.add_empty_state()?;
+        is_match.push(self.states[ROOT].is_match);
+        queue.push_back(ROOT);
+        while let Some(id) = queue.pop_front() {
+            for &(b, next) in &self.states[id].trans {
+                if ids[next] == dead_id() {
+                    ids[next] = dfa.add_empty_state()?;
+                    is_match.push(self.states[next].is_match);
+                    queue.push_back(next);
+                }
+                dfa.add_transition(ids[id], b, ids[next]);
+            }
+        }
+        dfa.set_start_state(ids[ROOT]);
+        dfa.shuffle_match_states(&is_match);
+        dfa.premultiply()?;
+        Ok(dfa.into_dense_dfa())
+    }
+
+    /// Merge every unchecked state deeper than the given depth with an
+    /// equivalent registered state, or register it if there isn't one.
+    ///
+    /// States are visited deepest first, so that the children of each state
+    /// are always registered before the state itself is compared.
+    fn minimize(&mut self, depth: usize) {
+        while self.unchecked.len() > depth {
+            let (parent, child) = self.unchecked.pop().unwrap();
+            let existing = self.register.get(&self.states[child]).cloned();
+            match existing {
+                Some(existing) => {
+                    self.states[parent].trans.last_mut().unwrap().1 = existing;
+                    self.states[child] = State::default();
+                    self.free.push(child);
+                }
+                None => {
+                    self.register.insert(self.states[child].clone(), child);
+                }
+            }
+        }
+    }
+
+    /// Add a new state without any transitions and return its identifier.
+    fn add_state(&mut self) -> usize {
+        match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.states.push(State::default());
+                self.states.len() - 1
+            }
+        }
+    }
+
+    /// Return byte classes that give each byte used by a transition its own
+    /// class, and put every run of other bytes in a single class.
+    fn byte_classes(&self) -> ByteClasses {
+        let mut used = [false; 256];
+        for state in &self.states {
+            for &(b, _) in &state.trans {
+                used[b as usize] = true;
+            }
+        }
+
+        // Byte classes must be contiguous ranges of bytes, so each run of
+        // unused bytes gets a class of its own.
+        let mut classes = ByteClasses::empty();
+        let mut class = 0;
+        for b in 1..256 {
+            if used[b] || used[b - 1] {
+                class += 1;
+            }
+            classes.set(b as u8, class);
+        }
+        classes
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dfa::DFA;
+
+    #[test]
+    fn same_as_minimized_literals() {
+        let mut words = vec![];
+        for a in &["", "a", "b", "ab", "ba", "abc", "bbc"] {
+            for b in &["", "c", "bc", "abc", "x"] {
+                words.push(format!("{}{}", a, b));
+            }
+        }
+        words.sort();
+
+        let mut builder = Builder::new();
+        for word in &words {
+            builder.insert(word).unwrap();
+        }
+        let dawg = builder.build().unwrap();
+        let min = dense::Builder::new()
+            .anchored(true)
+            .longest_match(true)
+            .minimize(true)
+            .build_literals(words.iter())
+            .unwrap();
+        assert_eq!(reachable(&min), reachable(&dawg));
+        for word in &words {
+            for suffix in &["", "c", "xyz"] {
+                let input = format!("{}{}", word, suffix);
+                assert_eq!(
+                    min.find(input.as_bytes()),
+                    dawg.find(input.as_bytes()),
+                    "{:?}", input,
+                );
+            }
+        }
+    }
+
+    /// Return the number of states reachable from the start state of the
+    /// given DFA, including the dead state.
+    fn reachable<D: DFA>(dfa: &D) -> usize {
+        let mut seen = HashMap::new();
+        let mut stack = vec![dfa.start_state()];
+        while let Some(id) = stack.pop() {
+            if seen.insert(id, ()).is_none() {
+                stack.extend((0..256).map(|b| dfa.next_state(id, b as u8)));
+            }
+        }
+        seen.len()
+    }
+
+    #[test]
+    fn rejects_unsorted_words() {
+        let mut builder = Builder::new();
+        builder.insert("b").unwrap();
+        builder.insert("b").unwrap();
+        assert!(builder.insert("a").is_err());
+        builder.insert("c").unwrap();
+
+        let dfa = builder.build().unwrap();
+        assert_eq!(None, dfa.find(b"a"));
+        assert_eq!(Some(1), dfa.find(b"b"));
+        assert_eq!(Some(1), dfa.find(b"c"));
+        assert!(Builder::new().build().unwrap().find(b"").is_none());
+    }
+}
@@ -2,32 +2,67 @@
 use core::fmt;
 #[cfg(feature = "std")]
 use core::iter;
+#[cfg(feature = "mmap")]
+use core::marker::PhantomData;
 use core::mem;
 use core::slice;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, VecDeque};
+#[cfg(feature = "mmap")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "mmap")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
-use byteorder::{ByteOrder, NativeEndian};
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
 #[cfg(feature = "std")]
-use byteorder::{BigEndian, LittleEndian};
+use byteorder::NativeEndian;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+#[cfg(feature = "sample")]
+use rand_core::RngCore;
 #[cfg(feature = "std")]
 use regex_syntax::ParserBuilder;
+#[cfg(feature = "std")]
+use regex_syntax::hir::Hir;
 
 use classes::ByteClasses;
 #[cfg(feature = "std")]
+use codegen;
+#[cfg(feature = "std")]
+use crc32::crc32;
+#[cfg(feature = "std")]
 use determinize::Determinizer;
 use dfa::DFA;
 #[cfg(feature = "std")]
+use dot;
+#[cfg(feature = "std")]
 use error::{Error, Result};
+#[cfg(feature = "sample")]
+use language;
+#[cfg(feature = "std")]
+use language::Graph;
+#[cfg(feature = "std")]
+pub use language::Strings;
+use memory::MemoryUsage;
 #[cfg(feature = "std")]
 use minimize::Minimizer;
 #[cfg(feature = "std")]
 use nfa::{NFA, NFABuilder};
 #[cfg(feature = "std")]
 use sparse::SparseDFA;
-use state_id::{StateID, dead_id};
+use state_id::{StateID, dead_id, is_zero_copy, read_state_id_bytes};
 #[cfg(feature = "std")]
 use state_id::{
     premultiply_overflow_error, next_state_id, write_state_id_bytes,
 };
+#[cfg(feature = "std")]
+use table;
 
 /// The size of the alphabet in a standard DFA.
 ///
@@ -45,6 +80,12 @@ const ALPHABET_LEN: usize = 256;
 pub(crate) const MASK_PREMULTIPLIED: u16 = 0b0000_0000_0000_0001;
 pub(crate) const MASK_ANCHORED: u16 = 0b0000_0000_0000_0010;
 
+/// The version of the dense DFA serialization format.
+///
+/// This must be incremented whenever the format changes, so that DFAs
+/// serialized in an older format are rejected instead of misread.
+const VERSION: u16 = 2;
+
 /// A dense table-based deterministic finite automaton (DFA).
 ///
 /// A dense DFA represents the core matching primitive in this crate. That is,
@@ -297,6 +338,40 @@ impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
     pub fn memory_usage(&self) -> usize {
         self.repr().memory_usage()
     }
+
+    /// Returns the memory used by each component of this DFA, in bytes.
+    ///
+    /// The transition table reported here is the same as the one reported
+    /// by [`memory_usage`](enum.DenseDFA.html#method.memory_usage). See
+    /// [`MemoryUsage`](../struct.MemoryUsage.html) for details about the
+    /// other components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::DenseDFA;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = DenseDFA::new("foo[0-9]+")?;
+    /// let usage = dfa.memory_breakdown();
+    /// assert_eq!(dfa.memory_usage(), usage.transitions());
+    /// assert_eq!(256, usage.byte_classes());
+    /// assert_eq!(0, usage.reverse());
+    /// assert!(usage.total() > dfa.memory_usage());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn memory_breakdown(&self) -> MemoryUsage {
+        self.repr().memory_breakdown()
+    }
+
+    /// Returns the equivalence classes of bytes used by this DFA.
+    ///
+    /// If this DFA does not use byte classes, then every byte is in a class
+    /// of its own. See [`ByteClasses`](../struct.ByteClasses.html) for an
+    /// example.
+    pub fn byte_classes(&self) -> &ByteClasses {
+        self.repr().byte_classes()
+    }
 }
 
 /// Routines for converting a dense DFA to other representations, such as
@@ -315,6 +390,11 @@ impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
     /// cases, if a dense DFA is constructable with `S` then a sparse DFA will
     /// be as well. However, it is not guaranteed.
     ///
+    /// The transitions of the sparse DFA are always expressed as ranges of
+    /// byte equivalence classes. If this DFA does not use byte classes, then
+    /// the classes are computed from its transition table, which never
+    /// increases the number of transitions in the sparse DFA.
+    ///
     /// # Example
     ///
     /// ```
@@ -414,11 +494,48 @@ impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
         self.repr().to_sized().map(|r| r.into_dense_dfa())
     }
 
+    /// Create a new DFA whose match semantics are equivalent to this DFA,
+    /// using the narrowest of `u8`, `u16`, `u32` and `u64` that can
+    /// represent all state identifiers in this DFA.
+    ///
+    /// Unlike `to_u8` and its siblings, this only fails if no
+    /// representation is big enough, which can only happen on platforms
+    /// without `u64` state identifiers. Whether this DFA uses byte classes
+    /// and premultiplication is preserved, which means that this is most
+    /// useful after minimization, when the DFA may fit into a narrower
+    /// representation than the one used to build it. To also choose
+    /// whether to use byte classes and premultiplication, use
+    /// [`dense::Builder::build_auto`](dense/struct.Builder.html#method.build_auto)
+    /// instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    /// use regex_automata::dense::AutoDFA;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().minimize(true).build("foo[0-9]+")?;
+    /// let small = dfa.shrink()?;
+    /// assert!(small.memory_usage() < dfa.memory_usage());
+    /// assert_eq!(Some(8), small.find(b"foo12345"));
+    /// match small {
+    ///     AutoDFA::U8(ref small) => {
+    ///         assert_eq!(small.state_count(), dfa.state_count());
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn shrink(&self) -> Result<AutoDFA> {
+        AutoDFA::narrowest(self.repr())
+    }
+
     /// Serialize a DFA to raw bytes, aligned to an 8 byte boundary, in little
     /// endian format.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_little_endian(&self) -> Result<Vec<u8>> {
@@ -429,7 +546,7 @@ impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
     /// endian format.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_big_endian(&self) -> Result<Vec<u8>> {
@@ -443,12 +560,676 @@ impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
     /// on the same platform.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_native_endian(&self) -> Result<Vec<u8>> {
         self.repr().to_bytes::<NativeEndian>()
     }
+
+    /// Serialize a DFA to raw bytes, aligned to an 8 byte boundary, using
+    /// the endianness given by `A`.
+    pub(crate) fn to_bytes<A: ByteOrder>(&self) -> Result<Vec<u8>> {
+        self.repr().to_bytes::<A>()
+    }
+
+    /// Build a DFA that is in a match state precisely when both this DFA
+    /// and the given DFA are in a match state.
+    ///
+    /// This is the classic product construction. When both DFAs are
+    /// anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), the DFA built
+    /// matches exactly the strings matched by both patterns. For other DFAs,
+    /// it reports a match at every position at which both DFAs report a
+    /// match. Since leftmost-first DFAs stop early once a preferred match is
+    /// found, this may not be every position at which both patterns match.
+    ///
+    /// The DFA built is anchored if either DFA is anchored. Its byte classes
+    /// are the coarsest classes that distinguish every pair of bytes that
+    /// either DFA distinguishes, and it is premultiplied if this DFA is. Only
+    /// pairs of states reachable from the start states are built, but the
+    /// result is not minimized.
+    ///
+    /// If the chosen state identifier representation is too small to
+    /// represent all states in the DFA built, then this returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let letters = builder.build("[a-z0-9]+")?;
+    /// let digit = builder.build("[a-z]*[0-9][a-z0-9]*")?;
+    /// let dfa = letters.intersect(&digit)?;
+    /// assert_eq!(Some(5), dfa.find(b"abc12"));
+    /// assert_eq!(None, dfa.find(b"abcde"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn intersect<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        let anchored = self.is_anchored() || other.is_anchored();
+        self.product(other, anchored, |a, b| a && b, |a, b| a && b)
+    }
+
+    /// Build a DFA that is in a match state precisely when either this DFA
+    /// or the given DFA is in a match state.
+    ///
+    /// This combines already compiled DFAs, such as DFAs deserialized from
+    /// different sources, without access to their patterns. When both DFAs
+    /// are anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), the DFA built
+    /// matches exactly the strings matched by either pattern. For other DFAs,
+    /// it reports a match at every position at which either DFA reports a
+    /// match, and a search with it only stops once both DFAs would stop.
+    /// Note that this does not give an alternation of the two patterns
+    /// leftmost-first semantics.
+    ///
+    /// The DFA built is anchored only if both DFAs are anchored. Otherwise,
+    /// its byte classes, premultiplication and errors are as described for
+    /// [`intersect`](enum.DenseDFA.html#method.intersect).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let bytes1 = builder.build("foo[0-9]+")?.to_u16()?;
+    /// let bytes1 = bytes1.to_bytes_native_endian()?;
+    /// let bytes2 = builder.build("bar")?.to_u16()?.to_bytes_native_endian()?;
+    ///
+    /// let dfa1: DenseDFA<&[u16], u16> = DenseDFA::try_from_bytes(&bytes1)?;
+    /// let dfa2: DenseDFA<&[u16], u16> = DenseDFA::try_from_bytes(&bytes2)?;
+    /// let dfa = dfa1.union(&dfa2)?;
+    /// assert_eq!(Some(5), dfa.find(b"foo12"));
+    /// assert_eq!(Some(3), dfa.find(b"bar"));
+    /// assert_eq!(None, dfa.find(b"baz"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn union<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        let anchored = self.is_anchored() && other.is_anchored();
+        self.product(other, anchored, |a, b| a || b, |a, b| a || b)
+    }
+
+    /// Build a DFA that is in a match state precisely when this DFA is not.
+    ///
+    /// When this DFA is anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), the DFA built
+    /// matches exactly the strings that the pattern does not match. Combined
+    /// with [`intersect`](enum.DenseDFA.html#method.intersect), this permits
+    /// filters such as "matches `A` but does not contain `B`".
+    ///
+    /// Every transition to the dead state of this DFA leads to a new state
+    /// in the DFA built, which is a match state that only transitions to
+    /// itself. As a result, a search with the DFA built rarely stops before
+    /// the end of the input. The DFA built is anchored if this DFA is, and
+    /// it uses the same byte classes.
+    ///
+    /// If the chosen state identifier representation is too small to
+    /// represent the new state, then this returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let words = builder.build("[a-z]+")?;
+    /// let forbidden = builder.build("(?:[a-z]*)bad(?:[a-z]*)")?;
+    /// let dfa = words.intersect(&forbidden.complement()?)?;
+    ///
+    /// let accepts = |s: &[u8]| {
+    ///     let mut state = dfa.start_state();
+    ///     for &b in s {
+    ///         state = dfa.next_state(state, b);
+    ///     }
+    ///     dfa.is_match_state(state)
+    /// };
+    /// assert!(accepts(b"good"));
+    /// assert!(!accepts(b"notbad"));
+    /// assert!(!accepts(b"good!"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn complement(&self) -> Result<DenseDFA<Vec<S>, S>> {
+        // Every pair of states in the product of a DFA with itself is of the
+        // form `(s, s)`, so this only adds the state that `(dead, dead)`
+        // becomes.
+        self.product(self, self.is_anchored(), |a, _| !a, |_, _| true)
+    }
+
+    /// Build a DFA that is in a match state precisely when this DFA is in a
+    /// match state and the given DFA is not.
+    ///
+    /// When both DFAs are anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), the DFA built
+    /// matches exactly the strings matched by this DFA's pattern but not by
+    /// the other pattern. This is equivalent to intersecting this DFA with
+    /// the [`complement`](enum.DenseDFA.html#method.complement) of the given
+    /// DFA, but it is built in one pass, and a search with it stops as soon
+    /// as this DFA would stop.
+    ///
+    /// The DFA built is anchored if this DFA is. Otherwise, its byte classes,
+    /// premultiplication and errors are as described for
+    /// [`intersect`](enum.DenseDFA.html#method.intersect).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let allow = builder.build(r"[a-z]+\.example\.com")?;
+    /// let deny = builder.build(r"(?:ads|tracker)\.example\.com")?;
+    /// let dfa = allow.difference(&deny)?;
+    /// assert_eq!(Some(15), dfa.find(b"www.example.com"));
+    /// assert_eq!(None, dfa.find(b"ads.example.com"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn difference<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        self.product(other, self.is_anchored(), |a, b| a && !b, |a, _| a)
+    }
+
+    /// Returns true if and only if this DFA and the given DFA are in a match
+    /// state after exactly the same inputs.
+    ///
+    /// When both DFAs are anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), this is true
+    /// precisely when both patterns match the same set of strings. This
+    /// makes it possible to check that a rewritten pattern is equivalent to
+    /// the original, regardless of how either DFA was configured or whether
+    /// it was minimized.
+    ///
+    /// This walks the pairs of states reachable from both start states at
+    /// the same time, and stops at the first pair that disagrees on whether
+    /// it is a match state. No DFA is built, but memory proportional to the
+    /// number of pairs visited is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let original = builder.build("(?:a|b)(?:a|b)*")?;
+    /// let rewritten = builder.minimize(true).build("[ab]+")?;
+    /// assert!(original.is_equivalent(&rewritten));
+    ///
+    /// let broken = builder.build("[ab]*")?;
+    /// assert!(!original.is_equivalent(&broken));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn is_equivalent<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+    ) -> bool {
+        !self.any_reachable_pair(other, |a, b| a != b)
+    }
+
+    /// Returns true if and only if the given DFA is in a match state after
+    /// every input after which this DFA is in a match state.
+    ///
+    /// When both DFAs are anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), this is true
+    /// precisely when every string matched by this DFA's pattern is also
+    /// matched by the other pattern. For example, it can check that a
+    /// stricter validation pattern never accepts an input that a legacy
+    /// pattern rejects.
+    ///
+    /// This walks pairs of states in the same way as
+    /// [`is_equivalent`](enum.DenseDFA.html#method.is_equivalent), and
+    /// stops at the first pair in which only this DFA's state is a match
+    /// state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let legacy = builder.build("[0-9a-z]+")?;
+    /// let strict = builder.build("[1-9][0-9]*")?;
+    /// assert!(strict.is_subset_of(&legacy));
+    /// assert!(!legacy.is_subset_of(&strict));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn is_subset_of<U: AsRef<[S]>>(&self, other: &DenseDFA<U, S>) -> bool {
+        !self.any_reachable_pair(other, |a, b| a && !b)
+    }
+
+    /// Returns an iterator over every string of at most `max_len` bytes
+    /// after which this DFA is in a match state.
+    ///
+    /// When this DFA is anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), these are
+    /// precisely the strings of at most `max_len` bytes matched by the DFA's
+    /// pattern in their entirety. Strings are yielded in shortlex order:
+    /// shorter strings come first, and strings of the same length are in
+    /// lexicographic order.
+    ///
+    /// Creating the iterator takes time and space proportional to `max_len`
+    /// times the number of states in this DFA. After that, the work done to
+    /// find each string is proportional to its length, since only prefixes
+    /// of matching strings are explored. Note though that the number of
+    /// strings can be exponential in `max_len`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .longest_match(true)
+    ///     .build("ba[rz]|fo+")?;
+    /// let strings: Vec<Vec<u8>> = dfa.strings(3).collect();
+    /// assert_eq!(strings, vec![
+    ///     b"fo".to_vec(), b"bar".to_vec(), b"baz".to_vec(), b"foo".to_vec(),
+    /// ]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn strings(&self, max_len: usize) -> Strings {
+        Strings::new(Graph::new(self, self.repr().byte_classes()), max_len)
+    }
+
+    /// Returns the number of strings of exactly `len` bytes after which this
+    /// DFA is in a match state.
+    ///
+    /// When this DFA is anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), this is the
+    /// number of strings of that length matched by the DFA's pattern in
+    /// their entirety, such as the number of valid identifiers in some
+    /// format. If the number does not fit in a `u128`, then `None` is
+    /// returned.
+    ///
+    /// The count is computed by dynamic programming over the transition
+    /// table, which takes time proportional to `len` times the number of
+    /// transitions in this DFA and space proportional to the number of
+    /// states.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .longest_match(true)
+    ///     .build("[A-Z]{2}-[0-9]{4}|[0-9]{7}")?;
+    /// let expected = 26 * 26 * 10_000 + 10_000_000;
+    /// assert_eq!(Some(expected), dfa.count_matches_of_len(7));
+    /// assert_eq!(Some(0), dfa.count_matches_of_len(6));
+    ///
+    /// let any = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .allow_invalid_utf8(true)
+    ///     .build("(?s-u).*")?;
+    /// assert_eq!(Some(1 << 120), any.count_matches_of_len(15));
+    /// assert_eq!(None, any.count_matches_of_len(16));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn count_matches_of_len(&self, len: usize) -> Option<u128> {
+        Graph::new(self, self.repr().byte_classes()).count_exact(len)
+    }
+
+    /// Write this DFA to `wtr` as a graph in the DOT language used by
+    /// Graphviz, labeling each edge with the byte ranges that lead to its
+    /// target.
+    ///
+    /// This is the same as [`dot::write`](../dot/fn.write.html). See the
+    /// [`dot`](../dot/index.html) module for details.
+    pub fn to_dot<W: io::Write>(&self, wtr: W) -> io::Result<()> {
+        dot::write(self, wtr)
+    }
+
+    /// Like [`to_dot`](enum.DenseDFA.html#method.to_dot), but label each
+    /// edge with the byte classes of this DFA that lead to its target, and
+    /// add a legend listing the bytes in each class.
+    ///
+    /// If this DFA does not use byte classes, then every byte is in a class
+    /// of its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().anchored(true).build("[a-z]+[0-9]")?;
+    /// let mut buf = vec![];
+    /// dfa.to_dot_with_classes(&mut buf).unwrap();
+    /// let graph = String::from_utf8(buf).unwrap();
+    /// assert!(graph.contains("#1: 0-9\\l"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn to_dot_with_classes<W: io::Write>(
+        &self,
+        wtr: W,
+    ) -> io::Result<()> {
+        dot::write_with_classes(self, self.repr().byte_classes(), wtr)
+    }
+
+    /// Write this DFA to `wtr` as Rust source code defining a static DFA
+    /// named `name`.
+    ///
+    /// This is the implementation of
+    /// [`codegen::write_dense`](codegen/fn.write_dense.html).
+    pub(crate) fn write_rust<W: io::Write>(
+        &self,
+        name: &str,
+        wtr: W,
+    ) -> io::Result<()> {
+        let r = self.repr();
+        let id_type = codegen::id_type::<S>()?;
+        let trans =
+            r.trans().iter().map(|&id| codegen::id_literal(id)).collect();
+        codegen::Source {
+            kind: "DenseDFA",
+            summary: format!(
+                "a dense DFA with {} states and {} byte classes",
+                r.state_count,
+                r.alphabet_len(),
+            ),
+            table_type: id_type,
+            id_type,
+            fields: vec![
+                (r.premultiplied.to_string(), "premultiplied"),
+                (r.anchored.to_string(), "anchored"),
+                (codegen::id_literal(r.start), "start state"),
+                (r.state_count.to_string(), "state count"),
+                (codegen::id_literal(r.max_match), "max match state"),
+            ],
+            byte_classes: &r.byte_classes,
+            tables: vec![(None, trans)],
+        }
+        .write(name, wtr)
+    }
+
+    /// Returns an iterator over every state in this DFA, including the dead
+    /// state and states that are not reachable from the start state.
+    ///
+    /// Each state is yielded along with an iterator over its transitions,
+    /// and states are yielded in the order in which they appear in the
+    /// transition table. The identifiers yielded can be given to the methods
+    /// of the [`DFA`](../trait.DFA.html) trait, such as `is_match_state` and
+    /// `next_state`. In particular, if this DFA is premultiplied, then its
+    /// identifiers are premultiplied as well.
+    ///
+    /// # Example
+    ///
+    /// This counts the number of transitions out of each match state.
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().anchored(true).build("[a-c]x|[0-9]+")?;
+    /// let mut counts = vec![];
+    /// for (id, transitions) in dfa.states() {
+    ///     if dfa.is_match_state(id) {
+    ///         counts.push(transitions.count());
+    ///     }
+    /// }
+    /// counts.sort();
+    /// assert_eq!(counts, vec![0, 1]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn states(&self) -> States<'_, T, S> {
+        let classes = self.repr().byte_classes();
+        States { classes, it: self.repr().states() }
+    }
+
+    /// Returns the total number of states in this DFA, including the dead
+    /// state. Every DFA has at least one state, even the empty DFA.
+    pub fn state_count(&self) -> usize {
+        self.repr().state_count()
+    }
+
+    /// Returns true if and only if some pair of states reachable from the
+    /// start states of this DFA and the given DFA satisfies `pred`, applied
+    /// to whether each state is a match state.
+    ///
+    /// Pairs in which both states are dead are not explored further, since
+    /// only the same pair is reachable from them.
+    fn any_reachable_pair<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+        pred: fn(bool, bool) -> bool,
+    ) -> bool {
+        let classes = refine_byte_classes(
+            self.repr().byte_classes(),
+            other.repr().byte_classes(),
+        );
+        let representatives: Vec<u8> = classes.representatives().collect();
+        let mut seen = HashMap::new();
+        let mut stack = vec![(self.start_state(), other.start_state())];
+        while let Some((a, b)) = stack.pop() {
+            if seen.insert((a, b), ()).is_some() {
+                continue;
+            }
+            if pred(self.is_match_state(a), other.is_match_state(b)) {
+                return true;
+            }
+            if self.is_dead_state(a) && other.is_dead_state(b) {
+                continue;
+            }
+            for &byte in &representatives {
+                let next =
+                    (self.next_state(a, byte), other.next_state(b, byte));
+                stack.push(next);
+            }
+        }
+        false
+    }
+
+    /// Build the product of this DFA and the given DFA.
+    ///
+    /// Whether a pair of states is a match state is given by `is_match`,
+    /// applied to whether each state is a match state. Similarly, whether a
+    /// pair is alive is given by `is_alive`, applied to whether each state
+    /// is not the dead state. Pairs that are not alive become the dead state
+    /// of the product.
+    fn product<U: AsRef<[S]>>(
+        &self,
+        other: &DenseDFA<U, S>,
+        anchored: bool,
+        is_match: fn(bool, bool) -> bool,
+        is_alive: fn(bool, bool) -> bool,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        let classes = refine_byte_classes(
+            self.repr().byte_classes(),
+            other.repr().byte_classes(),
+        );
+        let mut dfa =
+            Repr::empty_with_byte_classes(classes).anchored(anchored);
+        let is_alive = |(a, b): (S, S)| {
+            is_alive(!self.is_dead_state(a), !other.is_dead_state(b))
+        };
+        let is_match = |(a, b): (S, S)| {
+            is_match(self.is_match_state(a), other.is_match_state(b))
+        };
+
+        let representatives: Vec<u8> = classes.representatives().collect();
+        let mut ids = HashMap::new();
+        let mut matches = vec![false];
+        let mut queue = VecDeque::new();
+        let start = (self.start_state(), other.start_state());
+        if is_alive(start) {
+            let id = dfa.add_empty_state()?;
+            ids.insert(start, id);
+            matches.push(is_match(start));
+            queue.push_back((start, id));
+            dfa.set_start_state(id);
+        }
+        while let Some(((a, b), id)) = queue.pop_front() {
+            for &byte in &representatives {
+                let next =
+                    (self.next_state(a, byte), other.next_state(b, byte));
+                if !is_alive(next) {
+                    continue;
+                }
+                let next_id = match ids.get(&next) {
+                    Some(&next_id) => next_id,
+                    None => {
+                        let next_id = dfa.add_empty_state()?;
+                        ids.insert(next, next_id);
+                        matches.push(is_match(next));
+                        queue.push_back((next, next_id));
+                        next_id
+                    }
+                };
+                dfa.add_transition(id, byte, next_id);
+            }
+        }
+        dfa.shuffle_match_states(&matches);
+        if self.repr().premultiplied {
+            dfa.premultiply()?;
+        }
+        Ok(dfa.into_dense_dfa())
+    }
+}
+
+#[cfg(feature = "sample")]
+impl<T: AsRef<[S]>, S: StateID> DenseDFA<T, S> {
+    /// Return a random string of at most `max_len` bytes after which this
+    /// DFA is in a match state, or `None` if there is no such string.
+    ///
+    /// When this DFA is anchored and built with
+    /// [`longest_match`](struct.Builder.html#method.longest_match) or
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), every string
+    /// returned is matched by the DFA's pattern in its entirety, which is
+    /// useful for generating inputs that satisfy a validation pattern.
+    ///
+    /// The string is produced by a random walk from the start state. At each
+    /// step, the walk either stops, if the current state is a match state,
+    /// or takes a byte that can still lead to a match state within the
+    /// remaining length, with every such choice equally likely. This favors
+    /// short strings and strings through states with few outgoing bytes.
+    /// To choose uniformly among all strings instead, use
+    /// [`sample_uniform`](enum.DenseDFA.html#method.sample_uniform).
+    ///
+    /// This method is only available when the `sample` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate rand_core;
+    ///
+    /// use regex_automata::{DFA, dense};
+    /// # use rand_core::{RngCore, impls};
+    /// # struct Rng(u64);
+    /// # impl RngCore for Rng {
+    /// #     fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+    /// #     fn next_u64(&mut self) -> u64 {
+    /// #         self.0 = self.0.wrapping_mul(6364136223846793005)
+    /// #             .wrapping_add(1442695040888963407);
+    /// #         self.0 >> 11
+    /// #     }
+    /// #     fn fill_bytes(&mut self, dest: &mut [u8]) {
+    /// #         impls::fill_bytes_via_next(self, dest)
+    /// #     }
+    /// #     fn try_fill_bytes(&mut self, dest: &mut [u8])
+    /// #         -> Result<(), rand_core::Error> {
+    /// #         Ok(self.fill_bytes(dest))
+    /// #     }
+    /// # }
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// // Any implementation of `rand_core::RngCore` can be used, such as
+    /// // the generators provided by the `rand` crate.
+    /// let mut rng = Rng(42);
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .longest_match(true)
+    ///     .build("[a-z]{2,4}-[0-9]{3}")?;
+    /// for _ in 0..10 {
+    ///     let id = dfa.sample(&mut rng, 10).unwrap();
+    ///     assert_eq!(Some(id.len()), dfa.find(&id));
+    /// }
+    /// // No matching string is that short.
+    /// assert_eq!(None, dfa.sample(&mut rng, 5));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn sample<R: RngCore>(
+        &self,
+        rng: &mut R,
+        max_len: usize,
+    ) -> Option<Vec<u8>> {
+        let graph = Graph::new(self, self.repr().byte_classes());
+        language::sample(&graph, rng, max_len)
+    }
+
+    /// Return a string chosen uniformly at random from all strings of at
+    /// most `max_len` bytes after which this DFA is in a match state, or
+    /// `None` if there is no such string.
+    ///
+    /// This is like [`sample`](enum.DenseDFA.html#method.sample), except
+    /// that every string is equally likely. Since there are usually far more
+    /// long strings than short strings, the strings returned are usually
+    /// close to `max_len` bytes long. The probabilities are computed with
+    /// floating point arithmetic, so they are uniform up to rounding.
+    ///
+    /// This first counts the strings accepted from every state for every
+    /// length up to `max_len`, which requires time and space proportional to
+    /// `max_len` times the number of states in this DFA.
+    ///
+    /// This method is only available when the `sample` feature is enabled.
+    pub fn sample_uniform<R: RngCore>(
+        &self,
+        rng: &mut R,
+        max_len: usize,
+    ) -> Option<Vec<u8>> {
+        let graph = Graph::new(self, self.repr().byte_classes());
+        language::sample_uniform(&graph, rng, max_len)
+    }
+}
+
+/// Return the coarsest byte classes such that two bytes are in the same class
+/// only if they are in the same class in both of the given byte classes.
+#[cfg(feature = "std")]
+fn refine_byte_classes(a: &ByteClasses, b: &ByteClasses) -> ByteClasses {
+    // Since every class is a contiguous range of bytes, a new class begins
+    // wherever a new class begins in either of the given classes.
+    let mut classes = ByteClasses::empty();
+    let mut class = 0;
+    for byte in 1..256 {
+        let (prev, byte) = (byte as u8 - 1, byte as u8);
+        if a.get(prev) != a.get(byte) || b.get(prev) != b.get(byte) {
+            class += 1;
+        }
+        classes.set(byte, class);
+    }
+    classes
 }
 
 impl<'a, S: StateID> DenseDFA<&'a [S], S> {
@@ -471,15 +1252,21 @@ impl<'a, S: StateID> DenseDFA<&'a [S], S> {
     /// serialize DFAs using a fixed size representation for your state
     /// identifiers, such as `u8`, `u16`, `u32` or `u64`.
     ///
+    /// Since the transition table is borrowed from the given bytes, the
+    /// `NonZero` state identifier representations, which are stored
+    /// differently in memory than they are serialized, cannot be used here.
+    ///
     /// # Panics
     ///
     /// The bytes given should be *trusted*. In particular, if the bytes
     /// are not a valid serialization of a DFA, or if the given bytes are
-    /// not aligned to an 8 byte boundary, or if the endianness of the
+    /// not aligned to an 8 byte boundary, or if the state identifier
+    /// representation is a `NonZero` type, or if the endianness of the
     /// serialized bytes is different than the endianness of the machine that
     /// is deserializing the DFA, then this routine will panic. Moreover, it is
     /// possible for this deserialization routine to succeed even if the given
-    /// bytes do not represent a valid serialized dense DFA.
+    /// bytes do not represent a valid serialized dense DFA. In particular,
+    /// the checksum of the transition table is not verified.
     ///
     /// # Safety
     ///
@@ -518,17 +1305,163 @@ impl<'a, S: StateID> DenseDFA<&'a [S], S> {
     pub unsafe fn from_bytes(buf: &'a [u8]) -> DenseDFA<&'a [S], S> {
         Repr::from_bytes(buf).into_dense_dfa()
     }
-}
 
-#[cfg(feature = "std")]
-impl<S: StateID> DenseDFA<Vec<S>, S> {
-    /// Minimize this DFA in place.
+    /// Deserialize a DFA with a specific state identifier representation,
+    /// after exhaustively validating that the given bytes correspond to a
+    /// valid serialized DFA.
     ///
-    /// This is not part of the public API. It is only exposed to allow for
-    /// more granular external benchmarking.
-    #[doc(hidden)]
+    /// Unlike [`from_bytes`](enum.DenseDFA.html#method.from_bytes), this
+    /// routine is safe to call with *untrusted* bytes. In addition to the
+    /// checks done by `from_bytes`, this validates that the byte class map is
+    /// consistent, that the start state, the maximum match state and every
+    /// transition in the transition table correspond to a valid state
+    /// (respecting premultiplication, if enabled) and that the dead state
+    /// only ever transitions to itself. If any of these checks fail, or if
+    /// the given bytes are not aligned to the alignment of `S`, or if the
+    /// state identifier representation is a `NonZero` type, then an error is
+    /// returned instead of panicking.
+    ///
+    /// Every serialized DFA starts with a header recording the version of
+    /// the serialization format, the endianness and state identifier size it
+    /// was serialized with and a CRC-32 checksum of its transition table.
+    /// If the version, endianness or state identifier size do not match, or
+    /// if the transition table has been corrupted, then the error returned
+    /// has a kind of
+    /// [`VersionMismatch`](enum.ErrorKind.html#variant.VersionMismatch),
+    /// [`EndiannessMismatch`](enum.ErrorKind.html#variant.EndiannessMismatch),
+    /// [`StateIDSizeMismatch`](enum.ErrorKind.html#variant.StateIDSizeMismatch)
+    /// or
+    /// [`ChecksumMismatch`](enum.ErrorKind.html#variant.ChecksumMismatch),
+    /// respectively.
+    ///
+    /// Deserializing a DFA using this routine will never allocate heap
+    /// memory. However, since every transition must be checked, this takes
+    /// time proportional to the size of the DFA. If the bytes given are
+    /// always trusted, then `from_bytes` can be used to avoid this cost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let initial = DenseDFA::new("foo[0-9]+")?;
+    /// let bytes = initial.to_u16()?.to_bytes_native_endian()?;
+    /// let dfa: DenseDFA<&[u16], u16> = DenseDFA::try_from_bytes(&bytes)?;
+    ///
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    ///
+    /// // Garbage is rejected with an error.
+    /// let garbage = vec![0u16; 200];
+    /// let garbage = unsafe {
+    ///     ::std::slice::from_raw_parts(garbage.as_ptr() as *const u8, 400)
+    /// };
+    /// assert!(DenseDFA::<&[u16], u16>::try_from_bytes(garbage).is_err());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_from_bytes(buf: &'a [u8]) -> Result<DenseDFA<&'a [S], S>> {
+        Repr::try_from_bytes(buf).map(|r| r.into_dense_dfa())
+    }
+
+    /// Build a DFA from its raw parts, borrowing the given transition table.
+    ///
+    /// This is a `const fn`, which permits a DFA to be stored in a `static`
+    /// without any deserialization at runtime. It is what Rust code written
+    /// by the [`codegen`](codegen/index.html) module uses to construct its
+    /// DFAs, and is generally not meant to be called directly.
+    ///
+    /// The parts given must be exactly as they are stored in a DFA built by
+    /// this crate. In particular, if `premultiplied` is true, then `start`,
+    /// `max_match` and every transition must be premultiplied identifiers.
+    /// If every byte is in its own class, then the DFA does not use byte
+    /// classes and each state has 256 transitions. Otherwise, each state has
+    /// one transition for each class.
+    ///
+    /// # Safety
+    ///
+    /// This routine is unsafe because it permits callers to provide an
+    /// arbitrary transition table with possibly incorrect transitions. None
+    /// of the parts given are checked. Given an incorrect transition table,
+    /// it is possible for the search routines to access out-of-bounds memory
+    /// because of explicit bounds check elision.
+    pub const unsafe fn from_parts(
+        premultiplied: bool,
+        anchored: bool,
+        start: S,
+        state_count: usize,
+        max_match: S,
+        byte_classes: [u8; 256],
+        trans: &'a [S],
+    ) -> DenseDFA<&'a [S], S> {
+        let singleton = byte_classes[255] == 255;
+        let repr = Repr {
+            premultiplied,
+            anchored,
+            start,
+            state_count,
+            max_match,
+            byte_classes: ByteClasses::from_array(byte_classes),
+            trans,
+        };
+        match (premultiplied, singleton) {
+            (false, true) => DenseDFA::Standard(Standard(repr)),
+            (false, false) => DenseDFA::ByteClass(ByteClass(repr)),
+            (true, true) => DenseDFA::Premultiplied(Premultiplied(repr)),
+            (true, false) => {
+                DenseDFA::PremultipliedByteClass(PremultipliedByteClass(repr))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: StateID> DenseDFA<Vec<S>, S> {
+    /// Deserialize a DFA serialized with either endianness into an owned DFA
+    /// that uses the endianness of the current target.
+    ///
+    /// Unlike [`try_from_bytes`](enum.DenseDFA.html#method.try_from_bytes),
+    /// which borrows its transition table from the given bytes and therefore
+    /// requires them to be aligned and in native endian format, this copies
+    /// the given bytes, converting every state identifier to native
+    /// endianness if necessary. This makes it possible to build a DFA on a
+    /// machine with one endianness and deploy it to a machine with another,
+    /// at the cost of allocating a new transition table. The bytes given may
+    /// start at any address.
+    ///
+    /// The copy is validated in the same way as `try_from_bytes`, so this is
+    /// safe to call with *untrusted* bytes, and returns an error under the
+    /// same conditions, except that an endianness that differs from the
+    /// current target is never an error.
+    ///
+    /// # Example
+    ///
+    /// This example shows how to load a DFA serialized in big endian format,
+    /// no matter the endianness of the current machine.
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let initial = DenseDFA::new("foo[0-9]+")?;
+    /// let bytes = initial.to_u16()?.to_bytes_big_endian()?;
+    /// let dfa: DenseDFA<Vec<u16>, u16> =
+    ///     DenseDFA::from_bytes_any_endian(&bytes)?;
+    ///
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn from_bytes_any_endian(buf: &[u8]) -> Result<DenseDFA<Vec<S>, S>> {
+        Repr::from_bytes_any_endian(buf).map(|r| r.into_dense_dfa())
+    }
+
+    /// Minimize this DFA in place.
+    ///
+    /// This is not part of the public API. It is only exposed to allow for
+    /// more granular external benchmarking.
+    #[doc(hidden)]
     pub fn minimize(&mut self) {
-        self.repr_mut().minimize();
+        self.repr_mut().minimize(None).unwrap();
     }
 
     /// Return a mutable reference to the internal DFA representation.
@@ -543,6 +1476,170 @@ impl<S: StateID> DenseDFA<Vec<S>, S> {
     }
 }
 
+#[cfg(feature = "mmap")]
+impl<S: StateID> DenseDFA<Mapped<S>, S> {
+    /// Memory map the file at the given path and deserialize the DFA
+    /// serialized in it, without copying its transition table.
+    ///
+    /// The file should contain the bytes returned by one of the
+    /// serialization routines of a dense DFA, such as
+    /// [`to_bytes_native_endian`](enum.DenseDFA.html#method.to_bytes_native_endian).
+    /// Only the pages of the file that a search visits are read into memory,
+    /// and those pages are shared by every process that maps the same file.
+    /// This makes it cheap to start a process that uses a very large DFA.
+    ///
+    /// The DFA returned owns the map, which is unmapped when the DFA is
+    /// dropped. A borrowed DFA tied to the lifetime of the map can be
+    /// obtained with [`as_ref`](enum.DenseDFA.html#method.as_ref), for
+    /// example to build a [`Regex`](struct.Regex.html) whose DFAs are both
+    /// borrowed.
+    ///
+    /// Like [`try_from_bytes`](enum.DenseDFA.html#method.try_from_bytes),
+    /// this returns an error if the file cannot be mapped or if its header
+    /// is not valid, including when the version, endianness or state
+    /// identifier size of the serialized DFA does not match. Unlike
+    /// `try_from_bytes`, the transition table is neither validated nor
+    /// checksummed, which means that this takes constant time regardless of
+    /// the size of the DFA.
+    ///
+    /// This routine is only available when the `mmap` feature is enabled.
+    ///
+    /// # Safety
+    ///
+    /// This routine is unsafe for the same reason as
+    /// [`from_bytes`](enum.DenseDFA.html#method.from_bytes): given an
+    /// incorrect transition table, it is possible for the search routines to
+    /// access out-of-bounds memory. In addition, the file must not be
+    /// modified or truncated while it is mapped, since that would change the
+    /// transition table underneath the DFA.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::fs;
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let path = std::env::temp_dir().join("regex-automata-open.dfa");
+    /// let initial = DenseDFA::new("foo[0-9]+")?.to_u16()?;
+    /// fs::write(&path, initial.to_bytes_native_endian()?).unwrap();
+    ///
+    /// let dfa: DenseDFA<_, u16> = unsafe { DenseDFA::open(&path)? };
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub unsafe fn open<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<DenseDFA<Mapped<S>, S>> {
+        let path = path.as_ref();
+        let map = File::open(path).and_then(|f| Mmap::map(&f)).map_err(|e| {
+            Error::deserialize(&format!(
+                "failed to map DenseDFA file {}: {}",
+                path.display(),
+                e,
+            ))
+        })?;
+        let (r, _) = Repr::<&[S], S>::try_from_header(&map[..])?;
+        let offset = r.trans.as_ptr() as usize - map.as_ptr() as usize;
+        let (len, start, max_match) = (r.trans.len(), r.start, r.max_match);
+        let (premultiplied, anchored) = (r.premultiplied, r.anchored);
+        let (state_count, byte_classes) = (r.state_count, r.byte_classes);
+        let trans = Mapped { map, offset, len, _marker: PhantomData };
+        Ok(Repr {
+            premultiplied,
+            anchored,
+            start,
+            state_count,
+            max_match,
+            byte_classes,
+            trans,
+        }
+        .into_dense_dfa())
+    }
+}
+
+/// The transition table of a dense DFA stored in a memory mapped file.
+///
+/// This is the type of the transition table of a DFA returned by
+/// [`DenseDFA::open`](enum.DenseDFA.html#method.open). It keeps the file
+/// mapped for as long as the DFA is alive.
+///
+/// This type is only available when the `mmap` feature is enabled.
+#[cfg(feature = "mmap")]
+pub struct Mapped<S> {
+    map: Mmap,
+    /// The offset of the transition table in the map, in bytes. This is
+    /// always aligned for `S`, since maps are aligned to a page boundary.
+    offset: usize,
+    /// The number of state identifiers in the transition table.
+    len: usize,
+    _marker: PhantomData<S>,
+}
+
+#[cfg(feature = "mmap")]
+impl<S: StateID> AsRef<[S]> for Mapped<S> {
+    fn as_ref(&self) -> &[S] {
+        // SAFETY: `DenseDFA::open` checked that the transition table is
+        // aligned and contains exactly `len` state identifiers, and the map
+        // lives as long as the slice returned.
+        unsafe {
+            let ptr = self.map.as_ptr().add(self.offset) as *const S;
+            slice::from_raw_parts(ptr, self.len)
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<S> fmt::Debug for Mapped<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mapped")
+            .field("offset", &self.offset)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// Formats this DFA as a table with one line per state.
+///
+/// Each line marks whether the state is the dead state (`D`), the start
+/// state (`>`) or a match state (`*`), followed by the state's identifier and
+/// its transitions to states other than the dead state. Transitions to the
+/// same state are grouped together, and the bytes leading to each state are
+/// written as contiguous ranges. With the alternate flag, e.g., `{:#}`, the
+/// transitions are instead labeled with this DFA's byte classes, followed by
+/// a legend listing the bytes in each class.
+///
+/// This is meant for reading by humans, and its format may change.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::dense;
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new()
+///     .anchored(true)
+///     .premultiply(false)
+///     .build("[a-c]x|[0-9]+")?;
+/// let expected = "\
+/// DenseDFA(5 states, 7 byte classes, start 4)
+/// D   0
+///   * 1
+///   * 2  0-9 => 2
+///     3  x => 1
+///  >  4  0-9 => 2 | a-c => 3
+/// ";
+/// assert_eq!(expected, dfa.to_string());
+/// # Ok(()) }; example().unwrap()
+/// ```
+#[cfg(feature = "std")]
+impl<T: AsRef<[S]>, S: StateID> fmt::Display for DenseDFA<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let classes = self.repr().byte_classes();
+        table::write(f, "DenseDFA", self, self.states(), classes)
+    }
+}
+
 impl<T: AsRef<[S]>, S: StateID> DFA for DenseDFA<T, S> {
     type ID = S;
 
@@ -889,117 +1986,387 @@ impl<T: AsRef<[S]>, S: StateID> DFA for PremultipliedByteClass<T, S> {
     }
 }
 
-/// The internal representation of a dense DFA.
+/// A dense DFA that is searched directly from its serialized bytes without
+/// any alignment requirements.
 ///
-/// This representation is shared by all DFA variants.
-#[derive(Clone)]
-#[cfg_attr(not(feature = "std"), derive(Debug))]
-pub(crate) struct Repr<T, S> {
-    /// Whether the state identifiers in the transition table have been
-    /// premultiplied or not.
+/// A `DenseDFA` deserialized with
+/// [`DenseDFA::from_bytes`](enum.DenseDFA.html#method.from_bytes) requires
+/// its bytes to be aligned to an 8 byte boundary, since its transition table
+/// is reinterpreted as a slice of state identifiers. This is not always
+/// possible. For example, a DFA stored in a firmware image and executed in
+/// place from flash memory may live at an arbitrary offset that cannot be
+/// controlled, and copying it into RAM may not be an option on a device with
+/// very little of it.
+///
+/// This type accepts the same serialization format as `DenseDFA`, but never
+/// reinterprets the transition table. Instead, every transition followed
+/// decodes the next state identifier from the raw bytes using unaligned
+/// reads. Apart from a copy of the DFA's 256 byte equivalence class map, a
+/// DFA of this type uses no memory aside from the serialized bytes given.
+///
+/// The cost of this flexibility is search speed. Every transition requires
+/// decoding a state identifier from bytes and a branch on whether the DFA is
+/// premultiplied, so searching is slower than with any of the `DenseDFA`
+/// variants. If the serialized bytes can be placed at a properly aligned
+/// address, then `DenseDFA::from_bytes` should be preferred.
+///
+/// # Example
+///
+/// This example shows how to search with a DFA whose serialized bytes begin
+/// at an odd address, which `DenseDFA::from_bytes` would reject.
+///
+/// ```
+/// use regex_automata::{DFA, DenseDFA, dense};
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let initial = DenseDFA::new("foo[0-9]+")?;
+/// let bytes = initial.to_u16()?.to_bytes_native_endian()?;
+///
+/// let mut buf = vec![0; bytes.len() + 1];
+/// buf[1..].copy_from_slice(&bytes);
+/// let dfa: dense::Unaligned<&[u8], u16> =
+///     dense::Unaligned::from_bytes(&buf[1..]);
+///
+/// assert_eq!(Some(8), dfa.find(b"foo12345"));
+/// # Ok(()) }; example().unwrap()
+/// ```
+#[derive(Clone, Debug)]
+pub struct Unaligned<T: AsRef<[u8]>, S: StateID> {
+    premultiplied: bool,
+    anchored: bool,
+    /// Whether the state identifiers in the transition table are encoded in
+    /// big endian format. When this differs from the endianness of the
+    /// current target, then state identifiers are byte swapped when read.
+    big_endian: bool,
+    start: S,
+    state_count: usize,
+    max_match: S,
+    byte_classes: ByteClasses,
+    /// The transition table in the same layout as `Repr::trans`, except
+    /// encoded as bytes that may not be aligned.
+    trans: T,
+}
+
+impl<'a, S: StateID> Unaligned<&'a [u8], S> {
+    /// Deserialize a DFA for searching directly from the given bytes.
     ///
-    /// Premultiplied identifiers means that instead of your matching loop
-    /// looking something like this:
+    /// The bytes given should be generated by the serialization of a dense
+    /// DFA in the same way as for
+    /// [`DenseDFA::from_bytes`](enum.DenseDFA.html#method.from_bytes), except
+    /// that the bytes may start at any address.
     ///
-    ///   state = dfa.start
-    ///   for byte in haystack:
-    ///       next = dfa.transitions[state * len(alphabet) + byte]
-    ///       if dfa.is_match(next):
-    ///           return true
-    ///   return false
+    /// Additionally, the bytes may be serialized in either little or big
+    /// endian format, regardless of the endianness of the machine that is
+    /// deserializing them. When the two differ, every state identifier is
+    /// byte swapped as it is read during a search. This permits a single
+    /// serialized DFA to be shipped to devices with differing endianness, at
+    /// the cost of a small amount of search speed on some of them.
     ///
-    /// it can instead look like this:
+    /// Deserializing a DFA using this routine will never allocate heap memory.
+    /// This is also guaranteed to be a constant time operation that does not
+    /// vary with the size of the DFA.
     ///
-    ///   state = dfa.start
-    ///   for byte in haystack:
-    ///       next = dfa.transitions[state + byte]
-    ///       if dfa.is_match(next):
-    ///           return true
-    ///   return false
+    /// # Panics
     ///
-    /// In other words, we save a multiplication instruction in the critical
-    /// path. This turns out to be a decent performance win. The cost of using
-    /// premultiplied state ids is that they can require a bigger state id
-    /// representation.
-    premultiplied: bool,
-    /// Whether this DFA can only match at the beginning of input or not.
+    /// If the bytes given are not a valid serialization of a dense DFA, or
+    /// if the state identifier representation of the serialized bytes does
+    /// not match the one requested, then this routine will panic. It is
+    /// possible for this deserialization routine to succeed even if the given
+    /// bytes do not represent a valid serialized dense DFA.
     ///
-    /// When true, a match should only be reported if it begins at the 0th
-    /// index of the haystack.
-    anchored: bool,
-    /// The initial start state ID.
-    start: S,
-    /// The total number of states in this DFA. Note that a DFA always has at
-    /// least one state---the dead state---even the empty DFA. In particular,
-    /// the dead state always has ID 0 and is correspondingly always the first
-    /// state. The dead state is never a match state.
-    state_count: usize,
-    /// States in a DFA have a *partial* ordering such that a match state
-    /// always precedes any non-match state (except for the special dead
-    /// state).
+    /// Unlike `DenseDFA::from_bytes`, this routine is safe to call, since
+    /// every read from the transition table is bounds checked. Searching with
+    /// a DFA deserialized from invalid bytes may panic or produce incorrect
+    /// results, but it will never access out-of-bounds memory.
     ///
-    /// `max_match` corresponds to the last state that is a match state. This
-    /// encoding has two critical benefits. Firstly, we are not required to
-    /// store any additional per-state information about whether it is a match
-    /// state or not. Secondly, when searching with the DFA, we can do a single
-    /// comparison with `max_match` for each byte instead of two comparisons
-    /// for each byte (one testing whether it is a match and the other testing
-    /// whether we've reached a dead state). Namely, to determine the status
-    /// of the next state, we can do this:
+    /// # Example
     ///
-    ///   next_state = transition[cur_state * alphabet_len + cur_byte]
-    ///   if next_state <= max_match:
-    ///       // next_state is either dead (no-match) or a match
-    ///       return next_state != dead
-    max_match: S,
-    /// A set of equivalence classes, where a single equivalence class
-    /// represents a set of bytes that never discriminate between a match
-    /// and a non-match in the DFA. Each equivalence class corresponds to
-    /// a single letter in this DFA's alphabet, where the maximum number of
-    /// letters is 256 (each possible value of a byte). Consequently, the
-    /// number of equivalence classes corresponds to the number of transitions
-    /// for each DFA state.
+    /// This example shows how to search with a DFA that was serialized in
+    /// big endian format, no matter the endianness of the current machine.
     ///
-    /// The only time the number of equivalence classes is fewer than 256 is
-    /// if the DFA's kind uses byte classes. If the DFA doesn't use byte
-    /// classes, then this vector is empty.
-    byte_classes: ByteClasses,
-    /// A contiguous region of memory representing the transition table in
-    /// row-major order. The representation is dense. That is, every state has
-    /// precisely the same number of transitions. The maximum number of
-    /// transitions is 256. If a DFA has been instructed to use byte classes,
-    /// then the number of transitions can be much less.
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA, dense};
     ///
-    /// In practice, T is either Vec<S> or &[S].
-    trans: T,
-}
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let initial = DenseDFA::new("foo[0-9]+")?;
+    /// let bytes = initial.to_u16()?.to_bytes_big_endian()?;
+    /// let dfa: dense::Unaligned<&[u8], u16> =
+    ///     dense::Unaligned::from_bytes(&bytes);
+    ///
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn from_bytes(mut buf: &'a [u8]) -> Unaligned<&'a [u8], S> {
+        // skip over label
+        match buf.iter().position(|&b| b == b'\x00') {
+            None => panic!("could not find label"),
+            Some(i) => buf = &buf[i+1..],
+        }
 
-#[cfg(feature = "std")]
-impl<S: StateID> Repr<Vec<S>, S> {
-    /// Create a new empty DFA with singleton byte classes (every byte is its
-    /// own equivalence class).
-    pub fn empty() -> Repr<Vec<S>, S> {
-        Repr::empty_with_byte_classes(ByteClasses::singletons())
+        // detect the endianness of the DFA, which may differ from ours
+        match BigEndian::read_u16(buf) {
+            0xFEFF => Unaligned::from_bytes_endian::<BigEndian>(buf, true),
+            0xFFFE => Unaligned::from_bytes_endian::<LittleEndian>(buf, false),
+            endian_check => panic!(
+                "invalid endianness check, expected 0xFEFF in either \
+                 byte order but got 0x{:X}",
+                endian_check,
+            ),
+        }
     }
 
-    /// Create a new empty DFA with the given set of byte equivalence classes.
-    /// An empty DFA never matches any input.
-    pub fn empty_with_byte_classes(
-        byte_classes: ByteClasses,
-    ) -> Repr<Vec<S>, S> {
-        let mut dfa = Repr {
-            premultiplied: false,
-            anchored: true,
-            start: dead_id(),
-            state_count: 0,
-            max_match: S::from_usize(0),
-            byte_classes: byte_classes,
-            trans: vec![],
-        };
-        // Every state ID repr must be able to fit at least one state.
-        dfa.add_empty_state().unwrap();
-        dfa
-    }
+    /// Deserialize the remainder of a DFA, starting at its endianness check,
+    /// using the given endianness.
+    fn from_bytes_endian<E: ByteOrder>(
+        mut buf: &'a [u8],
+        big_endian: bool,
+    ) -> Unaligned<&'a [u8], S> {
+        // skip over endianness check
+        buf = &buf[2..];
+
+        // check that the version number is supported
+        let version = E::read_u16(buf);
+        buf = &buf[2..];
+        if version != VERSION {
+            panic!(
+                "expected version {}, but found unsupported version {}",
+                VERSION, version,
+            );
+        }
+
+        // read size of state
+        let state_size = E::read_u16(buf) as usize;
+        if state_size != mem::size_of::<S>() {
+            panic!(
+                "state size of DenseDFA ({}) does not match \
+                 requested state size ({})",
+                state_size, mem::size_of::<S>(),
+            );
+        }
+        buf = &buf[2..];
+
+        // read miscellaneous options
+        let opts = E::read_u16(buf);
+        buf = &buf[2..];
+
+        // read start state
+        let start = S::from_usize(E::read_u64(buf) as usize);
+        buf = &buf[8..];
+
+        // read state count
+        let state_count = E::read_u64(buf) as usize;
+        buf = &buf[8..];
+
+        // read max match state
+        let max_match = S::from_usize(E::read_u64(buf) as usize);
+        buf = &buf[8..];
+
+        // skip over the checksum, which is only verified by
+        // `DenseDFA::try_from_bytes`, and the reserved bytes
+        buf = &buf[8..];
+
+        // read byte classes
+        let byte_classes = ByteClasses::from_slice(&buf[..256]);
+        buf = &buf[256..];
+
+        let len = state_count * byte_classes.alphabet_len();
+        let len_bytes = len * state_size;
+        assert!(
+            buf.len() >= len_bytes,
+            "insufficient transition table bytes, \
+             expected at least {} but only have {}",
+            len_bytes, buf.len()
+        );
+        Unaligned {
+            premultiplied: opts & MASK_PREMULTIPLIED > 0,
+            anchored: opts & MASK_ANCHORED > 0,
+            big_endian,
+            start,
+            state_count,
+            max_match,
+            byte_classes,
+            trans: &buf[..len_bytes],
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>, S: StateID> Unaligned<T, S> {
+    /// Returns the memory usage, in bytes, of this DFA's transition table.
+    ///
+    /// Since the transition table of this DFA is never copied, this is
+    /// precisely the number of serialized bytes used by the transition table.
+    pub fn memory_usage(&self) -> usize {
+        self.trans.as_ref().len()
+    }
+
+    /// Return the number of elements in this DFA's alphabet.
+    fn alphabet_len(&self) -> usize {
+        self.byte_classes.alphabet_len()
+    }
+}
+
+impl<T: AsRef<[u8]>, S: StateID> DFA for Unaligned<T, S> {
+    type ID = S;
+
+    #[inline]
+    fn start_state(&self) -> S {
+        self.start
+    }
+
+    #[inline]
+    fn is_match_state(&self, id: S) -> bool {
+        id <= self.max_match && id != dead_id()
+    }
+
+    #[inline]
+    fn is_dead_state(&self, id: S) -> bool {
+        id == dead_id()
+    }
+
+    #[inline]
+    fn is_match_or_dead_state(&self, id: S) -> bool {
+        id <= self.max_match
+    }
+
+    #[inline]
+    fn is_anchored(&self) -> bool {
+        self.anchored
+    }
+
+    #[inline]
+    fn next_state(&self, current: S, input: u8) -> S {
+        let input = self.byte_classes.get(input) as usize;
+        let o = if self.premultiplied {
+            current.to_usize() + input
+        } else {
+            current.to_usize() * self.alphabet_len() + input
+        };
+        let start = o * mem::size_of::<S>();
+        let bytes = &self.trans.as_ref()[start..start + mem::size_of::<S>()];
+        if self.big_endian {
+            read_state_id_bytes::<BigEndian, S>(bytes)
+        } else {
+            read_state_id_bytes::<LittleEndian, S>(bytes)
+        }
+    }
+
+    #[inline]
+    unsafe fn next_state_unchecked(&self, current: S, input: u8) -> S {
+        self.next_state(current, input)
+    }
+}
+
+/// The internal representation of a dense DFA.
+///
+/// This representation is shared by all DFA variants.
+#[derive(Clone)]
+#[cfg_attr(not(feature = "std"), derive(Debug))]
+pub(crate) struct Repr<T, S> {
+    /// Whether the state identifiers in the transition table have been
+    /// premultiplied or not.
+    ///
+    /// Premultiplied identifiers means that instead of your matching loop
+    /// looking something like this:
+    ///
+    ///   state = dfa.start
+    ///   for byte in haystack:
+    ///       next = dfa.transitions[state * len(alphabet) + byte]
+    ///       if dfa.is_match(next):
+    ///           return true
+    ///   return false
+    ///
+    /// it can instead look like this:
+    ///
+    ///   state = dfa.start
+    ///   for byte in haystack:
+    ///       next = dfa.transitions[state + byte]
+    ///       if dfa.is_match(next):
+    ///           return true
+    ///   return false
+    ///
+    /// In other words, we save a multiplication instruction in the critical
+    /// path. This turns out to be a decent performance win. The cost of using
+    /// premultiplied state ids is that they can require a bigger state id
+    /// representation.
+    premultiplied: bool,
+    /// Whether this DFA can only match at the beginning of input or not.
+    ///
+    /// When true, a match should only be reported if it begins at the 0th
+    /// index of the haystack.
+    anchored: bool,
+    /// The initial start state ID.
+    start: S,
+    /// The total number of states in this DFA. Note that a DFA always has at
+    /// least one state---the dead state---even the empty DFA. In particular,
+    /// the dead state always has ID 0 and is correspondingly always the first
+    /// state. The dead state is never a match state.
+    state_count: usize,
+    /// States in a DFA have a *partial* ordering such that a match state
+    /// always precedes any non-match state (except for the special dead
+    /// state).
+    ///
+    /// `max_match` corresponds to the last state that is a match state. This
+    /// encoding has two critical benefits. Firstly, we are not required to
+    /// store any additional per-state information about whether it is a match
+    /// state or not. Secondly, when searching with the DFA, we can do a single
+    /// comparison with `max_match` for each byte instead of two comparisons
+    /// for each byte (one testing whether it is a match and the other testing
+    /// whether we've reached a dead state). Namely, to determine the status
+    /// of the next state, we can do this:
+    ///
+    ///   next_state = transition[cur_state * alphabet_len + cur_byte]
+    ///   if next_state <= max_match:
+    ///       // next_state is either dead (no-match) or a match
+    ///       return next_state != dead
+    max_match: S,
+    /// A set of equivalence classes, where a single equivalence class
+    /// represents a set of bytes that never discriminate between a match
+    /// and a non-match in the DFA. Each equivalence class corresponds to
+    /// a single letter in this DFA's alphabet, where the maximum number of
+    /// letters is 256 (each possible value of a byte). Consequently, the
+    /// number of equivalence classes corresponds to the number of transitions
+    /// for each DFA state.
+    ///
+    /// The only time the number of equivalence classes is fewer than 256 is
+    /// if the DFA's kind uses byte classes. If the DFA doesn't use byte
+    /// classes, then this vector is empty.
+    byte_classes: ByteClasses,
+    /// A contiguous region of memory representing the transition table in
+    /// row-major order. The representation is dense. That is, every state has
+    /// precisely the same number of transitions. The maximum number of
+    /// transitions is 256. If a DFA has been instructed to use byte classes,
+    /// then the number of transitions can be much less.
+    ///
+    /// In practice, T is either Vec<S> or &[S].
+    trans: T,
+}
+
+#[cfg(feature = "std")]
+impl<S: StateID> Repr<Vec<S>, S> {
+    /// Create a new empty DFA with singleton byte classes (every byte is its
+    /// own equivalence class).
+    pub fn empty() -> Repr<Vec<S>, S> {
+        Repr::empty_with_byte_classes(ByteClasses::singletons())
+    }
+
+    /// Create a new empty DFA with the given set of byte equivalence classes.
+    /// An empty DFA never matches any input.
+    pub fn empty_with_byte_classes(
+        byte_classes: ByteClasses,
+    ) -> Repr<Vec<S>, S> {
+        let mut dfa = Repr {
+            premultiplied: false,
+            anchored: true,
+            start: dead_id(),
+            state_count: 0,
+            max_match: S::from_usize(0),
+            byte_classes: byte_classes,
+            trans: vec![],
+        };
+        // Every state ID repr must be able to fit at least one state.
+        dfa.add_empty_state().unwrap();
+        dfa
+    }
 
     /// Sets whether this DFA is anchored or not.
     pub fn anchored(mut self, yes: bool) -> Repr<Vec<S>, S> {
@@ -1136,6 +2503,18 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
         self.trans().len() * mem::size_of::<S>()
     }
 
+    /// Returns the memory used by each component of this DFA, in bytes.
+    pub fn memory_breakdown(&self) -> MemoryUsage {
+        MemoryUsage {
+            transitions: self.memory_usage(),
+            byte_classes: mem::size_of::<ByteClasses>(),
+            // Match states are identified only by comparing with the last
+            // match state, so that is the only metadata needed.
+            match_states: mem::size_of::<S>(),
+            reverse: 0,
+        }
+    }
+
     /// Convert the given state identifier to the state's index. The state's
     /// index corresponds to the position in which it appears in the transition
     /// table. When a DFA is NOT premultiplied, then a state's identifier is
@@ -1198,7 +2577,7 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
     /// Serialize a DFA to raw bytes, aligned to an 8 byte boundary.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     #[cfg(feature = "std")]
@@ -1215,7 +2594,7 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
             // For version number.
             + 2
             // Size of state ID representation, in bytes.
-            // Must be 1, 2, 4 or 8.
+            // Must be 1, 2, 3, 4 or 8.
             + 2
             // For DFA misc options.
             + 2
@@ -1225,12 +2604,16 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
             + 8
             // For max match state.
             + 8
+            // For CRC-32 checksum of transition table.
+            + 4
+            // Reserved, must be zero. Keeps the table aligned.
+            + 4
             // For byte class map.
             + 256
             // For transition table.
             + trans_size;
         // sanity check, this can be updated if need be
-        assert_eq!(312 + trans_size, size);
+        assert_eq!(320 + trans_size, size);
         // This must always pass. It checks that the transition table is at
         // a properly aligned address.
         assert_eq!(0, (size - trans_size) % 8);
@@ -1247,13 +2630,13 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
         A::write_u16(&mut buf[i..], 0xFEFF);
         i += 2;
         // version number
-        A::write_u16(&mut buf[i..], 1);
+        A::write_u16(&mut buf[i..], VERSION);
         i += 2;
         // size of state ID
         let state_size = mem::size_of::<S>();
-        if ![1, 2, 4, 8].contains(&state_size) {
+        if ![1, 2, 3, 4, 8].contains(&state_size) {
             return Err(Error::serialize(&format!(
-                "state size of {} not supported, must be 1, 2, 4 or 8",
+                "state size of {} not supported, must be 1, 2, 3, 4 or 8",
                 state_size
             )));
         }
@@ -1281,17 +2664,25 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
             self.max_match.to_usize() as u64,
         );
         i += 8;
+        // checksum of transition table, written after the table below
+        let checksum_at = i;
+        i += 4;
+        // reserved
+        i += 4;
         // byte class map
         for b in (0..256).map(|b| b as u8) {
             buf[i] = self.byte_classes().get(b);
             i += 1;
         }
         // transition table
+        let trans_at = i;
         for &id in self.trans() {
             write_state_id_bytes::<A, _>(&mut buf[i..], id);
             i += state_size;
         }
         assert_eq!(size, i, "expected to consume entire buffer");
+        let checksum = crc32(&buf[trans_at..]);
+        A::write_u32(&mut buf[checksum_at..], checksum);
 
         Ok(buf)
     }
@@ -1299,7 +2690,12 @@ impl<T: AsRef<[S]>, S: StateID> Repr<T, S> {
 
 impl<'a, S: StateID> Repr<&'a [S], S> {
     /// The implementation for deserializing a DFA from raw bytes.
-    unsafe fn from_bytes(mut buf: &'a [u8]) -> Repr<&'a [S], S> {
+    unsafe fn from_bytes(buf: &'a [u8]) -> Repr<&'a [S], S> {
+        assert!(
+            is_zero_copy::<S>(),
+            "DenseDFA transition table cannot be borrowed using a state \
+             identifier representation that differs from its serialized form",
+        );
         assert_eq!(
             0,
             buf.as_ptr() as usize % mem::align_of::<S>(),
@@ -1307,92 +2703,321 @@ impl<'a, S: StateID> Repr<&'a [S], S> {
             buf.as_ptr() as usize
         );
 
-        // skip over label
-        match buf.iter().position(|&b| b == b'\x00') {
-            None => panic!("could not find label"),
-            Some(i) => buf = &buf[i+1..],
-        }
-
-        // check that current endianness is same as endianness of DFA
-        let endian_check = NativeEndian::read_u16(buf);
-        buf = &buf[2..];
-        if endian_check != 0xFEFF {
+        let dfa = Unaligned::<&[u8], S>::from_bytes(buf);
+        if dfa.big_endian != cfg!(target_endian = "big") {
             panic!(
-                "endianness mismatch, expected 0xFEFF but got 0x{:X}. \
+                "endianness mismatch, expected 0xFEFF but got 0xFFFE. \
                  are you trying to load a DenseDFA serialized with a \
                  different endianness?",
-                endian_check,
             );
         }
+        assert_eq!(
+            0,
+            dfa.trans.as_ptr() as usize % mem::align_of::<S>(),
+            "DenseDFA transition table is not properly aligned"
+        );
 
-        // check that the version number is supported
-        let version = NativeEndian::read_u16(buf);
-        buf = &buf[2..];
-        if version != 1 {
-            panic!(
-                "expected version 1, but found unsupported version {}",
-                version,
-            );
+        // SAFETY: This is the only actual unsafe thing in this entire routine.
+        // The key things we need to worry about here are alignment and size.
+        // The assert above covers alignment, and deserializing the unaligned
+        // DFA has already checked that the transition table has enough bytes.
+        let len = dfa.trans.len() / mem::size_of::<S>();
+        let trans = slice::from_raw_parts(dfa.trans.as_ptr() as *const S, len);
+        Repr {
+            premultiplied: dfa.premultiplied,
+            anchored: dfa.anchored,
+            start: dfa.start,
+            state_count: dfa.state_count,
+            max_match: dfa.max_match,
+            byte_classes: dfa.byte_classes,
+            trans,
         }
+    }
+}
 
-        // read size of state
-        let state_size = NativeEndian::read_u16(buf) as usize;
-        if state_size != mem::size_of::<S>() {
-            panic!(
-                "state size of DenseDFA ({}) does not match \
-                 requested state size ({})",
-                state_size, mem::size_of::<S>(),
-            );
+#[cfg(feature = "std")]
+impl<'a, S: StateID> Repr<&'a [S], S> {
+    /// The implementation for deserializing a DFA from untrusted raw bytes.
+    ///
+    /// This checks everything that `from_bytes` does, but returns an error
+    /// instead of panicking, and also validates the entire transition table.
+    fn try_from_bytes(buf: &'a [u8]) -> Result<Repr<&'a [S], S>> {
+        let (repr, checksum) = Repr::<&[S], S>::try_from_header(buf)?;
+        let alphabet_len = repr.alphabet_len();
+        for (i, &id) in repr.trans.iter().enumerate() {
+            if !repr.is_valid_id(id.to_usize() as u64) {
+                return Err(Error::deserialize(&format!(
+                    "transition {} of state {} points to invalid state {}",
+                    i % alphabet_len, i / alphabet_len, id.to_usize(),
+                )));
+            }
+        }
+        if repr.trans[..alphabet_len].iter().any(|&id| id != dead_id()) {
+            return Err(Error::deserialize(
+                "dead state has a transition to a state other than itself",
+            ));
         }
-        buf = &buf[2..];
 
-        // read miscellaneous options
-        let opts = NativeEndian::read_u16(buf);
-        buf = &buf[2..];
+        // Checking the checksum last means that a transition table that is
+        // corrupt in a way the checks above can detect is reported as such.
+        let found = crc32(&buf[320..]);
+        if found != checksum {
+            return Err(Error::checksum_mismatch(checksum, found));
+        }
+        Ok(repr)
+    }
 
-        // read start state
-        let start = S::from_usize(NativeEndian::read_u64(buf) as usize);
-        buf = &buf[8..];
+    /// Deserialize a DFA from raw bytes after checking everything that
+    /// `from_bytes` does, returning an error instead of panicking, along
+    /// with the checksum of its transition table.
+    ///
+    /// Unlike `try_from_bytes`, this takes constant time. The transition
+    /// table is not validated.
+    fn try_from_header(buf: &'a [u8]) -> Result<(Repr<&'a [S], S>, u32)> {
+        let label = b"rust-regex-automata-dfa\x00";
+        if !is_zero_copy::<S>() {
+            return Err(Error::deserialize(
+                "DenseDFA transition table cannot be borrowed using a state \
+                 identifier representation that differs from its serialized \
+                 form",
+            ));
+        }
+        if buf.as_ptr() as usize % mem::align_of::<S>() != 0 {
+            return Err(Error::deserialize(&format!(
+                "DenseDFA starting at address {} is not aligned to {} bytes",
+                buf.as_ptr() as usize,
+                mem::align_of::<S>(),
+            )));
+        }
+        if buf.len() < 320 {
+            return Err(Error::deserialize(&format!(
+                "expected at least 320 bytes for the DenseDFA header, \
+                 but only have {}",
+                buf.len(),
+            )));
+        }
+        if buf[..label.len()] != label[..] {
+            return Err(Error::deserialize("could not find DenseDFA label"));
+        }
+        let mut rest = &buf[label.len()..];
 
-        // read state count
-        let state_count = NativeEndian::read_u64(buf) as usize;
-        buf = &buf[8..];
+        // check that current endianness is same as endianness of DFA
+        let endian_check = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if endian_check == 0xFFFE {
+            return Err(Error::endianness_mismatch());
+        } else if endian_check != 0xFEFF {
+            return Err(Error::deserialize(&format!(
+                "invalid endianness check, expected 0xFEFF but got 0x{:X}",
+                endian_check,
+            )));
+        }
 
-        // read max match state
-        let max_match = S::from_usize(NativeEndian::read_u64(buf) as usize);
-        buf = &buf[8..];
+        // check that the version number is supported
+        let version = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if version != VERSION {
+            return Err(Error::version_mismatch(VERSION, version));
+        }
 
-        // read byte classes
-        let byte_classes = ByteClasses::from_slice(&buf[..256]);
-        buf = &buf[256..];
+        // read size of state
+        let state_size = NativeEndian::read_u16(rest) as usize;
+        rest = &rest[2..];
+        if state_size != mem::size_of::<S>() {
+            return Err(Error::state_id_size_mismatch(
+                mem::size_of::<S>(),
+                state_size,
+            ));
+        }
 
-        let len = state_count * byte_classes.alphabet_len();
-        let len_bytes = len * state_size;
-        assert!(
-            buf.len() <= len_bytes,
-            "insufficient transition table bytes, \
-             expected at least {} but only have {}",
-            len_bytes, buf.len()
-        );
-        assert_eq!(
-            0,
-            buf.as_ptr() as usize % mem::align_of::<S>(),
-            "DenseDFA transition table is not properly aligned"
-        );
+        // read miscellaneous options
+        let opts = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if opts & !(MASK_PREMULTIPLIED | MASK_ANCHORED) != 0 {
+            return Err(Error::deserialize(&format!(
+                "unrecognized DenseDFA options 0x{:X}", opts,
+            )));
+        }
+        let premultiplied = opts & MASK_PREMULTIPLIED > 0;
+
+        let start = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let state_count = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let max_match = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let checksum = NativeEndian::read_u32(rest);
+        rest = &rest[4..];
+        if NativeEndian::read_u32(rest) != 0 {
+            return Err(Error::deserialize(
+                "reserved DenseDFA header bytes are not zero",
+            ));
+        }
+        rest = &rest[4..];
+
+        // check that byte classes map every byte to a class in the alphabet
+        let byte_classes = ByteClasses::from_slice(&rest[..256]);
+        rest = &rest[256..];
+        let alphabet_len = byte_classes.alphabet_len();
+        if (0..256).any(|b| byte_classes.get(b as u8) as usize >= alphabet_len)
+        {
+            return Err(Error::deserialize(
+                "byte class map contains a class outside of the alphabet",
+            ));
+        }
 
-        // SAFETY: This is the only actual unsafe thing in this entire routine.
-        // The key things we need to worry about here are alignment and size.
-        // The two asserts above should cover both conditions.
-        let trans = slice::from_raw_parts(buf.as_ptr() as *const S, len);
-        Repr {
-            premultiplied: opts & MASK_PREMULTIPLIED > 0,
+        // check that the transition table has the expected size
+        if state_count == 0 || state_count > S::max_id() as u64 {
+            return Err(Error::deserialize(&format!(
+                "invalid DenseDFA state count {}", state_count,
+            )));
+        }
+        let state_count = state_count as usize;
+        let len = match state_count.checked_mul(alphabet_len) {
+            Some(len) if len.checked_mul(state_size).is_some() => len,
+            _ => {
+                return Err(Error::deserialize(&format!(
+                    "DenseDFA state count {} is too big", state_count,
+                )));
+            }
+        };
+        if rest.len() != len * state_size {
+            return Err(Error::deserialize(&format!(
+                "expected {} transition table bytes, but have {}",
+                len * state_size, rest.len(),
+            )));
+        }
+
+        // SAFETY: We checked above that the transition table is aligned and
+        // that it contains exactly `len` state identifiers.
+        let trans = unsafe {
+            slice::from_raw_parts(rest.as_ptr() as *const S, len)
+        };
+        let repr = Repr {
+            premultiplied,
             anchored: opts & MASK_ANCHORED > 0,
-            start,
+            start: S::from_usize(start as usize),
             state_count,
-            max_match,
+            max_match: S::from_usize(max_match as usize),
             byte_classes,
             trans,
+        };
+        if !repr.is_valid_id(start) {
+            return Err(Error::deserialize(&format!(
+                "invalid start state {}", start,
+            )));
+        }
+        if !repr.is_valid_id(max_match) {
+            return Err(Error::deserialize(&format!(
+                "invalid maximum match state {}", max_match,
+            )));
         }
+        Ok((repr, checksum))
+    }
+
+    /// Returns true if and only if the given identifier refers to the
+    /// beginning of a state in the transition table.
+    fn is_valid_id(&self, id: u64) -> bool {
+        if id > S::max_id() as u64 {
+            return false;
+        }
+        let id = id as usize;
+        if self.premultiplied {
+            let index = id / self.alphabet_len();
+            index * self.alphabet_len() == id && index < self.state_count
+        } else {
+            id < self.state_count
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: StateID> Repr<Vec<S>, S> {
+    /// The implementation for deserializing a DFA serialized with either
+    /// endianness into an owned DFA.
+    ///
+    /// This converts the given bytes to native endianness in an aligned copy
+    /// and then validates that copy using `try_from_bytes`.
+    fn from_bytes_any_endian(buf: &[u8]) -> Result<Repr<Vec<S>, S>> {
+        let label = b"rust-regex-automata-dfa\x00";
+        if buf.len() < 320 {
+            return Err(Error::deserialize(&format!(
+                "expected at least 320 bytes for the DenseDFA header, \
+                 but only have {}",
+                buf.len(),
+            )));
+        }
+        if buf[..label.len()] != label[..] {
+            return Err(Error::deserialize("could not find DenseDFA label"));
+        }
+
+        // Every state identifier representation has an alignment of at most
+        // 8 bytes, so a buffer of u64s is always suitably aligned.
+        let mut words = vec![0u64; buf.len() / 8 + 1];
+        // SAFETY: The slice covers only initialized memory owned by `words`,
+        // and any value of a u64 is also valid as bytes.
+        let copy = unsafe {
+            slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, buf.len())
+        };
+        copy.copy_from_slice(buf);
+        match BigEndian::read_u16(&buf[label.len()..]) {
+            0xFEFF if cfg!(target_endian = "big") => {}
+            0xFFFE if cfg!(target_endian = "little") => {}
+            0xFEFF => swap_to_native::<BigEndian>(copy),
+            0xFFFE => swap_to_native::<LittleEndian>(copy),
+            endian_check => {
+                return Err(Error::deserialize(&format!(
+                    "invalid endianness check, expected 0xFEFF in either \
+                     byte order but got 0x{:X}",
+                    endian_check,
+                )));
+            }
+        }
+        Repr::<&[S], S>::try_from_bytes(copy).map(|r| r.to_owned())
+    }
+}
+
+/// Rewrite the header and transition table of a serialized dense DFA from
+/// the endianness `E` to native endianness, in place.
+///
+/// The given bytes must contain at least a complete header. Any problems
+/// with the header, such as an unsupported state identifier size, are left
+/// for `Repr::try_from_bytes` to report.
+#[cfg(feature = "std")]
+fn swap_to_native<E: ByteOrder>(buf: &mut [u8]) {
+    // endianness check, version, state size and options
+    for &i in &[24, 26, 28, 30] {
+        let n = E::read_u16(&buf[i..]);
+        NativeEndian::write_u16(&mut buf[i..], n);
+    }
+    // start state, state count and max match state
+    for &i in &[32, 40, 48] {
+        let n = E::read_u64(&buf[i..]);
+        NativeEndian::write_u64(&mut buf[i..], n);
+    }
+    // The checksum is of the transition table as it was serialized, so it
+    // must be checked before the table is rewritten. If it matches, then it
+    // is replaced with the checksum of the rewritten table. Otherwise, it is
+    // kept, so that the mismatch is still reported.
+    let checksum = E::read_u32(&buf[56..]);
+    let valid = crc32(&buf[320..]) == checksum;
+    NativeEndian::write_u32(&mut buf[56..], checksum);
+    let reserved = E::read_u32(&buf[60..]);
+    NativeEndian::write_u32(&mut buf[60..], reserved);
+
+    let state_size = NativeEndian::read_u16(&buf[28..]) as usize;
+    if ![1, 2, 3, 4, 8].contains(&state_size) {
+        return;
+    }
+    for id in buf[320..].chunks_mut(state_size) {
+        if id.len() == state_size {
+            let n = E::read_uint(id, state_size);
+            NativeEndian::write_uint(id, n, state_size);
+        }
+    }
+    if valid {
+        let checksum = crc32(&buf[320..]);
+        NativeEndian::write_u32(&mut buf[56..], checksum);
     }
 }
 
@@ -1427,11 +3052,15 @@ impl<S: StateID> Repr<Vec<S>, S> {
 
     /// Minimize this DFA using Hopcroft's algorithm.
     ///
+    /// If a deadline is given and it passes before minimization completes,
+    /// then an error is returned and this DFA is left unchanged. Without a
+    /// deadline, this never fails.
+    ///
     /// This cannot be called on a premultiplied DFA.
-    pub fn minimize(&mut self) {
+    pub fn minimize(&mut self, deadline: Option<Deadline>) -> Result<()> {
         assert!(!self.premultiplied, "can't minimize premultiplied DFA");
 
-        Minimizer::new(self).run();
+        Minimizer::new(self).deadline(deadline).run()
     }
 
     /// Set the start state of this DFA.
@@ -1683,6 +3312,80 @@ impl<'a, T: AsRef<[S]>, S: StateID> Iterator for StateIter<'a, T, S> {
     }
 }
 
+/// An iterator over all states in a dense DFA.
+///
+/// Each item is a pair of a state identifier and an iterator over the
+/// transitions out of that state.
+///
+/// This is created by [`DenseDFA::states`](enum.DenseDFA.html#method.states).
+/// `'a` corresponds to the lifetime of the DFA, `T` corresponds to the type
+/// of its transition table and `S` corresponds to its state identifier
+/// representation.
+#[cfg(feature = "std")]
+pub struct States<'a, T: 'a, S: 'a> {
+    classes: &'a ByteClasses,
+    it: StateIter<'a, T, S>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: AsRef<[S]>, S: StateID> Iterator for States<'a, T, S> {
+    type Item = (S, Transitions<'a, S>);
+
+    fn next(&mut self) -> Option<(S, Transitions<'a, S>)> {
+        let classes = self.classes;
+        self.it.next().map(|(id, state)| {
+            let transitions =
+                Transitions { classes, trans: state.transitions, byte: 0 };
+            (id, transitions)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T, S> fmt::Debug for States<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("States").finish()
+    }
+}
+
+/// An iterator over the transitions out of a single state of a dense DFA.
+///
+/// Each item is a triple of an inclusive range of bytes and the identifier of
+/// the state that every byte in the range leads to. Ranges are yielded in
+/// ascending order, and each range is as large as possible, even if the DFA
+/// uses byte classes. Transitions to the dead state are not yielded.
+///
+/// This is created by [`DenseDFA::states`](enum.DenseDFA.html#method.states).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Transitions<'a, S: 'a> {
+    classes: &'a ByteClasses,
+    trans: &'a [S],
+    /// The next byte to look at, which is `256` once every byte is done.
+    byte: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a, S: StateID> Iterator for Transitions<'a, S> {
+    type Item = (u8, u8, S);
+
+    fn next(&mut self) -> Option<(u8, u8, S)> {
+        let (classes, trans) = (self.classes, self.trans);
+        let next_of = |b: usize| trans[classes.get(b as u8) as usize];
+        while self.byte < 256 {
+            let start = self.byte;
+            let next = next_of(start);
+            while self.byte < 256 && next_of(self.byte) == next {
+                self.byte += 1;
+            }
+            if next != dead_id() {
+                return Some((start as u8, (self.byte - 1) as u8, next));
+            }
+        }
+        None
+    }
+}
+
 /// An immutable representation of a single DFA state.
 ///
 /// `'a` correspondings to the lifetime of a DFA's transition table and `S`
@@ -1858,6 +3561,559 @@ impl<'a, S: StateID> Iterator for StateTransitionIterMut<'a, S> {
     }
 }
 
+/// The match semantics used when building a DFA.
+///
+/// This determines whether the order of alternations and the greediness of
+/// repetitions in a pattern imply a priority between matches, which in turn
+/// determines which matches a DFA may stop searching at.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MatchKind {
+    /// Report matches using leftmost-first semantics, where earlier branches
+    /// of an alternation are preferred over later branches and greedy
+    /// repetitions are preferred over non-greedy ones.
+    ///
+    /// When a match is found, every lower priority way of matching is pruned
+    /// from the DFA. For example, `Sam|Samwise` will never match `Samwise`.
+    ///
+    /// This is the default.
+    LeftmostFirst,
+    /// Report all matches, where the order of alternations and the
+    /// greediness of repetitions is irrelevant.
+    ///
+    /// No way of matching is ever pruned, so a DFA enters a match state at
+    /// every position at which any match ends. For example, `Sam|Samwise`
+    /// enters a match state after both `Sam` and `Samwise`. Consequently,
+    /// `a+` and `a+?` are equivalent.
+    ///
+    /// Unlike [`longest_match`](struct.Builder.html#method.longest_match),
+    /// this may be used to build unanchored DFAs. Since an unanchored DFA
+    /// can start a new match at any position, it will usually not enter a
+    /// dead state before the end of the input. Therefore, `DFA::find`
+    /// generally reports the end of the last match in the input rather than
+    /// the end of the leftmost one. This is most useful with `is_match`,
+    /// with `shortest_match` or when walking the DFA's transitions manually.
+    All,
+    /// Report matches using leftmost-longest semantics, as in POSIX, where
+    /// the match that begins earliest is reported and, among the matches
+    /// that begin there, the longest is reported.
+    ///
+    /// The order of alternations and the greediness of repetitions is
+    /// irrelevant. For example, both `Sam|Samwise` and `Samwise|Sam` match
+    /// `Samwise` in `Samwise`, and `a+?` matches `aaa` in `aaa`.
+    ///
+    /// Unlike [`longest_match`](struct.Builder.html#method.longest_match),
+    /// this may be used to build unanchored DFAs, in which case `DFA::find`
+    /// reports the end of the leftmost-longest match.
+    LeftmostLongest,
+}
+
+/// The policy used by
+/// [`dense::Builder::build_auto`](struct.Builder.html#method.build_auto) to
+/// choose the representation of a DFA.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AutoPolicy {
+    /// Choose the representation with the smallest transition table.
+    ///
+    /// The DFA uses byte classes, and the narrowest state identifier
+    /// representation that fits its states. Its state identifiers are
+    /// premultiplied only when this does not require a wider
+    /// representation.
+    ///
+    /// This is the default.
+    Smallest,
+    /// Choose the representation with the fastest transitions.
+    ///
+    /// The DFA does not use byte classes and its state identifiers are
+    /// premultiplied, using the narrowest representation that fits them.
+    /// Since every byte then needs a transition of its own, the transition
+    /// table is usually much bigger than with `Smallest`.
+    Fastest,
+}
+
+/// A report describing each step of compiling a pattern into a DFA.
+///
+/// This is returned by
+/// [`dense::Builder::explain`](struct.Builder.html#method.explain). Its
+/// `Display` implementation writes a human readable summary.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Explanation {
+    hir: String,
+    nfa_states: usize,
+    alphabet_len: usize,
+    dfa_states: usize,
+    minimized_states: Option<usize>,
+    memory_usage: usize,
+}
+
+#[cfg(feature = "std")]
+impl Explanation {
+    /// Returns the pattern's high-level intermediate representation, as
+    /// printed by `regex-syntax`. This shows the pattern after flags have
+    /// been applied and Unicode classes have been expanded.
+    pub fn hir(&self) -> &str {
+        &self.hir
+    }
+
+    /// Returns the number of states in the NFA compiled from the pattern.
+    pub fn nfa_states(&self) -> usize {
+        self.nfa_states
+    }
+
+    /// Returns the number of byte classes in the DFA's alphabet. When byte
+    /// classes are disabled, this is always `256`.
+    pub fn alphabet_len(&self) -> usize {
+        self.alphabet_len
+    }
+
+    /// Returns the number of states in the DFA after determinization.
+    pub fn dfa_states(&self) -> usize {
+        self.dfa_states
+    }
+
+    /// Returns the number of states in the DFA after minimization, or
+    /// `None` if minimization is disabled.
+    pub fn minimized_states(&self) -> Option<usize> {
+        self.minimized_states
+    }
+
+    /// Returns the memory usage, in bytes, of the final DFA's transition
+    /// table when using `usize` for state identifiers.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "hir: {}", self.hir)?;
+        writeln!(f, "nfa states: {}", self.nfa_states)?;
+        writeln!(f, "byte classes: {}", self.alphabet_len)?;
+        writeln!(f, "dfa states: {}", self.dfa_states)?;
+        match self.minimized_states {
+            None => writeln!(f, "minimized states: (disabled)")?,
+            Some(n) => writeln!(f, "minimized states: {}", n)?,
+        }
+        write!(f, "memory usage: {} bytes", self.memory_usage)
+    }
+}
+
+/// Statistics collected while building a DFA.
+///
+/// This is returned by
+/// [`dense::Builder::build_with_stats`](struct.Builder.html#method.build_with_stats)
+/// along with the DFA. Its `Display` implementation writes a human readable
+/// summary.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct BuildStats {
+    nfa_states: usize,
+    alphabet_len: usize,
+    dfa_states: usize,
+    minimized_states: Option<usize>,
+    peak_memory_usage: usize,
+    parse_time: Duration,
+    nfa_time: Duration,
+    determinize_time: Duration,
+    minimize_time: Option<Duration>,
+    premultiply_time: Option<Duration>,
+}
+
+#[cfg(feature = "std")]
+impl BuildStats {
+    /// Create statistics for a build that has not started yet.
+    fn new() -> BuildStats {
+        BuildStats {
+            nfa_states: 0,
+            alphabet_len: 0,
+            dfa_states: 0,
+            minimized_states: None,
+            peak_memory_usage: 0,
+            parse_time: Duration::from_secs(0),
+            nfa_time: Duration::from_secs(0),
+            determinize_time: Duration::from_secs(0),
+            minimize_time: None,
+            premultiply_time: None,
+        }
+    }
+
+    /// Returns the number of states in the NFA that the DFA was built from.
+    pub fn nfa_states(&self) -> usize {
+        self.nfa_states
+    }
+
+    /// Returns the number of byte classes in the DFA's alphabet. When byte
+    /// classes are disabled, this is always `256`.
+    pub fn alphabet_len(&self) -> usize {
+        self.alphabet_len
+    }
+
+    /// Returns the number of states in the DFA after determinization.
+    pub fn dfa_states(&self) -> usize {
+        self.dfa_states
+    }
+
+    /// Returns the number of states in the DFA after minimization, or
+    /// `None` if minimization is disabled.
+    pub fn minimized_states(&self) -> Option<usize> {
+        self.minimized_states
+    }
+
+    /// Returns the largest amount of heap memory, in bytes, used by the
+    /// DFA's transition table at any point during the build.
+    ///
+    /// Since the transition table only grows during determinization and
+    /// never grows afterwards, this is its size once determinization is
+    /// done. This does not include memory used by the NFA or by the
+    /// bookkeeping of determinization.
+    pub fn peak_memory_usage(&self) -> usize {
+        self.peak_memory_usage
+    }
+
+    /// Returns the time spent parsing the pattern.
+    pub fn parse_time(&self) -> Duration {
+        self.parse_time
+    }
+
+    /// Returns the time spent compiling the NFA.
+    pub fn nfa_time(&self) -> Duration {
+        self.nfa_time
+    }
+
+    /// Returns the time spent determinizing the NFA.
+    pub fn determinize_time(&self) -> Duration {
+        self.determinize_time
+    }
+
+    /// Returns the time spent minimizing the DFA, or `None` if minimization
+    /// is disabled.
+    pub fn minimize_time(&self) -> Option<Duration> {
+        self.minimize_time
+    }
+
+    /// Returns the time spent premultiplying the DFA's state identifiers, or
+    /// `None` if premultiplication is disabled.
+    pub fn premultiply_time(&self) -> Option<Duration> {
+        self.premultiply_time
+    }
+
+    /// Returns the total time spent in every phase of the build.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time
+            + self.nfa_time
+            + self.determinize_time
+            + self.minimize_time.unwrap_or(Duration::from_secs(0))
+            + self.premultiply_time.unwrap_or(Duration::from_secs(0))
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for BuildStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "nfa states: {}", self.nfa_states)?;
+        writeln!(f, "byte classes: {}", self.alphabet_len)?;
+        writeln!(f, "dfa states: {}", self.dfa_states)?;
+        match self.minimized_states {
+            None => writeln!(f, "minimized states: (disabled)")?,
+            Some(n) => writeln!(f, "minimized states: {}", n)?,
+        }
+        writeln!(f, "peak memory usage: {} bytes", self.peak_memory_usage)?;
+        writeln!(f, "parse time: {:?}", self.parse_time)?;
+        writeln!(f, "nfa time: {:?}", self.nfa_time)?;
+        writeln!(f, "determinize time: {:?}", self.determinize_time)?;
+        match self.minimize_time {
+            None => writeln!(f, "minimize time: (disabled)")?,
+            Some(t) => writeln!(f, "minimize time: {:?}", t)?,
+        }
+        match self.premultiply_time {
+            None => writeln!(f, "premultiply time: (disabled)")?,
+            Some(t) => writeln!(f, "premultiply time: {:?}", t)?,
+        }
+        write!(f, "total time: {:?}", self.total_time())
+    }
+}
+
+/// A dense DFA using the narrowest representation of state identifiers that
+/// fits its states.
+///
+/// This is returned by
+/// [`dense::Builder::build_auto`](struct.Builder.html#method.build_auto).
+/// Each variant is a [`DenseDFA`](enum.DenseDFA.html) using a different
+/// state identifier representation, and which variant is used depends on
+/// the number of states in the DFA and on the
+/// [`AutoPolicy`](enum.AutoPolicy.html) of the builder.
+///
+/// This type implements the [`DFA`](../trait.DFA.html) trait using `usize`
+/// for state identifiers, which are converted to and from the
+/// representation of the underlying DFA on each transition. Searching with
+/// the trait's search routines, such as `find`, does not incur this cost,
+/// but performance sensitive code that walks the transitions of a DFA
+/// should match on the variants of this type, and use each variant's
+/// implementation of the `DFA` trait directly.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub enum AutoDFA {
+    /// A DFA that uses `u8` for state identifiers.
+    U8(DenseDFA<Vec<u8>, u8>),
+    /// A DFA that uses `u16` for state identifiers.
+    U16(DenseDFA<Vec<u16>, u16>),
+    /// A DFA that uses `u32` for state identifiers.
+    #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+    U32(DenseDFA<Vec<u32>, u32>),
+    /// A DFA that uses `u64` for state identifiers.
+    #[cfg(target_pointer_width = "64")]
+    U64(DenseDFA<Vec<u64>, u64>),
+    /// Hints that destructuring should not be exhaustive.
+    ///
+    /// This enum may grow additional variants, so this makes sure clients
+    /// don't count on exhaustive matching. (Otherwise, adding a new variant
+    /// could break existing code.)
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+#[cfg(feature = "std")]
+impl AutoDFA {
+    /// Convert the given DFA to the narrowest state identifier
+    /// representation that fits all of its states.
+    fn narrowest<T: AsRef<[S]>, S: StateID>(
+        dfa: &Repr<T, S>,
+    ) -> Result<AutoDFA> {
+        if let Ok(dfa) = dfa.to_sized::<u8>() {
+            return Ok(AutoDFA::U8(dfa.into_dense_dfa()));
+        }
+        #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+        {
+            if let Ok(dfa) = dfa.to_sized::<u16>() {
+                return Ok(AutoDFA::U16(dfa.into_dense_dfa()));
+            }
+        }
+        #[cfg(target_pointer_width = "64")]
+        {
+            if let Ok(dfa) = dfa.to_sized::<u32>() {
+                return Ok(AutoDFA::U32(dfa.into_dense_dfa()));
+            }
+        }
+        AutoDFA::widest(dfa)
+    }
+
+    /// Convert the given DFA to the widest state identifier representation
+    /// available on the target platform.
+    #[cfg(target_pointer_width = "64")]
+    fn widest<T: AsRef<[S]>, S: StateID>(
+        dfa: &Repr<T, S>,
+    ) -> Result<AutoDFA> {
+        Ok(AutoDFA::U64(dfa.to_sized::<u64>()?.into_dense_dfa()))
+    }
+
+    /// Convert the given DFA to the widest state identifier representation
+    /// available on the target platform.
+    #[cfg(target_pointer_width = "32")]
+    fn widest<T: AsRef<[S]>, S: StateID>(
+        dfa: &Repr<T, S>,
+    ) -> Result<AutoDFA> {
+        Ok(AutoDFA::U32(dfa.to_sized::<u32>()?.into_dense_dfa()))
+    }
+
+    /// Convert the given DFA to the widest state identifier representation
+    /// available on the target platform.
+    #[cfg(target_pointer_width = "16")]
+    fn widest<T: AsRef<[S]>, S: StateID>(
+        dfa: &Repr<T, S>,
+    ) -> Result<AutoDFA> {
+        Ok(AutoDFA::U16(dfa.to_sized::<u16>()?.into_dense_dfa()))
+    }
+
+    /// Returns the number of bytes used by each state identifier in this
+    /// DFA's transition table.
+    pub fn state_id_size(&self) -> usize {
+        match *self {
+            AutoDFA::U8(_) => 1,
+            AutoDFA::U16(_) => 2,
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(_) => 4,
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(_) => 8,
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    /// Returns the memory usage, in bytes, of this DFA.
+    ///
+    /// See [`DenseDFA::memory_usage`](enum.DenseDFA.html#method.memory_usage)
+    /// for details.
+    pub fn memory_usage(&self) -> usize {
+        match *self {
+            AutoDFA::U8(ref dfa) => dfa.memory_usage(),
+            AutoDFA::U16(ref dfa) => dfa.memory_usage(),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref dfa) => dfa.memory_usage(),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref dfa) => dfa.memory_usage(),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl DFA for AutoDFA {
+    type ID = usize;
+
+    #[inline]
+    fn start_state(&self) -> usize {
+        match *self {
+            AutoDFA::U8(ref r) => r.start_state().to_usize(),
+            AutoDFA::U16(ref r) => r.start_state().to_usize(),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.start_state().to_usize(),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.start_state().to_usize(),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn is_match_state(&self, id: usize) -> bool {
+        match *self {
+            AutoDFA::U8(ref r) => r.is_match_state(StateID::from_usize(id)),
+            AutoDFA::U16(ref r) => r.is_match_state(StateID::from_usize(id)),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.is_match_state(StateID::from_usize(id)),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.is_match_state(StateID::from_usize(id)),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn is_dead_state(&self, id: usize) -> bool {
+        id == dead_id()
+    }
+
+    #[inline]
+    fn is_match_or_dead_state(&self, id: usize) -> bool {
+        self.is_dead_state(id) || self.is_match_state(id)
+    }
+
+    #[inline]
+    fn is_anchored(&self) -> bool {
+        match *self {
+            AutoDFA::U8(ref r) => r.is_anchored(),
+            AutoDFA::U16(ref r) => r.is_anchored(),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.is_anchored(),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.is_anchored(),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn next_state(&self, current: usize, input: u8) -> usize {
+        match *self {
+            AutoDFA::U8(ref r) => {
+                r.next_state(StateID::from_usize(current), input).to_usize()
+            }
+            AutoDFA::U16(ref r) => {
+                r.next_state(StateID::from_usize(current), input).to_usize()
+            }
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => {
+                r.next_state(StateID::from_usize(current), input).to_usize()
+            }
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => {
+                r.next_state(StateID::from_usize(current), input).to_usize()
+            }
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    unsafe fn next_state_unchecked(&self, current: usize, input: u8) -> usize {
+        match *self {
+            AutoDFA::U8(ref r) => {
+                let current = StateID::from_usize(current);
+                r.next_state_unchecked(current, input).to_usize()
+            }
+            AutoDFA::U16(ref r) => {
+                let current = StateID::from_usize(current);
+                r.next_state_unchecked(current, input).to_usize()
+            }
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => {
+                let current = StateID::from_usize(current);
+                r.next_state_unchecked(current, input).to_usize()
+            }
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => {
+                let current = StateID::from_usize(current);
+                r.next_state_unchecked(current, input).to_usize()
+            }
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    // As with `DenseDFA`, we specialize the following methods so that the
+    // case analysis between the different representations is done once
+    // before searching, instead of for every transition.
+
+    #[inline]
+    fn is_match_at(&self, bytes: &[u8], start: usize) -> bool {
+        match *self {
+            AutoDFA::U8(ref r) => r.is_match_at(bytes, start),
+            AutoDFA::U16(ref r) => r.is_match_at(bytes, start),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.is_match_at(bytes, start),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.is_match_at(bytes, start),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn shortest_match_at(&self, bytes: &[u8], start: usize) -> Option<usize> {
+        match *self {
+            AutoDFA::U8(ref r) => r.shortest_match_at(bytes, start),
+            AutoDFA::U16(ref r) => r.shortest_match_at(bytes, start),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.shortest_match_at(bytes, start),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.shortest_match_at(bytes, start),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn find_at(&self, bytes: &[u8], start: usize) -> Option<usize> {
+        match *self {
+            AutoDFA::U8(ref r) => r.find_at(bytes, start),
+            AutoDFA::U16(ref r) => r.find_at(bytes, start),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.find_at(bytes, start),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.find_at(bytes, start),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn rfind_at(&self, bytes: &[u8], start: usize) -> Option<usize> {
+        match *self {
+            AutoDFA::U8(ref r) => r.rfind_at(bytes, start),
+            AutoDFA::U16(ref r) => r.rfind_at(bytes, start),
+            #[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+            AutoDFA::U32(ref r) => r.rfind_at(bytes, start),
+            #[cfg(target_pointer_width = "64")]
+            AutoDFA::U64(ref r) => r.rfind_at(bytes, start),
+            AutoDFA::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
+
 /// A builder for constructing a deterministic finite automaton from regular
 /// expressions.
 ///
@@ -1882,7 +4138,14 @@ pub struct Builder {
     premultiply: bool,
     byte_classes: bool,
     reverse: bool,
+    case_insensitive: bool,
     longest_match: bool,
+    match_kind: MatchKind,
+    match_only: bool,
+    auto_policy: AutoPolicy,
+    dfa_size_limit: Option<usize>,
+    time_limit: Option<Duration>,
+    progress: Option<ProgressCallback>,
 }
 
 #[cfg(feature = "std")]
@@ -1897,7 +4160,14 @@ impl Builder {
             premultiply: true,
             byte_classes: true,
             reverse: false,
+            case_insensitive: false,
             longest_match: false,
+            match_kind: MatchKind::LeftmostFirst,
+            match_only: false,
+            auto_policy: AutoPolicy::Smallest,
+            dfa_size_limit: None,
+            time_limit: None,
+            progress: None,
         }
     }
 
@@ -1939,28 +4209,515 @@ impl Builder {
         }
 
         let nfa = self.build_nfa(pattern)?;
-        let mut dfa =
-            if self.byte_classes {
-                Determinizer::new(&nfa)
-                    .with_byte_classes()
-                    .longest_match(self.longest_match)
-                    .build()
-            } else {
-                Determinizer::new(&nfa)
-                    .longest_match(self.longest_match)
-                    .build()
-            }?;
+        self.build_from_nfa_with_size(&nfa)
+    }
+
+    /// Build a DFA from the given pattern, along with statistics about each
+    /// phase of the build.
+    ///
+    /// The DFA is the same as the one returned by
+    /// [`build`](struct.Builder.html#method.build). Unlike
+    /// [`explain`](struct.Builder.html#method.explain), which reports the
+    /// sizes of each step without producing a DFA, this also measures how
+    /// long each phase took, which makes it possible to find out why a
+    /// pattern is slow to compile.
+    ///
+    /// If there was a problem parsing or compiling the pattern, then an error
+    /// is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let (dfa, stats) = dense::Builder::new()
+    ///     .minimize(true)
+    ///     .build_with_stats(r"\w{3}")?;
+    /// assert_eq!(Some(3), dfa.find(b"abc"));
+    /// assert_eq!(Some(dfa.state_count()), stats.minimized_states());
+    /// assert!(stats.dfa_states() >= dfa.state_count());
+    /// assert!(stats.minimize_time().is_some());
+    /// println!("{}", stats);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_with_stats(
+        &self,
+        pattern: &str,
+    ) -> Result<(DenseDFA<Vec<usize>, usize>, BuildStats)> {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let mut stats = BuildStats::new();
+        let start = Instant::now();
+        let hir = self
+            .parser
+            .build()
+            .parse(pattern)
+            .map_err(Error::syntax)?;
+        stats.parse_time = start.elapsed();
+        let start = Instant::now();
+        let nfa = self.nfa.build(hir)?;
+        stats.nfa_time = start.elapsed();
+        let dfa = self.build_from_nfa_with_stats(&nfa, &mut stats)?;
+        Ok((dfa, stats))
+    }
+
+    /// Build a DFA from the given pattern, choosing its representation
+    /// automatically.
+    ///
+    /// Instead of using this builder's
+    /// [`byte_classes`](struct.Builder.html#method.byte_classes) and
+    /// [`premultiply`](struct.Builder.html#method.premultiply) options and a
+    /// fixed state identifier representation, this picks the combination of
+    /// them that best fits the
+    /// [`auto_policy`](struct.Builder.html#method.auto_policy) of this
+    /// builder. By default, this is the combination with the smallest
+    /// transition table. All other options are respected.
+    ///
+    /// If there was a problem parsing or compiling the pattern, then an error
+    /// is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::DFA;
+    /// use regex_automata::dense::{self, AutoDFA, AutoPolicy};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().build_auto("foo[0-9]+")?;
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    /// match dfa {
+    ///     AutoDFA::U8(ref dfa) => assert!(dfa.state_count() <= 256),
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// let fast = dense::Builder::new()
+    ///     .auto_policy(AutoPolicy::Fastest)
+    ///     .build_auto("foo[0-9]+")?;
+    /// assert_eq!(Some(8), fast.find(b"foo12345"));
+    /// assert!(dfa.memory_usage() < fast.memory_usage());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_auto(&self, pattern: &str) -> Result<AutoDFA> {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let nfa = self.build_nfa(pattern)?;
+        let mut builder = self.clone();
+        builder.byte_classes(self.auto_policy == AutoPolicy::Smallest);
+        let deadline = self.time_limit.map(Deadline::new);
+        let mut dfa = builder.determinize::<usize>(&nfa, deadline)?;
         if self.minimize {
-            dfa.minimize();
+            dfa.minimize(deadline)?;
+        }
+        let premultiply = match self.auto_policy {
+            AutoPolicy::Fastest => true,
+            AutoPolicy::Smallest => {
+                // Premultiplication is free as long as the largest
+                // premultiplied identifier fits in the same representation.
+                let last = dfa.state_count() - 1;
+                match last.checked_mul(dfa.alphabet_len()) {
+                    None => false,
+                    Some(max) => id_size(max) == id_size(last),
+                }
+            }
+        };
+        if premultiply {
+            dfa.premultiply()?;
+        }
+        AutoDFA::narrowest(&dfa)
+    }
+
+    /// Build a DFA that matches any of the given literals.
+    ///
+    /// The literals are compiled without going through the regex parser, so
+    /// they never need to be escaped. Of the parser options of this builder,
+    /// only [`case_insensitive`](struct.Builder.html#method.case_insensitive)
+    /// applies. Each literal that is valid UTF-8 is matched as a sequence
+    /// of codepoints, and every other literal as a sequence of bytes, where
+    /// case insensitivity only applies to ASCII bytes. Like an alternation,
+    /// earlier literals are preferred over later literals with leftmost
+    /// first match semantics. If there are no literals, then the DFA never
+    /// matches.
+    ///
+    /// Whole word matching is not available, since it would require a word
+    /// boundary assertion, which this crate does not support.
+    ///
+    /// For large sets of literals that are always searched with anchored,
+    /// leftmost longest semantics, a
+    /// [`KeywordDFA`](../keyword/struct.KeywordDFA.html) is typically much
+    /// smaller and faster to build.
+    ///
+    /// If there was a problem building the DFA, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new()
+    ///     .case_insensitive(true)
+    ///     .build_literals(&["a+b", "(c)"])?;
+    /// assert_eq!(Some(5), dfa.find(b"xxA+B"));
+    /// assert_eq!(Some(4), dfa.find(b"x(C)"));
+    /// assert_eq!(None, dfa.find(b"aab"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_literals<I, P>(
+        &self,
+        literals: I,
+    ) -> Result<DenseDFA<Vec<usize>, usize>>
+    where I: IntoIterator<Item=P>, P: AsRef<[u8]>
+    {
+        self.build_literals_with_size::<usize, I, P>(literals)
+    }
+
+    /// Build a DFA that matches any of the given literals using a specific
+    /// representation for the DFA's state IDs.
+    ///
+    /// See [`build_literals`](struct.Builder.html#method.build_literals)
+    /// and [`build_with_size`](struct.Builder.html#method.build_with_size)
+    /// for more details.
+    pub fn build_literals_with_size<S, I, P>(
+        &self,
+        literals: I,
+    ) -> Result<DenseDFA<Vec<S>, S>>
+    where S: StateID, I: IntoIterator<Item=P>, P: AsRef<[u8]>
+    {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let nfa = self.nfa.build_literals(literals, self.case_insensitive)?;
+        self.build_from_nfa_with_size(&nfa)
+    }
+
+    /// Build a DFA from the given high level intermediate representation of
+    /// a regular expression.
+    ///
+    /// This permits callers that already parse or transform patterns with
+    /// `regex-syntax` to skip converting them back to a string. Since the
+    /// HIR has already been parsed, the parser options of this builder, such
+    /// as case insensitivity, are ignored. All other options are respected.
+    ///
+    /// Note that unless
+    /// [`allow_invalid_utf8`](struct.Builder.html#method.allow_invalid_utf8)
+    /// is enabled, the HIR must not be able to match invalid UTF-8, just as
+    /// if it had been parsed by this builder.
+    ///
+    /// If there was a problem compiling the HIR, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate regex_syntax;
+    ///
+    /// use regex_automata::{DFA, dense};
+    /// use regex_syntax::hir::{Hir, Repetition, RepetitionKind};
+    /// use regex_syntax::Parser;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let digit = Parser::new().parse("[0-9]").unwrap();
+    /// let hir = Hir::concat(vec![
+    ///     Parser::new().parse("foo").unwrap(),
+    ///     Hir::repetition(Repetition {
+    ///         kind: RepetitionKind::OneOrMore,
+    ///         greedy: true,
+    ///         hir: Box::new(digit),
+    ///     }),
+    /// ]);
+    /// let dfa = dense::Builder::new().build_from_hir(&hir)?;
+    /// assert_eq!(Some(6), dfa.find(b"foo123"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_from_hir(
+        &self,
+        hir: &Hir,
+    ) -> Result<DenseDFA<Vec<usize>, usize>> {
+        self.build_from_hir_with_size::<usize>(hir)
+    }
+
+    /// Build a DFA from the given high level intermediate representation of
+    /// a regular expression using a specific representation for the DFA's
+    /// state IDs.
+    ///
+    /// See [`build_from_hir`](struct.Builder.html#method.build_from_hir)
+    /// and [`build_with_size`](struct.Builder.html#method.build_with_size)
+    /// for more details.
+    pub fn build_from_hir_with_size<S: StateID>(
+        &self,
+        hir: &Hir,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let nfa = self.nfa.build(hir.clone())?;
+        self.build_from_nfa_with_size(&nfa)
+    }
+
+    /// Build a DFA that matches every string within the given Levenshtein
+    /// distance of `query`.
+    ///
+    /// The distance is the number of codepoint insertions, deletions and
+    /// substitutions needed to turn a matched string into `query`. The query
+    /// is always treated literally, so neither the syntax nor the case
+    /// insensitivity options of this builder apply. All other options,
+    /// such as anchoring, reversal and minimization, are respected.
+    ///
+    /// Since the number of DFA states grows quickly with both the length of
+    /// the query and the distance, this is best suited for short keywords
+    /// and small distances.
+    ///
+    /// When anchored, the DFA is a classic Levenshtein automaton: a string
+    /// is within the distance of the query precisely when the DFA is in a
+    /// match state after consuming all of it. To guarantee this, an anchored
+    /// DFA is always built as if with
+    /// [`MatchKind::All`](enum.MatchKind.html#variant.All), regardless of
+    /// the match semantics configured on this builder, since leftmost-first
+    /// semantics would stop reporting matches after the first one found
+    /// (for example, rejecting `foobarx` after accepting `foobar`). When
+    /// unanchored, the configured match semantics are used as for any other
+    /// search. The anchored form is the one needed to
+    /// walk the DFA in lockstep with another automaton, such as a finite
+    /// state transducer of dictionary keys, where the dead state signals
+    /// that no extension of the current key can match.
+    ///
+    /// If there was a problem building the DFA, then an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().build_levenshtein("foobar", 1)?;
+    /// assert_eq!(Some(7), dfa.find(b"a fobar"));
+    /// assert_eq!(Some(8), dfa.find(b"a foxbar"));
+    /// assert_eq!(None, dfa.find(b"a fxxbar"));
+    ///
+    /// // Check whether entire strings are within the distance.
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .build_levenshtein("foobar", 1)?;
+    /// let accepts = |s: &[u8]| {
+    ///     let mut state = dfa.start_state();
+    ///     for &b in s {
+    ///         state = dfa.next_state(state, b);
+    ///     }
+    ///     dfa.is_match_state(state)
+    /// };
+    /// assert!(accepts(b"foobar"));
+    /// assert!(accepts(b"foobr"));
+    /// assert!(accepts(b"foobaz"));
+    /// assert!(accepts(b"foobarx"));
+    /// assert!(!accepts(b"foobarxx"));
+    /// // No string beginning with two edits can match.
+    /// let state = dfa.next_state(dfa.start_state(), b'z');
+    /// assert!(dfa.is_dead_state(dfa.next_state(state, b'z')));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_levenshtein(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<DenseDFA<Vec<usize>, usize>> {
+        self.build_levenshtein_with_size::<usize>(query, distance)
+    }
+
+    /// Build a DFA that matches every string within the given Levenshtein
+    /// distance of `query` using a specific representation for the DFA's
+    /// state IDs.
+    ///
+    /// See [`build_levenshtein`](struct.Builder.html#method.build_levenshtein)
+    /// and [`build_with_size`](struct.Builder.html#method.build_with_size)
+    /// for more details.
+    pub fn build_levenshtein_with_size<S: StateID>(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let nfa = self.nfa.build_levenshtein(query, distance)?;
+        if self.anchored && self.match_kind != MatchKind::All {
+            let mut builder = self.clone();
+            builder.match_kind(MatchKind::All);
+            return builder.build_from_nfa_with_size(&nfa);
+        }
+        self.build_from_nfa_with_size(&nfa)
+    }
+
+    /// Build a report describing each step of compiling the given pattern
+    /// into a DFA with this builder's configuration.
+    ///
+    /// This is useful for understanding why the DFA for a particular pattern
+    /// is large or slow to build. The DFA built along the way is discarded,
+    /// and premultiplication, which never changes the number of states, is
+    /// skipped.
+    ///
+    /// If there was a problem parsing or compiling the pattern, then an error
+    /// is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let report = dense::Builder::new().minimize(true).explain(r"\w{3}")?;
+    /// assert!(report.nfa_states() > 0);
+    /// assert!(report.minimized_states().unwrap() <= report.dfa_states());
+    /// println!("{}", report);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn explain(&self, pattern: &str) -> Result<Explanation> {
+        if self.longest_match && !self.anchored {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        let hir = self
+            .parser
+            .build()
+            .parse(pattern)
+            .map_err(Error::syntax)?;
+        let hir_string = hir.to_string();
+        let nfa = self.nfa.build(hir)?;
+        let deadline = self.time_limit.map(Deadline::new);
+        let mut dfa = self.determinize::<usize>(&nfa, deadline)?;
+        let dfa_states = dfa.state_count();
+        let minimized_states = if self.minimize {
+            dfa.minimize(deadline)?;
+            Some(dfa.state_count())
+        } else {
+            None
+        };
+        Ok(Explanation {
+            hir: hir_string,
+            nfa_states: nfa.len(),
+            alphabet_len: dfa.alphabet_len(),
+            dfa_states,
+            minimized_states,
+            memory_usage: dfa.memory_usage(),
+        })
+    }
+
+    /// Build a DFA from the given NFA.
+    ///
+    /// The NFA determines whether the DFA is anchored or reversed, so the
+    /// [`anchored`](struct.Builder.html#method.anchored) and
+    /// [`reverse`](struct.Builder.html#method.reverse) options of this
+    /// builder, along with its parser options, are ignored. All other
+    /// options are respected.
+    ///
+    /// This permits building several DFAs from the same NFA without parsing
+    /// and compiling the pattern again. NFAs can be built with
+    /// [`build_nfa`](struct.Builder.html#method.build_nfa).
+    ///
+    /// If there was a problem building the DFA, then an error is returned.
+    pub fn build_from_nfa(
+        &self,
+        nfa: &NFA,
+    ) -> Result<DenseDFA<Vec<usize>, usize>> {
+        self.build_from_nfa_with_size::<usize>(nfa)
+    }
+
+    /// Build a DFA from the given NFA using a specific representation for
+    /// the DFA's state IDs.
+    ///
+    /// See [`build_from_nfa`](struct.Builder.html#method.build_from_nfa)
+    /// and [`build_with_size`](struct.Builder.html#method.build_with_size)
+    /// for more details.
+    pub fn build_from_nfa_with_size<S: StateID>(
+        &self,
+        nfa: &NFA,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        self.build_from_nfa_with_stats(nfa, &mut BuildStats::new())
+    }
+
+    /// Build a DFA from the given NFA, recording the sizes and durations of
+    /// determinization, minimization and premultiplication in `stats`.
+    fn build_from_nfa_with_stats<S: StateID>(
+        &self,
+        nfa: &NFA,
+        stats: &mut BuildStats,
+    ) -> Result<DenseDFA<Vec<S>, S>> {
+        if self.longest_match && !nfa.is_anchored() {
+            return Err(Error::unsupported_longest_match());
+        }
+
+        stats.nfa_states = nfa.len();
+        let deadline = self.time_limit.map(Deadline::new);
+        let start = Instant::now();
+        let mut dfa = self.determinize(nfa, deadline)?;
+        stats.determinize_time = start.elapsed();
+        stats.alphabet_len = dfa.alphabet_len();
+        stats.dfa_states = dfa.state_count();
+        stats.peak_memory_usage = dfa.memory_usage();
+        if self.minimize {
+            let start = Instant::now();
+            dfa.minimize(deadline)?;
+            stats.minimize_time = Some(start.elapsed());
+            stats.minimized_states = Some(dfa.state_count());
         }
         if self.premultiply {
+            let start = Instant::now();
             dfa.premultiply()?;
+            stats.premultiply_time = Some(start.elapsed());
         }
         Ok(dfa.into_dense_dfa())
     }
 
-    /// Builds an NFA from the given pattern.
-    pub(crate) fn build_nfa(&self, pattern: &str) -> Result<NFA> {
+    /// Determinize the given NFA without minimizing or premultiplying it.
+    fn determinize<S: StateID>(
+        &self,
+        nfa: &NFA,
+        deadline: Option<Deadline>,
+    ) -> Result<Repr<Vec<S>, S>> {
+        let progress = self.progress.as_ref();
+        let longest_match =
+            self.longest_match || self.match_kind == MatchKind::All;
+        let leftmost_longest = self.match_kind == MatchKind::LeftmostLongest;
+        let dfa =
+            if self.byte_classes {
+                Determinizer::new(nfa)
+                    .with_byte_classes()
+                    .longest_match(longest_match)
+                    .leftmost_longest(leftmost_longest)
+                    .match_only(self.match_only)
+                    .size_limit(self.dfa_size_limit)
+                    .deadline(deadline)
+                    .progress(progress)
+                    .build()
+            } else {
+                Determinizer::new(nfa)
+                    .longest_match(longest_match)
+                    .leftmost_longest(leftmost_longest)
+                    .match_only(self.match_only)
+                    .size_limit(self.dfa_size_limit)
+                    .deadline(deadline)
+                    .progress(progress)
+                    .build()
+            }?;
+        Ok(dfa)
+    }
+
+    /// Build an NFA from the given pattern.
+    ///
+    /// The parser options of this builder are respected, along with the
+    /// options that change the NFA itself: anchoring, reversal, the NFA size
+    /// limit and whether invalid UTF-8 is allowed. See the
+    /// [`nfa`](../nfa/index.html) module for what can be done with an NFA.
+    ///
+    /// If there was a problem parsing or compiling the pattern, then an error
+    /// is returned.
+    pub fn build_nfa(&self, pattern: &str) -> Result<NFA> {
         let hir = self
             .parser
             .build()
@@ -1987,6 +4744,7 @@ impl Builder {
     /// By default this is disabled. It may alternatively be selectively
     /// enabled in the regular expression itself via the `i` flag.
     pub fn case_insensitive(&mut self, yes: bool) -> &mut Builder {
+        self.case_insensitive = yes;
         self.parser.case_insensitive(yes);
         self
     }
@@ -2228,6 +4986,316 @@ impl Builder {
         self.longest_match = yes;
         self
     }
+
+    /// Set the match semantics of the DFA.
+    ///
+    /// With [`MatchKind::All`](enum.MatchKind.html#variant.All), matches are
+    /// never pruned based on the order of alternations or the greediness of
+    /// repetitions. This is useful when such order is incidental, for example
+    /// when patterns are generated, and every match is needed. With
+    /// [`MatchKind::LeftmostLongest`](enum.MatchKind.html#variant.LeftmostLongest),
+    /// the longest of the leftmost matches is found, as in POSIX. See
+    /// [`MatchKind`](enum.MatchKind.html) for more details.
+    ///
+    /// By default this is set to `MatchKind::LeftmostFirst`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::DFA;
+    /// use regex_automata::dense::{self, MatchKind};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().anchored(true).build("Sam|Samwise")?;
+    /// assert_eq!(Some(3), dfa.find(b"Samwise"));
+    ///
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .match_kind(MatchKind::All)
+    ///     .build("Sam|Samwise")?;
+    /// assert_eq!(Some(7), dfa.find(b"Samwise"));
+    ///
+    /// // Unanchored DFAs are supported too.
+    /// let dfa = dense::Builder::new()
+    ///     .match_kind(MatchKind::All)
+    ///     .build("Sam|Samwise")?;
+    /// assert_eq!(Some(6), dfa.shortest_match(b"My Samwise"));
+    /// assert_eq!(Some(10), dfa.find(b"My Samwise"));
+    ///
+    /// // Leftmost-longest semantics find the longest of the leftmost matches.
+    /// let dfa = dense::Builder::new()
+    ///     .match_kind(MatchKind::LeftmostLongest)
+    ///     .build("Sam|Samwise")?;
+    /// assert_eq!(Some(10), dfa.find(b"My Samwise Sam"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn match_kind(&mut self, kind: MatchKind) -> &mut Builder {
+        self.match_kind = kind;
+        self
+    }
+
+    /// Build a DFA that only detects whether a match exists.
+    ///
+    /// When enabled, every match state is collapsed into a single match state
+    /// that can never be left, and none of the states that are reachable only
+    /// after a match are built. The resulting DFA is typically smaller and
+    /// faster to build, especially for unanchored patterns, but it can only
+    /// answer `is_match`. Since the DFA never leaves a match state, the
+    /// `shortest_match` routines report the same offset as usual, while the
+    /// `find` routines report the end of the input whenever a match exists.
+    ///
+    /// A DFA built this way shouldn't be used as part of a
+    /// [`Regex`](../struct.Regex.html), since there is no meaningful end of
+    /// match to search for a start of match from.
+    ///
+    /// By default this is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::DFA;
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let full = dense::Builder::new().build(r"foo\w*")?;
+    /// let small = dense::Builder::new().match_only(true).build(r"foo\w*")?;
+    /// assert!(small.memory_usage() < full.memory_usage());
+    ///
+    /// assert!(small.is_match(b"xyz foobar"));
+    /// assert!(!small.is_match(b"xyz fobar"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn match_only(&mut self, yes: bool) -> &mut Builder {
+        self.match_only = yes;
+        self
+    }
+
+    /// Set the policy used by
+    /// [`build_auto`](struct.Builder.html#method.build_auto) to choose the
+    /// representation of a DFA.
+    ///
+    /// This has no effect on the other build routines.
+    ///
+    /// By default, this is
+    /// [`AutoPolicy::Smallest`](enum.AutoPolicy.html#variant.Smallest).
+    pub fn auto_policy(&mut self, policy: AutoPolicy) -> &mut Builder {
+        self.auto_policy = policy;
+        self
+    }
+
+    /// Set a limit, in bytes, on the size of the DFA's transition table.
+    ///
+    /// When set, determinization stops with an error as soon as the
+    /// transition table grows beyond this limit. This protects against
+    /// patterns whose DFAs are exponentially large, which is important
+    /// when building DFAs from untrusted patterns. Since the DFA is
+    /// determinized before it is minimized, the limit applies to the DFA
+    /// before minimization.
+    ///
+    /// By default there is no limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{dense, ErrorKind};
+    ///
+    /// let err = dense::Builder::new()
+    ///     .dfa_size_limit(Some(1 << 16))
+    ///     .build(r"[01]*1[01]{15}")
+    ///     .unwrap_err();
+    /// match *err.kind() {
+    ///     ErrorKind::DFAExceededSizeLimit { limit } => {
+    ///         assert_eq!(1 << 16, limit);
+    ///     }
+    ///     ref kind => panic!("unexpected error: {:?}", kind),
+    /// }
+    /// ```
+    pub fn dfa_size_limit(&mut self, limit: Option<usize>) -> &mut Builder {
+        self.dfa_size_limit = limit;
+        self
+    }
+
+    /// Set a limit, in bytes, on the approximate size of the NFA compiled
+    /// from a pattern.
+    ///
+    /// When set, compiling the NFA stops with an error as soon as it grows
+    /// beyond this limit, before determinization begins. This catches
+    /// patterns that are expensive to compile on their own, such as large
+    /// bounded repetitions of large Unicode classes. It is independent of
+    /// [`dfa_size_limit`](struct.Builder.html#method.dfa_size_limit),
+    /// since a small NFA may still determinize into a huge DFA.
+    ///
+    /// By default there is no limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{dense, ErrorKind};
+    ///
+    /// let err = dense::Builder::new()
+    ///     .nfa_size_limit(Some(1 << 16))
+    ///     .build(r"\w{100}")
+    ///     .unwrap_err();
+    /// match *err.kind() {
+    ///     ErrorKind::NFAExceededSizeLimit { limit } => {
+    ///         assert_eq!(1 << 16, limit);
+    ///     }
+    ///     ref kind => panic!("unexpected error: {:?}", kind),
+    /// }
+    /// ```
+    pub fn nfa_size_limit(&mut self, limit: Option<usize>) -> &mut Builder {
+        self.nfa.size_limit(limit);
+        self
+    }
+
+    /// Set a limit on the time spent determinizing and minimizing a DFA.
+    ///
+    /// Some patterns, particularly those with large bounded repetitions of
+    /// classes, produce DFAs that are exponentially large and can take
+    /// minutes to build. When a limit is set, building stops with an error
+    /// once the limit has passed. The clock starts once the pattern has been
+    /// compiled to an NFA, which is bounded separately by
+    /// [`nfa_size_limit`](struct.Builder.html#method.nfa_size_limit).
+    ///
+    /// The time is only checked between DFA states, so a build may overrun
+    /// the limit by the time it takes to build a single state. This makes
+    /// the limit a poor fit for hard deadlines, but does make it effective
+    /// against patterns that are slow to build because they have many
+    /// states. For a limit that is independent of the speed of the machine,
+    /// use [`dfa_size_limit`](struct.Builder.html#method.dfa_size_limit)
+    /// instead.
+    ///
+    /// By default there is no limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use regex_automata::{dense, ErrorKind};
+    ///
+    /// let limit = Duration::from_millis(10);
+    /// let err = dense::Builder::new()
+    ///     .time_limit(Some(limit))
+    ///     .build("[01]*1[01]{25}")
+    ///     .unwrap_err();
+    /// match *err.kind() {
+    ///     ErrorKind::TimeLimitExceeded { limit: got } => {
+    ///         assert_eq!(limit, got);
+    ///     }
+    ///     ref kind => panic!("unexpected error: {:?}", kind),
+    /// }
+    /// ```
+    pub fn time_limit(&mut self, limit: Option<Duration>) -> &mut Builder {
+        self.time_limit = limit;
+        self
+    }
+
+    /// Set a callback that is called periodically while the DFA is
+    /// determinized.
+    ///
+    /// The callback receives a [`Progress`](struct.Progress.html) report
+    /// after every 256 DFA states are compiled, which makes it suitable for
+    /// showing progress on long builds. Builds of DFAs with fewer states
+    /// never call it. If the callback returns `false`, then the build stops
+    /// with an [`ErrorKind::Cancelled`](../enum.ErrorKind.html) error.
+    ///
+    /// The callback is shared by clones of this builder, and may be called
+    /// from multiple threads at once when building a
+    /// [`Regex`](../struct.Regex.html).
+    ///
+    /// By default there is no callback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{dense, ErrorKind};
+    ///
+    /// let err = dense::Builder::new()
+    ///     .progress(|progress| progress.dfa_states() < 1000)
+    ///     .build("[01]*1[01]{20}")
+    ///     .unwrap_err();
+    /// match *err.kind() {
+    ///     ErrorKind::Cancelled => {}
+    ///     ref kind => panic!("unexpected error: {:?}", kind),
+    /// }
+    /// ```
+    pub fn progress<F>(&mut self, callback: F) -> &mut Builder
+    where F: Fn(&Progress) -> bool + Send + Sync + 'static
+    {
+        self.progress = Some(ProgressCallback(Arc::new(callback)));
+        self
+    }
+}
+
+/// The number of DFA states compiled between each call to a progress
+/// callback.
+#[cfg(feature = "std")]
+pub(crate) const PROGRESS_INTERVAL: usize = 256;
+
+/// A report of how far the determinization of a DFA has come.
+///
+/// This is given to the callback set by
+/// [`dense::Builder::progress`](struct.Builder.html#method.progress).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub(crate) dfa_states: usize,
+    pub(crate) memory_usage: usize,
+}
+
+#[cfg(feature = "std")]
+impl Progress {
+    /// Returns the number of DFA states built so far, including those whose
+    /// transitions have not been compiled yet.
+    pub fn dfa_states(&self) -> usize {
+        self.dfa_states
+    }
+
+    /// Returns the heap memory, in bytes, used by the DFA's transition table
+    /// so far.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
+    }
+}
+
+/// A progress callback shared by clones of a builder.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub(crate) struct ProgressCallback(
+    pub(crate) Arc<dyn Fn(&Progress) -> bool + Send + Sync>,
+);
+
+#[cfg(feature = "std")]
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ProgressCallback(..)")
+    }
+}
+
+/// A point in time after which building a DFA is abandoned, along with the
+/// limit that it was derived from for error reporting.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Deadline {
+    at: Instant,
+    limit: Duration,
+}
+
+#[cfg(feature = "std")]
+impl Deadline {
+    /// Create a deadline that passes once the given limit has elapsed from
+    /// now.
+    fn new(limit: Duration) -> Deadline {
+        Deadline { at: Instant::now() + limit, limit }
+    }
+
+    /// Return an error if this deadline has passed.
+    pub(crate) fn check(&self) -> Result<()> {
+        if Instant::now() >= self.at {
+            return Err(Error::time_limit_exceeded(self.limit));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature = "std")]
@@ -2237,6 +5305,21 @@ impl Default for Builder {
     }
 }
 
+/// Returns the number of bytes needed to represent the given state
+/// identifier with the narrowest of `u8`, `u16`, `u32` and `u64`.
+#[cfg(feature = "std")]
+fn id_size(id: usize) -> usize {
+    if id >> 8 == 0 {
+        1
+    } else if id >> 16 == 0 {
+        2
+    } else if (id as u64) >> 32 == 0 {
+        4
+    } else {
+        8
+    }
+}
+
 /// Return the given byte as its escaped string form.
 #[cfg(feature = "std")]
 fn escape(b: u8) -> String {
@@ -2248,6 +5331,7 @@ fn escape(b: u8) -> String {
 #[cfg(test)]
 #[allow(dead_code)]
 mod tests {
+    use error::ErrorKind;
     use nfa::NFA;
     use super::*;
 
@@ -2288,6 +5372,441 @@ mod tests {
         assert!(builder.build_with_size::<u8>(pattern).is_err());
     }
 
+    #[test]
+    fn try_from_bytes_roundtrip() {
+        let dfa = Builder::new().build_with_size::<u16>("foo[0-9]+").unwrap();
+        let bytes = dfa.to_bytes_native_endian().unwrap();
+        let dfa: DenseDFA<&[u16], u16> =
+            DenseDFA::try_from_bytes(&bytes).unwrap();
+        assert_eq!(Some(8), dfa.find(b"foo12345"));
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_invalid() {
+        // Serialized DFAs must be aligned, so work with a buffer of u16s
+        // that can be viewed as bytes.
+        fn load(ids: &[u16]) -> Result<()> {
+            let buf = unsafe {
+                slice::from_raw_parts(ids.as_ptr() as *const u8, ids.len() * 2)
+            };
+            DenseDFA::<&[u16], u16>::try_from_bytes(buf).map(|_| ())
+        }
+
+        let dfa = Builder::new()
+            .premultiply(true)
+            .build_with_size::<u16>("foo[0-9]+")
+            .unwrap();
+        let alphabet_len = dfa.repr().alphabet_len() as u16;
+        let state_count = dfa.repr().state_count() as u16;
+        let bytes = dfa.to_bytes_native_endian().unwrap();
+        let ids: Vec<u16> =
+            bytes.chunks(2).map(NativeEndian::read_u16).collect();
+        // The header is 320 bytes, so this is the first transition of the
+        // dead state.
+        let first = 160;
+        let last = ids.len() - 1;
+        assert!(load(&ids).is_ok());
+
+        // truncated transition table
+        assert!(load(&ids[..last]).is_err());
+        // bad label
+        let mut bad = ids.clone();
+        bad[0] = 0;
+        assert!(load(&bad).is_err());
+        // transition that doesn't point to the start of a state
+        let mut bad = ids.clone();
+        bad[last] = 1;
+        assert!(load(&bad).is_err());
+        // transition that points past the last state
+        let mut bad = ids.clone();
+        bad[last] = state_count * alphabet_len;
+        assert!(load(&bad).is_err());
+        // dead state that leaves itself
+        let mut bad = ids.clone();
+        bad[first] = alphabet_len;
+        assert!(load(&bad).is_err());
+
+        // mismatched header fields
+        let mut bad = ids.clone();
+        bad[12] = 0xFEFF_u16.swap_bytes();
+        match *load(&bad).unwrap_err().kind() {
+            ErrorKind::EndiannessMismatch => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+        let mut bad = ids.clone();
+        bad[13] = 1;
+        match *load(&bad).unwrap_err().kind() {
+            ErrorKind::VersionMismatch { expected: 2, found: 1 } => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+        let mut bad = ids.clone();
+        bad[14] = 4;
+        match *load(&bad).unwrap_err().kind() {
+            ErrorKind::StateIDSizeMismatch { expected: 2, found: 4 } => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+        // non-zero reserved bytes
+        let mut bad = ids.clone();
+        bad[31] = 1;
+        assert!(load(&bad).is_err());
+        // valid transition to a different state
+        let mut bad = ids.clone();
+        bad[last] = if bad[last] == 0 { alphabet_len } else { 0 };
+        match *load(&bad).unwrap_err().kind() {
+            ErrorKind::ChecksumMismatch { .. } => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn from_bytes_any_endian_swaps_foreign_bytes() {
+        let dfa = Builder::new()
+            .premultiply(true)
+            .build_with_size::<u16>("foo[0-9]+|ba[rz]")
+            .unwrap();
+        let foreign = if cfg!(target_endian = "little") {
+            dfa.to_bytes_big_endian().unwrap()
+        } else {
+            dfa.to_bytes_little_endian().unwrap()
+        };
+        let native = dfa.to_bytes_native_endian().unwrap();
+        assert!(DenseDFA::<&[u16], u16>::try_from_bytes(&foreign).is_err());
+
+        for bytes in &[&foreign, &native] {
+            // Loading must not depend on the alignment of the bytes.
+            let mut buf = vec![0; bytes.len() + 1];
+            buf[1..].copy_from_slice(bytes);
+            let got: DenseDFA<Vec<u16>, u16> =
+                DenseDFA::from_bytes_any_endian(&buf[1..]).unwrap();
+            assert_eq!(dfa.repr().trans(), got.repr().trans());
+            assert_eq!(dfa.start_state(), got.start_state());
+            assert_eq!(Some(8), got.find(b"foo12345"));
+            assert_eq!(Some(3), got.find(b"baz"));
+        }
+
+        // Redirect a transition to the dead state, which is still valid.
+        let mut bad = foreign.clone();
+        {
+            let id = bad[320..]
+                .chunks_mut(2)
+                .rev()
+                .find(|id| id != &[0, 0])
+                .unwrap();
+            id.copy_from_slice(&[0, 0]);
+        }
+        match *DenseDFA::<Vec<u16>, u16>::from_bytes_any_endian(&bad)
+            .unwrap_err()
+            .kind()
+        {
+            ErrorKind::ChecksumMismatch { .. } => {}
+            ref kind => panic!("unexpected error: {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn levenshtein_matches_brute_force() {
+        fn distance(a: &[u8], b: &[u8]) -> usize {
+            let mut prev: Vec<usize> = (0..b.len() + 1).collect();
+            for (i, &x) in a.iter().enumerate() {
+                let mut cur = vec![i + 1];
+                for (j, &y) in b.iter().enumerate() {
+                    let sub = prev[j] + if x == y { 0 } else { 1 };
+                    cur.push(sub.min(prev[j + 1] + 1).min(cur[j] + 1));
+                }
+                prev = cur;
+            }
+            prev[b.len()]
+        }
+
+        let query = "abc";
+        let mut builder = Builder::new();
+        builder.anchored(true);
+        let fwd = builder.build_levenshtein(query, 1).unwrap();
+        let rev = builder.reverse(true).build_levenshtein(query, 1).unwrap();
+
+        let mut inputs = vec![vec![]];
+        for len in 1..6 {
+            let mut next = vec![];
+            for input in inputs.iter().filter(|i| i.len() == len - 1) {
+                for &b in b"abcx" {
+                    let mut input = input.clone();
+                    input.push(b);
+                    next.push(input);
+                }
+            }
+            inputs.extend(next);
+        }
+        for input in &inputs {
+            let expected = (0..input.len() + 1)
+                .any(|end| distance(&input[..end], query.as_bytes()) <= 1);
+            assert_eq!(expected, fwd.is_match(input), "{:?}", input);
+
+            let expected = (0..input.len() + 1)
+                .any(|start| distance(&input[start..], query.as_bytes()) <= 1);
+            assert_eq!(expected, rev.rfind(input).is_some(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn match_only_agrees_with_is_match() {
+        let patterns = &[r"foo\w*", r"a+b|c", r"[0-9]{2,4}x?", r"(?i)ab"];
+        let inputs: &[&[u8]] = &[
+            b"", b"foo", b"xfoobar", b"fo", b"aab", b"c", b"1", b"123x",
+            b"AB", b"zzz",
+        ];
+        for pattern in patterns {
+            for &anchored in &[false, true] {
+                let mut builder = Builder::new();
+                builder.anchored(anchored);
+                let full = builder.build(pattern).unwrap();
+                let small = builder.match_only(true).build(pattern).unwrap();
+                for input in inputs {
+                    assert_eq!(
+                        full.is_match(input),
+                        small.is_match(input),
+                        "pattern: {:?}, input: {:?}", pattern, input,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn levenshtein_accepts_brute_force() {
+        fn distance(a: &[u8], b: &[u8]) -> usize {
+            let mut prev: Vec<usize> = (0..b.len() + 1).collect();
+            for (i, &x) in a.iter().enumerate() {
+                let mut cur = vec![i + 1];
+                for (j, &y) in b.iter().enumerate() {
+                    let sub = prev[j] + if x == y { 0 } else { 1 };
+                    cur.push(sub.min(prev[j + 1] + 1).min(cur[j] + 1));
+                }
+                prev = cur;
+            }
+            prev[b.len()]
+        }
+
+        let queries = &["", "a", "ab", "aba", "abc"];
+        let mut inputs: Vec<Vec<u8>> = vec![vec![]];
+        for len in 1..6 {
+            let mut next = vec![];
+            for input in inputs.iter().filter(|i| i.len() == len - 1) {
+                for &b in b"abc" {
+                    let mut input = input.clone();
+                    input.push(b);
+                    next.push(input);
+                }
+            }
+            inputs.extend(next);
+        }
+        for &kind in &[
+            MatchKind::LeftmostFirst,
+            MatchKind::LeftmostLongest,
+            MatchKind::All,
+        ] {
+            for query in queries {
+                for k in 0..3 {
+                    let dfa = Builder::new()
+                        .anchored(true)
+                        .match_kind(kind)
+                        .build_levenshtein(query, k)
+                        .unwrap();
+                    for input in &inputs {
+                        let expected =
+                            distance(input, query.as_bytes()) <= k as usize;
+                        assert_eq!(
+                            expected,
+                            accepts(&dfa, input),
+                            "kind: {:?}, query: {:?}, k: {}, input: {:?}",
+                            kind, query, k, input,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn leftmost_longest_matches_brute_force() {
+        let patterns = &[
+            "ab|bcde", "a+?", "b|abcd|c", "c*", "(a|ab)(c|bcd)", "(?:e|de)+",
+        ];
+        let mut inputs: Vec<Vec<u8>> = vec![vec![]];
+        for len in 1..5 {
+            let mut next = vec![];
+            for input in inputs.iter().filter(|i| i.len() == len - 1) {
+                for &b in b"abcde" {
+                    let mut input = input.clone();
+                    input.push(b);
+                    next.push(input);
+                }
+            }
+            inputs.extend(next);
+        }
+        for pattern in patterns {
+            let mut builder = Builder::new();
+            builder.match_kind(MatchKind::LeftmostLongest);
+            let dfa = builder.build(pattern).unwrap();
+            let longest = Builder::new()
+                .anchored(true)
+                .longest_match(true)
+                .build(pattern)
+                .unwrap();
+            for input in &inputs {
+                // The leftmost-longest match ends where the longest match
+                // beginning at the earliest possible position ends.
+                let expected = (0..input.len() + 1)
+                    .filter_map(|start| {
+                        longest.find(&input[start..]).map(|end| start + end)
+                    })
+                    .next();
+                assert_eq!(
+                    expected,
+                    dfa.find(input),
+                    "pattern: {:?}, input: {:?}", pattern, input,
+                );
+            }
+        }
+    }
+
+    /// Return whether the given DFA is in a match state after consuming all
+    /// of the given input.
+    fn accepts<D: DFA>(dfa: &D, input: &[u8]) -> bool {
+        let mut state = dfa.start_state();
+        for &b in input {
+            state = dfa.next_state(state, b);
+        }
+        dfa.is_match_state(state)
+    }
+
+    #[test]
+    fn product_matches_brute_force() {
+        let mut inputs: Vec<Vec<u8>> = vec![vec![]];
+        for len in 1..5 {
+            let prev = inputs.clone();
+            for input in prev.iter().filter(|i| i.len() == len - 1) {
+                for &b in b"ab0-" {
+                    let mut input = input.clone();
+                    input.push(b);
+                    inputs.push(input);
+                }
+            }
+        }
+        let pairs = &[
+            ("[a-b]+", "(?:ab)*"),
+            ("[a-z0-9]*[0-9]", "a[a-z0-9]*"),
+            ("a|b|-", ".?"),
+            ("", "[^b]*"),
+        ];
+        for &(p1, p2) in pairs {
+            let mut builder = Builder::new();
+            builder.anchored(true).longest_match(true);
+            let dfa1 = builder.build(p1).unwrap();
+            let dfa2 = builder
+                .byte_classes(false)
+                .premultiply(false)
+                .build(p2)
+                .unwrap();
+            let both = dfa1.intersect(&dfa2).unwrap();
+            let either = dfa1.union(&dfa2).unwrap();
+            let not = dfa1.complement().unwrap();
+            let minus = dfa1.difference(&dfa2).unwrap();
+            for input in &inputs {
+                assert_eq!(
+                    accepts(&dfa1, input) && accepts(&dfa2, input),
+                    accepts(&both, input),
+                    "{:?} and {:?} on {:?}", p1, p2, input,
+                );
+                assert_eq!(
+                    accepts(&dfa1, input) || accepts(&dfa2, input),
+                    accepts(&either, input),
+                    "{:?} or {:?} on {:?}", p1, p2, input,
+                );
+                assert_eq!(
+                    !accepts(&dfa1, input),
+                    accepts(&not, input),
+                    "not {:?} on {:?}", p1, input,
+                );
+                assert_eq!(
+                    accepts(&dfa1, input) && !accepts(&dfa2, input),
+                    accepts(&minus, input),
+                    "{:?} minus {:?} on {:?}", p1, p2, input,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn equivalence_ignores_configuration() {
+        let same = &[
+            ("a+", "aa*"),
+            ("a|b|c", "[a-c]"),
+            ("(?:ab)*a", "a(?:ba)*"),
+            ("[^a]", "[\\x00-`b-\\x{10FFFF}]"),
+        ];
+        for &(p1, p2) in same {
+            let mut builder = Builder::new();
+            builder.anchored(true).longest_match(true);
+            let dfa1 = builder.build(p1).unwrap();
+            let dfa2 = builder
+                .byte_classes(false)
+                .premultiply(false)
+                .minimize(true)
+                .build(p2)
+                .unwrap();
+            assert!(dfa1.is_equivalent(&dfa2), "{:?} == {:?}", p1, p2);
+            assert!(dfa2.is_equivalent(&dfa1), "{:?} == {:?}", p2, p1);
+        }
+
+        let mut builder = Builder::new();
+        builder.anchored(true).longest_match(true);
+        let dfa1 = builder.build("a*").unwrap();
+        let dfa2 = builder.build("a+").unwrap();
+        let dfa3 = builder.build("a{0,100}").unwrap();
+        assert!(!dfa1.is_equivalent(&dfa2));
+        assert!(!dfa1.is_equivalent(&dfa3));
+        assert!(dfa1.is_equivalent(&dfa1));
+
+        assert!(dfa2.is_subset_of(&dfa1));
+        assert!(dfa3.is_subset_of(&dfa1));
+        assert!(!dfa1.is_subset_of(&dfa2));
+        assert!(!dfa1.is_subset_of(&dfa3));
+        assert!(dfa1.is_subset_of(&dfa1));
+    }
+
+    #[test]
+    fn build_literals_matches_literally() {
+        let none: &[&str] = &[];
+        let dfa = Builder::new().build_literals(none).unwrap();
+        assert_eq!(None, dfa.find(b""));
+        assert_eq!(None, dfa.find(b"abc"));
+        let dfa = Builder::new()
+            .anchored(true)
+            .minimize(true)
+            .build_literals(none)
+            .unwrap();
+        assert!(dfa.is_dead_state(dfa.start_state()));
+
+        let dfa = Builder::new()
+            .anchored(true)
+            .case_insensitive(true)
+            .build_literals([&b"a\xFFb"[..], &b"\xCE\xB4."[..], b""].iter())
+            .unwrap();
+        assert_eq!(Some(3), dfa.find(b"A\xFFB"));
+        assert_eq!(Some(0), dfa.find(b"A\xEFB"));
+        assert_eq!(Some(3), dfa.find("\u{394}.".as_bytes()));
+        assert_eq!(Some(0), dfa.find("\u{394}x".as_bytes()));
+
+        let dfa = Builder::new()
+            .anchored(true)
+            .reverse(true)
+            .build_literals(["ab.", "cd"].iter())
+            .unwrap();
+        assert_eq!(Some(0), dfa.rfind(b"ab."));
+        assert_eq!(None, dfa.rfind(b"abx"));
+        assert_eq!(Some(1), dfa.rfind(b"xcd"));
+    }
+
     fn print_automata(pattern: &str) {
         println!("BUILDING AUTOMATA");
         let (nfa, dfa, mdfa) = build_automata(pattern);
@@ -2372,6 +5891,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sparse_computes_byte_classes() {
+        let pattern = r"[a-z]+[0-9]|\x{2603}";
+        let with = Builder::new().byte_classes(true).build(pattern).unwrap();
+        let without =
+            Builder::new().byte_classes(false).build(pattern).unwrap();
+        let (with, without) =
+            (with.to_sparse().unwrap(), without.to_sparse().unwrap());
+
+        assert!(without.memory_usage() <= with.memory_usage());
+        for haystack in &["abc1", "abc", "x\u{2603}", "Z9"] {
+            let haystack = haystack.as_bytes();
+            assert_eq!(with.find(haystack), without.find(haystack));
+        }
+    }
+
+    #[test]
+    fn build_stats_agree_with_explanation() {
+        let pattern = r"[a-z]+[0-9]|\x{2603}";
+        for &minimize in &[false, true] {
+            let mut builder = Builder::new();
+            builder.minimize(minimize);
+            let (dfa, stats) = builder.build_with_stats(pattern).unwrap();
+            let report = builder.explain(pattern).unwrap();
+            let expected = builder.build(pattern).unwrap();
+            assert_eq!(expected.state_count(), dfa.state_count());
+            assert_eq!(report.nfa_states(), stats.nfa_states());
+            assert_eq!(report.alphabet_len(), stats.alphabet_len());
+            assert_eq!(report.dfa_states(), stats.dfa_states());
+            assert_eq!(report.minimized_states(), stats.minimized_states());
+            assert_eq!(minimize, stats.minimize_time().is_some());
+            assert!(stats.peak_memory_usage() >= dfa.memory_usage());
+            assert!(stats.premultiply_time().is_some());
+        }
+    }
+
+    #[test]
+    fn build_auto_picks_smallest() {
+        for pattern in &[r"[a-z]+[0-9]", r"(?-u)\w{20}", r"(?-u)a[ab]{8}"] {
+            let dfa = Builder::new().build_auto(pattern).unwrap();
+            let fast = Builder::new()
+                .auto_policy(AutoPolicy::Fastest)
+                .build_auto(pattern)
+                .unwrap();
+            let mut sizes = vec![];
+            for &classes in &[false, true] {
+                for &premultiply in &[false, true] {
+                    let other = Builder::new()
+                        .byte_classes(classes)
+                        .premultiply(premultiply)
+                        .build(pattern)
+                        .unwrap();
+                    let shrunk = other.shrink().unwrap().memory_usage();
+                    let narrowest = other
+                        .to_u8()
+                        .map(|d| d.memory_usage())
+                        .or_else(|_| other.to_u16().map(|d| d.memory_usage()))
+                        .or_else(|_| other.to_u32().map(|d| d.memory_usage()))
+                        .unwrap();
+                    assert_eq!(narrowest, shrunk);
+                    sizes.push(shrunk);
+                    for haystack in &["abc1", "xyz", "a\u{2603}b9"] {
+                        let haystack = haystack.as_bytes();
+                        assert_eq!(other.find(haystack), dfa.find(haystack));
+                        assert_eq!(other.find(haystack), fast.find(haystack));
+                    }
+                }
+            }
+            let smallest = sizes.into_iter().min();
+            assert_eq!(Some(dfa.memory_usage()), smallest, "{}", pattern);
+            assert!(fast.memory_usage() >= dfa.memory_usage());
+        }
+
+        let dfa = Builder::new().build_auto(r"(?-u)a[ab]{8}").unwrap();
+        assert_eq!(2, dfa.state_id_size());
+    }
+
+    #[test]
+    fn transitions_agree_with_next_state() {
+        let dfa = Builder::new().build(r"[a-z]+[0-9]|\x{2603}").unwrap();
+        let sparse = dfa.to_sparse().unwrap();
+        assert_eq!(dfa.state_count(), sparse.state_count());
+
+        let mut checked = 0;
+        for (id, trans) in dfa.states() {
+            let mut next = [dead_id(); 256];
+            for (start, end, to) in trans {
+                for b in start..=end {
+                    next[b as usize] = to;
+                }
+            }
+            for (b, &to) in next.iter().enumerate() {
+                assert_eq!(dfa.next_state(id, b as u8), to);
+            }
+            checked += 1;
+        }
+        assert_eq!(dfa.state_count(), checked);
+        for (id, trans) in sparse.states() {
+            let mut count = 0;
+            for (start, end, to) in trans {
+                for b in start..=end {
+                    assert_eq!(sparse.next_state(id, b), to);
+                    count += 1;
+                }
+            }
+            let live = (0..256)
+                .map(|b| sparse.next_state(id, b as u8))
+                .filter(|&to| !sparse.is_dead_state(to))
+                .count();
+            assert_eq!(live, count);
+        }
+    }
+
     fn grapheme_pattern() -> &'static str {
         r"(?x)
             (?:
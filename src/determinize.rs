@@ -2,8 +2,8 @@ use std::collections::HashMap;
 use std::mem;
 use std::rc::Rc;
 
-use dense;
-use error::Result;
+use dense::{self, Deadline, Progress, ProgressCallback};
+use error::{Error, Result};
 use nfa::{self, NFA};
 use sparse_set::SparseSet;
 use state_id::{StateID, dead_id};
@@ -47,6 +47,20 @@ pub(crate) struct Determinizer<'a, S: StateID> {
     scratch_nfa_states: Vec<nfa::StateID>,
     /// Whether to build a DFA that finds the longest possible match.
     longest_match: bool,
+    /// Whether to build a DFA that finds the leftmost longest match.
+    leftmost_longest: bool,
+    /// Scratch space for the ends of each group of NFA states computed by
+    /// `next_leftmost_longest`, for amortizing allocation.
+    scratch_ends: Vec<usize>,
+    /// Whether to build a DFA that only detects whether a match exists.
+    match_only: bool,
+    /// The maximum size, in bytes, of the DFA's transition table.
+    size_limit: Option<usize>,
+    /// The time after which determinization is abandoned.
+    deadline: Option<Deadline>,
+    /// A callback that is periodically told how far determinization has
+    /// come, and which may stop it by returning `false`.
+    progress: Option<&'a ProgressCallback>,
 }
 
 /// An intermediate representation for a DFA state during determinization.
@@ -56,6 +70,14 @@ struct State {
     is_match: bool,
     /// An ordered sequence of NFA states that make up this DFA state.
     nfa_states: Vec<nfa::StateID>,
+    /// When building a leftmost longest DFA, the NFA states are partitioned
+    /// into groups of states that began matching at the same position, with
+    /// groups that began earlier first. This records the end of each group
+    /// in `nfa_states`. Any states after the last group belong to the
+    /// unanchored prefix.
+    ///
+    /// This is always empty for other DFAs.
+    groups: Vec<usize>,
 }
 
 impl<'a, S: StateID> Determinizer<'a, S> {
@@ -73,6 +95,12 @@ impl<'a, S: StateID> Determinizer<'a, S> {
             stack: vec![],
             scratch_nfa_states: vec![],
             longest_match: false,
+            leftmost_longest: false,
+            scratch_ends: vec![],
+            match_only: false,
+            size_limit: None,
+            deadline: None,
+            progress: None,
         }
     }
 
@@ -93,6 +121,52 @@ impl<'a, S: StateID> Determinizer<'a, S> {
         self
     }
 
+    /// Instruct the determinizer to build a DFA that recognizes the leftmost
+    /// longest match, as in POSIX. This takes precedence over
+    /// `longest_match`, with which it coincides for anchored NFAs.
+    pub fn leftmost_longest(mut self, yes: bool) -> Determinizer<'a, S> {
+        self.leftmost_longest = yes;
+        self
+    }
+
+    /// Instruct the determinizer to build a DFA that only detects whether a
+    /// match exists. Every match state in such a DFA is collapsed into a
+    /// single match state whose transitions all lead back to itself, so none
+    /// of the states that would only be reachable after a match are built.
+    pub fn match_only(mut self, yes: bool) -> Determinizer<'a, S> {
+        self.match_only = yes;
+        self
+    }
+
+    /// Instruct the determinizer to stop with an error once the transition
+    /// table of the DFA exceeds the given size, in bytes.
+    pub fn size_limit(mut self, limit: Option<usize>) -> Determinizer<'a, S> {
+        self.size_limit = limit;
+        self
+    }
+
+    /// Instruct the determinizer to stop with an error once the given
+    /// deadline passes. The deadline is checked before each DFA state is
+    /// compiled.
+    pub fn deadline(
+        mut self,
+        deadline: Option<Deadline>,
+    ) -> Determinizer<'a, S> {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Instruct the determinizer to call the given callback after every
+    /// `dense::PROGRESS_INTERVAL` DFA states are compiled. If the callback
+    /// returns `false`, then determinization stops with an error.
+    pub fn progress(
+        mut self,
+        callback: Option<&'a ProgressCallback>,
+    ) -> Determinizer<'a, S> {
+        self.progress = callback;
+        self
+    }
+
     /// Build the DFA. If there was a problem constructing the DFA (e.g., if
     /// the chosen state identifier representation is too small), then an error
     /// is returned.
@@ -101,7 +175,31 @@ impl<'a, S: StateID> Determinizer<'a, S> {
             self.dfa.byte_classes().representatives().collect();
         let mut sparse = self.new_sparse_set();
         let mut uncompiled = vec![self.add_start(&mut sparse)?];
+        let mut compiled = 0;
         while let Some(dfa_id) = uncompiled.pop() {
+            if let Some(ref deadline) = self.deadline {
+                deadline.check()?;
+            }
+            compiled += 1;
+            if let Some(callback) = self.progress {
+                if compiled % dense::PROGRESS_INTERVAL == 0 {
+                    let progress = Progress {
+                        dfa_states: self.builder_states.len(),
+                        memory_usage: self.dfa.memory_usage(),
+                    };
+                    if !(callback.0)(&progress) {
+                        return Err(Error::cancelled());
+                    }
+                }
+            }
+            if self.match_only
+                && self.builder_states[dfa_id.to_usize()].is_match
+            {
+                for &b in &representative_bytes {
+                    self.dfa.add_transition(dfa_id, b, dfa_id);
+                }
+                continue;
+            }
             for &b in &representative_bytes {
                 let (next_dfa_id, is_new) = self.cached_state(
                     dfa_id, b, &mut sparse,
@@ -145,10 +243,19 @@ impl<'a, S: StateID> Determinizer<'a, S> {
         sparse: &mut SparseSet,
     ) -> Result<(S, bool)> {
         sparse.clear();
-        // Compute the set of all reachable NFA states, including epsilons.
-        self.next(dfa_id, b, sparse);
-        // Build a candidate state and check if it has already been built.
-        let state = self.new_state(sparse);
+        // Compute the set of all reachable NFA states, including epsilons,
+        // and build a candidate state to check if it has already been built.
+        let state =
+            if self.leftmost_longest {
+                let mut ends = mem::replace(&mut self.scratch_ends, vec![]);
+                self.next_leftmost_longest(dfa_id, b, sparse, &mut ends);
+                let state = self.new_state_leftmost_longest(sparse, &ends);
+                self.scratch_ends = ends;
+                state
+            } else {
+                self.next(dfa_id, b, sparse);
+                self.new_state(sparse)
+            };
         if let Some(&cached_id) = self.cache.get(&state) {
             // Since we have a cached state, put the constructed state's
             // memory back into our scratch space, so that it can be reused.
@@ -181,6 +288,43 @@ impl<'a, S: StateID> Determinizer<'a, S> {
         }
     }
 
+    /// Like `next`, but for building a leftmost longest DFA.
+    ///
+    /// The NFA states of each group of the given DFA state, and then of the
+    /// unanchored prefix, are followed in turn. The end of the NFA states
+    /// reached from each of them is recorded in `ends`. Since a state that
+    /// was already reached is never added again, each state reached belongs
+    /// to the earliest group that reaches it, which is precisely the group
+    /// whose match would begin earliest.
+    fn next_leftmost_longest(
+        &mut self,
+        dfa_id: S,
+        b: u8,
+        next_nfa_states: &mut SparseSet,
+        ends: &mut Vec<usize>,
+    ) {
+        next_nfa_states.clear();
+        ends.clear();
+        let state = self.builder_states[dfa_id.to_usize()].clone();
+        let mut start = 0;
+        let group_ends =
+            state.groups.iter().cloned().chain(Some(state.nfa_states.len()));
+        for end in group_ends {
+            for &nfa_id in &state.nfa_states[start..end] {
+                match *self.nfa.state(nfa_id) {
+                    nfa::State::Union { .. } | nfa::State::Match => {}
+                    nfa::State::Range { start, end, next } => {
+                        if start <= b && b <= end {
+                            self.epsilon_closure(next, next_nfa_states);
+                        }
+                    }
+                }
+            }
+            ends.push(next_nfa_states.len());
+            start = end;
+        }
+    }
+
     /// Compute the epsilon closure for the given NFA state.
     fn epsilon_closure(&mut self, start: nfa::StateID, set: &mut SparseSet) {
         if !self.nfa.state(start).is_epsilon() {
@@ -216,8 +360,20 @@ impl<'a, S: StateID> Determinizer<'a, S> {
     fn add_start(&mut self, sparse: &mut SparseSet) -> Result<S> {
         sparse.clear();
         self.epsilon_closure(self.nfa.start(), sparse);
-        let state = self.new_state(&sparse);
-        let id = self.add_state(state)?;
+        let state =
+            if self.leftmost_longest {
+                self.new_state_leftmost_longest(sparse, &[sparse.len()])
+            } else {
+                self.new_state(&sparse)
+            };
+        // A start state without any NFA states can never match, such as the
+        // start state of an NFA for an empty set of literals. It must be the
+        // existing dead state, since adding it again would replace the dead
+        // state in the cache.
+        let id = match self.cache.get(&state) {
+            Some(&id) => id,
+            None => self.add_state(state)?,
+        };
         self.dfa.set_start_state(id);
         Ok(id)
     }
@@ -228,6 +384,11 @@ impl<'a, S: StateID> Determinizer<'a, S> {
     /// dead state for all possible inputs.
     fn add_state(&mut self, state: State) -> Result<S> {
         let id = self.dfa.add_empty_state()?;
+        if let Some(limit) = self.size_limit {
+            if self.dfa.memory_usage() > limit {
+                return Err(Error::dfa_exceeded_size_limit(limit));
+            }
+        }
         let rstate = Rc::new(state);
         self.builder_states.push(rstate.clone());
         self.cache.insert(rstate, id);
@@ -239,6 +400,7 @@ impl<'a, S: StateID> Determinizer<'a, S> {
         let mut state = State {
             is_match: false,
             nfa_states: mem::replace(&mut self.scratch_nfa_states, vec![]),
+            groups: vec![],
         };
         state.nfa_states.clear();
 
@@ -256,6 +418,72 @@ impl<'a, S: StateID> Determinizer<'a, S> {
                 nfa::State::Union { .. } => {}
             }
         }
+        if self.match_only && state.is_match {
+            // Forgetting the NFA states makes every match state equivalent,
+            // which in turn makes the cache map them all to one DFA state.
+            state.nfa_states.clear();
+        }
+        state
+    }
+
+    /// Like `new_state`, but for building a leftmost longest DFA, where
+    /// `ends` records the end of each group of NFA states in the given set.
+    ///
+    /// States of the unanchored prefix in any group are moved after every
+    /// other state, and the remaining states of each group form a group of
+    /// the new DFA state. In particular, the states that the unanchored
+    /// prefix reaches without its own states form a new group that begins
+    /// matching at the current position.
+    ///
+    /// Once a group contains a match, no match can begin later than the
+    /// matches of that group, so every later group, including the unanchored
+    /// prefix, is dropped.
+    fn new_state_leftmost_longest(
+        &mut self,
+        set: &SparseSet,
+        ends: &[usize],
+    ) -> State {
+        let mut state = State {
+            is_match: false,
+            nfa_states: mem::replace(&mut self.scratch_nfa_states, vec![]),
+            groups: vec![],
+        };
+        state.nfa_states.clear();
+
+        let mut prefix = vec![];
+        let mut start = 0;
+        for &end in ends {
+            let mut group_match = false;
+            for &id in &set.as_slice()[start..end] {
+                match *self.nfa.state(id) {
+                    nfa::State::Range { .. } => {
+                        if self.nfa.is_unanchored_prefix(id) {
+                            prefix.push(id);
+                        } else {
+                            state.nfa_states.push(id);
+                        }
+                    }
+                    nfa::State::Match => group_match = true,
+                    nfa::State::Union { .. } => {}
+                }
+            }
+            if state.groups.last().cloned().unwrap_or(0)
+                < state.nfa_states.len()
+            {
+                state.groups.push(state.nfa_states.len());
+            }
+            if group_match {
+                state.is_match = true;
+                prefix.clear();
+                break;
+            }
+            start = end;
+        }
+        state.nfa_states.extend(prefix);
+        if self.match_only && state.is_match {
+            state.nfa_states.clear();
+            state.groups.clear();
+        }
         state
     }
 
@@ -268,6 +496,6 @@ impl<'a, S: StateID> Determinizer<'a, S> {
 impl State {
     /// Create a new empty dead state.
     fn dead() -> State {
-        State { nfa_states: vec![], is_match: false }
+        State { nfa_states: vec![], is_match: false, groups: vec![] }
     }
 }
@@ -1,3 +1,13 @@
+use core::slice;
+#[cfg(feature = "std")]
+use std::vec;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+#[cfg(feature = "std")]
+use classes::ByteClasses;
+#[cfg(feature = "std")]
+use language::Graph;
 use state_id::StateID;
 
 /// A trait describing the interface of a deterministic finite automaton (DFA).
@@ -239,7 +249,12 @@ pub trait DFA {
 
         let mut state = self.start_state();
         if self.is_match_or_dead_state(state) {
-            return if self.is_dead_state(state) { None } else { Some(0) };
+            return
+                if self.is_dead_state(state) {
+                    None
+                } else {
+                    Some(start)
+                };
         }
         for (i, &b) in bytes[start..].iter().enumerate() {
             state = unsafe { self.next_state_unchecked(state, b) };
@@ -320,6 +335,794 @@ pub trait DFA {
         }
         last_match
     }
+
+    /// Like `find`, but when no match is found, reports why.
+    ///
+    /// On success, this returns the end of the leftmost match, exactly like
+    /// `find`. On failure, the returned [`NoMatch`](struct.NoMatch.html)
+    /// records where the DFA entered a dead state (if it did at all) and the
+    /// length of the longest prefix of `bytes` from which a match was still
+    /// possible. This is useful for understanding near misses without
+    /// walking the DFA's transitions manually.
+    ///
+    /// Note that the diagnostic is most useful with anchored DFAs. An
+    /// unanchored DFA can begin a new match at any position, so it rarely
+    /// enters a dead state.
+    ///
+    /// This routine is meant for debugging. It is not optimized and it always
+    /// scans the entire input when there is no match.
+    ///
+    /// # Example
+    ///
+    /// This example shows how to use this method with a
+    /// [`DenseDFA`](enum.DenseDFA.html).
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().anchored(true).build("foo[0-9]+bar")?;
+    /// assert_eq!(Ok(11), dfa.diagnose(b"foo12345bar"));
+    ///
+    /// let err = dfa.diagnose(b"foo123xbar").unwrap_err();
+    /// assert_eq!(Some(6), err.dead_at());
+    /// assert_eq!(Some(b'x'), err.byte());
+    /// assert_eq!(6, err.viable_len());
+    ///
+    /// // Running out of input isn't a dead transition.
+    /// let err = dfa.diagnose(b"foo123").unwrap_err();
+    /// assert_eq!(None, err.dead_at());
+    /// assert_eq!(6, err.viable_len());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[inline(never)]
+    fn diagnose(&self, bytes: &[u8]) -> Result<usize, NoMatch> {
+        let mut state = self.start_state();
+        if self.is_dead_state(state) {
+            return Err(NoMatch { dead_at: None, byte: None, viable_len: 0 });
+        }
+        let mut last_match =
+            if self.is_match_state(state) { Some(0) } else { None };
+        for (i, &b) in bytes.iter().enumerate() {
+            state = self.next_state(state, b);
+            if self.is_dead_state(state) {
+                return match last_match {
+                    Some(end) => Ok(end),
+                    None => Err(NoMatch {
+                        dead_at: Some(i),
+                        byte: Some(b),
+                        viable_len: i,
+                    }),
+                };
+            }
+            if self.is_match_state(state) {
+                last_match = Some(i + 1);
+            }
+        }
+        match last_match {
+            Some(end) => Ok(end),
+            None => Err(NoMatch {
+                dead_at: None,
+                byte: None,
+                viable_len: bytes.len(),
+            }),
+        }
+    }
+
+    /// Run a search beginning at each of the given offsets, and write the
+    /// end of each match found to the corresponding slot in `ends`.
+    ///
+    /// Each search treats its offset as the beginning of the input. That is,
+    /// `ends[i]` is set to the same value as
+    /// `self.find(&bytes[starts[i]..]).map(|end| starts[i] + end)`. In
+    /// particular, an anchored DFA reports only matches that begin precisely
+    /// at each offset, which makes this useful for verifying candidate match
+    /// positions found by some other means, such as a fast literal scan.
+    ///
+    /// Running every probe through one call avoids recomputing the start
+    /// state and checking the anchoring of this DFA for each offset. The
+    /// offsets may be given in any order, but probes of nearby offsets in
+    /// ascending order tend to make the best use of the CPU's caches.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `starts` and `ends` have different lengths, or if any
+    /// offset is greater than `bytes.len()`.
+    ///
+    /// # Example
+    ///
+    /// This example shows how to use this method with a
+    /// [`DenseDFA`](enum.DenseDFA.html).
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().anchored(true).build("foo[0-9]+")?;
+    /// let haystack = b"foo1 foo foo23";
+    /// let mut ends = [None; 3];
+    /// dfa.probe_many(haystack, &[0, 5, 9], &mut ends);
+    /// assert_eq!([Some(4), None, Some(14)], ends);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[inline(never)]
+    fn probe_many(
+        &self,
+        bytes: &[u8],
+        starts: &[usize],
+        ends: &mut [Option<usize>],
+    ) {
+        assert_eq!(starts.len(), ends.len(), "one end is needed per start");
+        let start_state = self.start_state();
+        let start_match = self.is_match_state(start_state);
+        for (&start, end) in starts.iter().zip(ends.iter_mut()) {
+            let mut state = start_state;
+            let mut last_match = if start_match { Some(start) } else { None };
+            for (i, &b) in bytes[start..].iter().enumerate() {
+                state = unsafe { self.next_state_unchecked(state, b) };
+                if self.is_match_or_dead_state(state) {
+                    if self.is_dead_state(state) {
+                        break;
+                    }
+                    last_match = Some(start + i + 1);
+                }
+            }
+            *end = last_match;
+        }
+    }
+
+    /// Returns true if and only if the input yielded by the given chunks
+    /// matches this DFA.
+    ///
+    /// The chunks are searched as if they were concatenated into one
+    /// contiguous slice, without copying them. This stops asking for chunks
+    /// in precisely the same circumstances as `is_match`.
+    ///
+    /// # Example
+    ///
+    /// This example shows how to search the contents of a `VecDeque`, which
+    /// may be split into two slices.
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = DenseDFA::new("foo[0-9]+bar")?;
+    /// let mut buf: VecDeque<u8> = b"xfoo12".iter().cloned().collect();
+    /// buf.pop_front();
+    /// buf.extend(b"3bar");
+    /// let (head, tail) = buf.as_slices();
+    /// assert!(dfa.is_match_chunks([head, tail].iter()));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[inline(never)]
+    fn is_match_chunks<C: Chunks>(&self, mut chunks: C) -> bool {
+        let mut state = self.start_state();
+        if self.is_match_or_dead_state(state) {
+            return self.is_match_state(state);
+        }
+        while let Some(chunk) = chunks.next_chunk() {
+            for &b in chunk {
+                state = unsafe { self.next_state_unchecked(state, b) };
+                if self.is_match_or_dead_state(state) {
+                    return self.is_match_state(state);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the first position at which a match is found in the input
+    /// yielded by the given chunks, using the same semantics as
+    /// `shortest_match`.
+    ///
+    /// The chunks are searched as if they were concatenated into one
+    /// contiguous slice, without copying them, and the offset returned is
+    /// relative to the start of the first chunk. Offsets are reported as
+    /// `u64`, so that they cannot overflow on targets where `usize` is
+    /// smaller, even when a chunk source reuses its buffer to provide more
+    /// input than fits in memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = DenseDFA::new("foo[0-9]+")?;
+    /// let chunks: &[&[u8]] = &[b"xxf", b"o", b"o12", b"3yy"];
+    /// assert_eq!(Some(6), dfa.shortest_match_chunks(chunks.iter()));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[inline(never)]
+    fn shortest_match_chunks<C: Chunks>(&self, mut chunks: C) -> Option<u64> {
+        let mut state = self.start_state();
+        if self.is_match_or_dead_state(state) {
+            return
+                if self.is_dead_state(state) {
+                    None
+                } else {
+                    Some(0)
+                };
+        }
+        let mut offset = 0u64;
+        while let Some(chunk) = chunks.next_chunk() {
+            for (i, &b) in chunk.iter().enumerate() {
+                state = unsafe { self.next_state_unchecked(state, b) };
+                if self.is_match_or_dead_state(state) {
+                    return
+                        if self.is_dead_state(state) {
+                            None
+                        } else {
+                            Some(offset + i as u64 + 1)
+                        };
+                }
+            }
+            offset += chunk.len() as u64;
+        }
+        None
+    }
+
+    /// Returns the end offset of the longest match in the input yielded by
+    /// the given chunks, using the same semantics as `find`.
+    ///
+    /// The chunks are searched as if they were concatenated into one
+    /// contiguous slice, without copying them, and the offset returned is
+    /// relative to the start of the first chunk. Matches may span any number
+    /// of chunks. As with `shortest_match_chunks`, offsets are reported as
+    /// `u64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = DenseDFA::new("foo[0-9]+")?;
+    /// let chunks: &[&[u8]] = &[b"xxf", b"o", b"o12", b"3yy"];
+    /// assert_eq!(Some(8), dfa.find_chunks(chunks.iter()));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[inline(never)]
+    fn find_chunks<C: Chunks>(&self, mut chunks: C) -> Option<u64> {
+        let mut state = self.start_state();
+        let mut last_match =
+            if self.is_dead_state(state) {
+                return None;
+            } else if self.is_match_state(state) {
+                Some(0)
+            } else {
+                None
+            };
+        let mut offset = 0u64;
+        while let Some(chunk) = chunks.next_chunk() {
+            for (i, &b) in chunk.iter().enumerate() {
+                state = unsafe { self.next_state_unchecked(state, b) };
+                if self.is_match_or_dead_state(state) {
+                    if self.is_dead_state(state) {
+                        return last_match;
+                    }
+                    last_match = Some(offset + i as u64 + 1);
+                }
+            }
+            offset += chunk.len() as u64;
+        }
+        last_match
+    }
+
+    /// Returns true if and only if this DFA is in a match state before
+    /// reading any input.
+    ///
+    /// The queries `matches_empty`, `min_match_len`, `max_match_len` and
+    /// `is_finite_language` describe the strings that lead from the start
+    /// state of this DFA to a match state. For an anchored DFA built with
+    /// [`longest_match`](dense/struct.Builder.html#method.longest_match),
+    /// these are precisely the strings matched by its pattern. An unanchored
+    /// DFA can match after any prefix, so its matches are never bounded in
+    /// length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// assert!(DenseDFA::new("a*")?.matches_empty());
+    /// assert!(!DenseDFA::new("a+")?.matches_empty());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    fn matches_empty(&self) -> bool {
+        self.is_match_state(self.start_state())
+    }
+
+    /// Returns the length of the shortest string after which this DFA is in
+    /// a match state, or `None` if this DFA never matches.
+    ///
+    /// This can be used to skip haystacks that are too short to match. The
+    /// length is found by a breadth first search of the states that are
+    /// reachable from the start state.
+    ///
+    /// This method is only available when the `std` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = DenseDFA::new("[0-9]{3}-[0-9]{4}|[0-9]{10}")?;
+    /// assert_eq!(Some(8), dfa.min_match_len());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    fn min_match_len(&self) -> Option<usize> {
+        Graph::new(self, &ByteClasses::singletons()).min_match_len()
+    }
+
+    /// Returns the length of the longest string after which this DFA is in
+    /// a match state, or `None` if there is no bound on its length or if
+    /// this DFA never matches.
+    ///
+    /// This can be used to reject patterns whose matches may be arbitrarily
+    /// long. The length is found by searching for a cycle among the states
+    /// that are reachable from the start state and from which a match state
+    /// can be reached.
+    ///
+    /// This method is only available when the `std` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// let dfa = builder.build("[0-9]{3}-[0-9]{4}|[0-9]{10}")?;
+    /// assert_eq!(Some(10), dfa.max_match_len());
+    /// assert_eq!(None, builder.build("[0-9]+")?.max_match_len());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    fn max_match_len(&self) -> Option<usize> {
+        Graph::new(self, &ByteClasses::singletons()).max_match_len()
+    }
+
+    /// Returns true if and only if there are finitely many strings after
+    /// which this DFA is in a match state.
+    ///
+    /// This is true when the lengths of those strings are bounded, which
+    /// includes the case in which this DFA never matches.
+    ///
+    /// This method is only available when the `std` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let mut builder = dense::Builder::new();
+    /// builder.anchored(true).longest_match(true);
+    /// assert!(builder.build("foo|bar|quux")?.is_finite_language());
+    /// assert!(!builder.build("fo+")?.is_finite_language());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    fn is_finite_language(&self) -> bool {
+        let graph = Graph::new(self, &ByteClasses::singletons());
+        graph.max_match_len().is_some() || graph.min_match_len().is_none()
+    }
+}
+
+/// A cursor over input that is split into several non-contiguous chunks,
+/// such as the leaves of a rope or a queue of network buffers.
+///
+/// Chunks are yielded in order, and the input is the concatenation of every
+/// chunk. Unlike an iterator, each chunk only needs to live until the next
+/// chunk is requested, which permits implementations to reuse an internal
+/// buffer.
+///
+/// This is implemented for iterators over a slice of chunks and, when the
+/// `std` feature is enabled, for owning iterators over a `Vec` of chunks.
+pub trait Chunks {
+    /// Return the next chunk of input, or `None` if there is no more input.
+    ///
+    /// Empty chunks are permitted.
+    fn next_chunk(&mut self) -> Option<&[u8]>;
+}
+
+impl<'a, 'b> Chunks for slice::Iter<'b, &'a [u8]> {
+    fn next_chunk(&mut self) -> Option<&[u8]> {
+        self.next().cloned()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Chunks for vec::IntoIter<&[u8]> {
+    fn next_chunk(&mut self) -> Option<&[u8]> {
+        self.next()
+    }
+}
+
+/// A description of why a [`DFA::diagnose`](trait.DFA.html#method.diagnose)
+/// search failed to find a match.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NoMatch {
+    dead_at: Option<usize>,
+    byte: Option<u8>,
+    viable_len: usize,
+}
+
+impl NoMatch {
+    /// Returns the position of the input byte that caused the DFA to enter a
+    /// dead state.
+    ///
+    /// If the DFA never entered a dead state, then this returns `None`. In
+    /// that case, the input ended before a match could be found, or the DFA
+    /// can never match anything at all.
+    pub fn dead_at(&self) -> Option<usize> {
+        self.dead_at
+    }
+
+    /// Returns the input byte that caused the DFA to enter a dead state.
+    ///
+    /// This is `None` precisely when `dead_at` is `None`.
+    pub fn byte(&self) -> Option<u8> {
+        self.byte
+    }
+
+    /// Returns the length of the longest prefix of the input after which
+    /// a match was still possible.
+    ///
+    /// When the DFA entered a dead state, this is equal to the position of
+    /// the offending byte. Otherwise, it is equal to the length of the input,
+    /// except when the DFA can never match anything, in which case it is `0`.
+    pub fn viable_len(&self) -> usize {
+        self.viable_len
+    }
+}
+
+/// The state of a validation performed by an
+/// [`Incremental`](struct.Incremental.html) search state.
+///
+/// This is returned by
+/// [`Incremental::verdict`](struct.Incremental.html#method.verdict).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    /// The input given so far is matched by the DFA. More input may change
+    /// this.
+    Accept,
+    /// Neither the input given so far nor any extension of it can be matched
+    /// by the DFA.
+    Reject,
+    /// The input given so far is not matched by the DFA, but some extension
+    /// of it might be.
+    Undecided,
+}
+
+/// A search state that is advanced incrementally as input becomes available.
+///
+/// This answers, at any point, whether some extension of the input fed so far
+/// could still be matched by the DFA. This is useful for interactive
+/// validation, where input arrives one keystroke at a time, or for parser
+/// lookahead. The underlying DFA is usually anchored, since unanchored DFAs
+/// can begin a new match at any position and are therefore almost always
+/// viable.
+///
+/// A search state also counts the bytes fed to it and remembers where the
+/// most recent match ended. These positions are absolute offsets into the
+/// entire stream, and are reported as `u64` so that they cannot wrap around
+/// when searching streams larger than `usize` on 32-bit targets.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::{Incremental, dense};
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new().anchored(true).build("[0-9]{3}-[0-9]{4}")?;
+/// let mut input = Incremental::new(&dfa);
+///
+/// assert!(input.feed(b"555"));
+/// assert!(!input.is_match());
+/// assert!(input.feed(b"-123"));
+/// assert!(input.feed(b"4"));
+/// assert!(input.is_match());
+///
+/// input.reset();
+/// assert!(!input.feed(b"55x"));
+/// assert!(!input.is_viable());
+/// # Ok(()) }; example().unwrap()
+/// ```
+#[derive(Clone, Debug)]
+pub struct Incremental<D: DFA> {
+    dfa: D,
+    state: D::ID,
+    offset: u64,
+    last_match: Option<u64>,
+}
+
+impl<D: DFA> Incremental<D> {
+    /// Create a new incremental search state for the given DFA, positioned
+    /// at the beginning of the input.
+    pub fn new(dfa: D) -> Incremental<D> {
+        let mut input = Incremental {
+            state: dfa.start_state(),
+            dfa,
+            offset: 0,
+            last_match: None,
+        };
+        input.reset();
+        input
+    }
+
+    /// Advance this search state with the given bytes, which are treated as a
+    /// continuation of all bytes previously given.
+    ///
+    /// This returns the same value as `is_viable` after consuming the bytes.
+    /// Once the search state is no longer viable, the remaining input is not
+    /// inspected.
+    pub fn feed(&mut self, bytes: &[u8]) -> bool {
+        for (i, &b) in bytes.iter().enumerate() {
+            if self.dfa.is_dead_state(self.state) {
+                break;
+            }
+            self.state = self.dfa.next_state(self.state, b);
+            if self.dfa.is_match_state(self.state) {
+                self.last_match = Some(self.offset + i as u64 + 1);
+            }
+        }
+        self.offset += bytes.len() as u64;
+        self.is_viable()
+    }
+
+    /// Returns true if and only if some extension of the input given so far,
+    /// including the empty extension, could be matched by the DFA.
+    ///
+    /// Once this returns false, it will continue to return false until this
+    /// search state is reset.
+    pub fn is_viable(&self) -> bool {
+        !self.dfa.is_dead_state(self.state)
+    }
+
+    /// Returns true if and only if the DFA matches the input given so far.
+    ///
+    /// For an anchored DFA, this means a match begins at the start of the
+    /// input and ends at its current end.
+    pub fn is_match(&self) -> bool {
+        self.dfa.is_match_state(self.state)
+    }
+
+    /// Returns whether the input given so far is a complete match, can never
+    /// become a match or might still become a match with more input.
+    ///
+    /// This makes a search state usable as a streaming validator for an
+    /// entire value, such as a URI or a token received over the wire,
+    /// without buffering the value. The value is valid precisely when the
+    /// verdict is `Accept` once all of it has been fed, and invalid as soon
+    /// as the verdict is `Reject`.
+    ///
+    /// For this to work, the DFA must be anchored and it must not stop at the
+    /// first match. DFAs built with leftmost first semantics stop extending a
+    /// match once a preferred alternative has matched, so that `a|ab`
+    /// rejects `ab`. Building the DFA with
+    /// [`longest_match`](dense/struct.Builder.html#method.longest_match)
+    /// enabled avoids this.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{Incremental, Verdict, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .longest_match(true)
+    ///     .build("[a-z]+://[a-z]+|[a-z]+")?;
+    /// let mut input = Incremental::new(&dfa);
+    ///
+    /// input.feed(b"http");
+    /// assert_eq!(Verdict::Accept, input.verdict());
+    /// input.feed(b":/");
+    /// assert_eq!(Verdict::Undecided, input.verdict());
+    /// input.feed(b"/example");
+    /// assert_eq!(Verdict::Accept, input.verdict());
+    /// input.feed(b"/");
+    /// assert_eq!(Verdict::Reject, input.verdict());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn verdict(&self) -> Verdict {
+        if self.dfa.is_dead_state(self.state) {
+            Verdict::Reject
+        } else if self.dfa.is_match_state(self.state) {
+            Verdict::Accept
+        } else {
+            Verdict::Undecided
+        }
+    }
+
+    /// Return the total number of bytes fed to this search state since it
+    /// was created or last reset.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Return the absolute offset at which the most recent match ended, if
+    /// the DFA has matched any prefix of the input given so far.
+    ///
+    /// Once the DFA enters a dead state, this no longer changes. Thus, for a
+    /// DFA with leftmost first match semantics, this reports the end of the
+    /// leftmost first match once the search state is no longer viable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{Incremental, dense};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new().build("foo[0-9]+")?;
+    /// let mut input = Incremental::new(&dfa);
+    ///
+    /// input.feed(b"xxfoo1");
+    /// input.feed(b"23 and more");
+    /// assert_eq!(Some(8), input.last_match_end());
+    /// assert_eq!(17, input.offset());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn last_match_end(&self) -> Option<u64> {
+        self.last_match
+    }
+
+    /// Reset this search state to the beginning of the input.
+    pub fn reset(&mut self) {
+        self.state = self.dfa.start_state();
+        self.offset = 0;
+        self.last_match = if self.dfa.is_match_state(self.state) {
+            Some(0)
+        } else {
+            None
+        };
+    }
+
+    /// Return the underlying DFA.
+    pub fn dfa(&self) -> &D {
+        &self.dfa
+    }
+
+    /// Capture the position of this search state so that it can be resumed
+    /// later with [`resume`](struct.Incremental.html#method.resume).
+    ///
+    /// The snapshot can be serialized with
+    /// [`Suspended::to_bytes`](struct.Suspended.html#method.to_bytes), which
+    /// permits resuming a search in another process, possibly on another
+    /// machine, as long as the DFA used there was deserialized from the same
+    /// bytes as the DFA used here.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, DenseDFA, Incremental, Suspended};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let original = DenseDFA::new("foo[0-9]+")?;
+    /// let bytes = original.to_u16()?.to_bytes_native_endian()?;
+    ///
+    /// let mut input = Incremental::new(original.to_u16()?);
+    /// input.feed(b"xxfoo1");
+    /// let saved = input.suspend().to_bytes();
+    ///
+    /// // Later, possibly elsewhere.
+    /// let dfa: DenseDFA<&[u16], u16> = unsafe { DenseDFA::from_bytes(&bytes) };
+    /// let suspended = Suspended::from_bytes(&saved).unwrap();
+    /// let mut input = Incremental::resume(dfa, suspended);
+    /// input.feed(b"23 and more");
+    /// assert_eq!(Some(8), input.last_match_end());
+    /// assert_eq!(17, input.offset());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn suspend(&self) -> Suspended<D::ID> {
+        Suspended {
+            state: self.state,
+            offset: self.offset,
+            last_match: self.last_match,
+        }
+    }
+
+    /// Resume a search state captured by
+    /// [`suspend`](struct.Incremental.html#method.suspend) with the given
+    /// DFA.
+    ///
+    /// The DFA must be the same DFA that the search state was captured from,
+    /// or a DFA deserialized from the same bytes. Otherwise, the search
+    /// reports an unspecified result, or panics if the captured state does
+    /// not exist in the given DFA.
+    pub fn resume(dfa: D, suspended: Suspended<D::ID>) -> Incremental<D> {
+        Incremental {
+            dfa,
+            state: suspended.state,
+            offset: suspended.offset,
+            last_match: suspended.last_match,
+        }
+    }
+}
+
+/// The size, in bytes, of a serialized
+/// [`Suspended`](struct.Suspended.html) search state.
+const SUSPENDED_SIZE: usize = 24;
+
+/// The encoding of a missing match end in a serialized search state. No
+/// match can end at the largest `u64`, since that would require feeding more
+/// bytes than a `u64` can count.
+const SUSPENDED_NO_MATCH: u64 = !0;
+
+/// A snapshot of an [`Incremental`](struct.Incremental.html) search state,
+/// from which the search can be resumed.
+///
+/// The type parameter `S` is the representation of state identifiers in the
+/// DFA that the search state was captured from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Suspended<S> {
+    state: S,
+    offset: u64,
+    last_match: Option<u64>,
+}
+
+impl<S: StateID> Suspended<S> {
+    /// Return the identifier of the DFA state that the search was in.
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    /// Return the total number of bytes that had been fed to the search.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Return the absolute offset at which the most recent match ended, if
+    /// any.
+    pub fn last_match_end(&self) -> Option<u64> {
+        self.last_match
+    }
+
+    /// Serialize this snapshot to a fixed size sequence of bytes.
+    ///
+    /// The encoding is always little endian, so that a search can be resumed
+    /// on a machine with a different endianness, provided that the DFA
+    /// itself was serialized with a matching endianness.
+    pub fn to_bytes(&self) -> [u8; SUSPENDED_SIZE] {
+        let mut buf = [0; SUSPENDED_SIZE];
+        LittleEndian::write_u64(&mut buf[0..], self.state.to_usize() as u64);
+        LittleEndian::write_u64(&mut buf[8..], self.offset);
+        let last_match = self.last_match.unwrap_or(SUSPENDED_NO_MATCH);
+        LittleEndian::write_u64(&mut buf[16..], last_match);
+        buf
+    }
+
+    /// Deserialize a snapshot from bytes written by
+    /// [`to_bytes`](struct.Suspended.html#method.to_bytes).
+    ///
+    /// This returns `None` if the given bytes have the wrong length, or if
+    /// they describe a state identifier that does not fit in `S`.
+    pub fn from_bytes(buf: &[u8]) -> Option<Suspended<S>> {
+        if buf.len() != SUSPENDED_SIZE {
+            return None;
+        }
+        let state = LittleEndian::read_u64(&buf[0..]);
+        if state > S::max_id() as u64 {
+            return None;
+        }
+        let last_match = match LittleEndian::read_u64(&buf[16..]) {
+            SUSPENDED_NO_MATCH => None,
+            end => Some(end),
+        };
+        Some(Suspended {
+            state: S::from_usize(state as usize),
+            offset: LittleEndian::read_u64(&buf[8..]),
+            last_match,
+        })
+    }
 }
 
 impl<'a, T: DFA> DFA for &'a T {
@@ -364,3 +1167,85 @@ impl<'a, T: DFA> DFA for &'a T {
         (**self).next_state_unchecked(current, input)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dense::DenseDFA;
+    use regex::Regex;
+
+    /// Split the given bytes into chunks at the given offsets.
+    fn split<'a>(bytes: &'a [u8], at: &[usize]) -> Vec<&'a [u8]> {
+        let mut chunks = vec![];
+        let mut last = 0;
+        for &i in at {
+            chunks.push(&bytes[last..i]);
+            last = i;
+        }
+        chunks.push(&bytes[last..]);
+        chunks
+    }
+
+    #[test]
+    fn chunks_empty() {
+        let none: &[&[u8]] = &[];
+        let empty: &[&[u8]] = &[b"", b""];
+        let dfa = DenseDFA::new("a*").unwrap();
+        assert_eq!(Some(0), dfa.find_chunks(none.iter()));
+        assert_eq!(Some(0), dfa.shortest_match_chunks(empty.iter()));
+
+        let dfa = DenseDFA::new("ab").unwrap();
+        let chunks: &[&[u8]] = &[b"", b"a", b"", b"", b"b", b""];
+        assert!(dfa.is_match_chunks(chunks.iter()));
+        assert_eq!(Some(2), dfa.find_chunks(chunks.iter()));
+        assert_eq!(Some(2), dfa.shortest_match_chunks(chunks.iter()));
+        assert!(!dfa.is_match_chunks(none.iter()));
+        assert_eq!(None, dfa.find_chunks(empty.iter()));
+    }
+
+    #[test]
+    fn chunks_spanning_match() {
+        let re = Regex::new("foo[0-9]+bar").unwrap();
+        let chunks: &[&[u8]] =
+            &[b"zzf", b"o", b"o1", b"2", b"3b", b"a", b"rz"];
+        assert!(re.is_match_chunks(chunks.iter()));
+        assert_eq!(Some(11), re.forward().find_chunks(chunks.iter()));
+        assert_eq!(Some(11), re.shortest_match_chunks(chunks.iter()));
+    }
+
+    #[test]
+    fn chunks_match_at_end() {
+        let dfa = DenseDFA::new("foo[0-9]+").unwrap();
+        let chunks: &[&[u8]] = &[b"xxfo", b"o1", b"23"];
+        assert_eq!(Some(8), dfa.find_chunks(chunks.iter()));
+        assert_eq!(Some(6), dfa.shortest_match_chunks(chunks.iter()));
+
+        let dfa = DenseDFA::new("z+").unwrap();
+        let chunks: &[&[u8]] = &[b"xz", b"z", b"", b"z"];
+        assert_eq!(Some(4), dfa.find_chunks(chunks.iter()));
+        assert_eq!(Some(2), dfa.shortest_match_chunks(chunks.iter()));
+    }
+
+    #[test]
+    fn chunks_same_as_contiguous() {
+        let text = b"foo1 foofoo12 abcz ab foo";
+        for pattern in &["foo[0-9]*", "[a-z]*", "ab|abcz", "z*", "x"] {
+            let re = Regex::new(pattern).unwrap();
+            for i in 0..text.len() {
+                for j in i..text.len() {
+                    let chunks = split(text, &[i, j]);
+                    assert_eq!(
+                        re.forward().find(text).map(|e| e as u64),
+                        re.forward().find_chunks(chunks.iter()),
+                        "{} {:?}", pattern, chunks,
+                    );
+                    assert_eq!(
+                        re.shortest_match(text).map(|e| e as u64),
+                        re.shortest_match_chunks(chunks.iter()),
+                        "{} {:?}", pattern, chunks,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+/*!
+Write a DFA in the [DOT](https://graphviz.org/doc/info/lang.html) language
+used by Graphviz.
+
+This is intended for debugging DFAs, for example to see which part of a
+pattern caused its DFA to have many more states than expected. The graph
+written contains every state reachable from the start state. Each edge is
+labeled with the byte ranges that lead from one state to another, so that
+there is at most one edge between any two states. Transitions to the dead
+state are omitted, unless the start state is itself dead.
+
+Match states are drawn with a double circle, and the start state is pointed
+to by an edge without a source. The identifier of each state is its
+identifier in the DFA.
+
+Dense and sparse DFAs can also label each edge with the byte classes, rather
+than the bytes, that lead to its target, along with a legend listing the
+bytes in each class. This makes DFAs whose transitions are split into many
+small byte ranges easier to read. See
+[`DenseDFA::to_dot_with_classes`](../enum.DenseDFA.html#method.to_dot_with_classes).
+
+This module is only available when the `std` feature is enabled.
+
+# Example
+
+```
+use regex_automata::{dot, DenseDFA};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let dfa = DenseDFA::new("foo[0-9]+")?;
+let mut buf = vec![];
+dot::write(&dfa, &mut buf).unwrap();
+assert!(String::from_utf8(buf).unwrap().starts_with("digraph dfa {"));
+// Render with, e.g., `dot -Tsvg dfa.dot > dfa.svg`.
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::io;
+
+use classes::ByteClasses;
+use dfa::DFA;
+use html::{reachable, transitions};
+use state_id::StateID;
+
+/// Write the given DFA to `wtr` as a DOT graph, labeling each edge with the
+/// byte ranges that lead to its target.
+///
+/// Since the DFA is explored from its start state using only its transition
+/// function, this works with any implementation of the
+/// [`DFA`](../trait.DFA.html) trait.
+pub fn write<D: DFA, W: io::Write>(dfa: &D, wtr: W) -> io::Result<()> {
+    write_graph(dfa, None, wtr)
+}
+
+/// Like `write`, but label each edge with the given byte classes instead of
+/// the bytes in them, and add a legend listing the bytes in each class.
+///
+/// Every byte in the same class must lead to the same state from every
+/// state of the DFA.
+pub(crate) fn write_with_classes<D: DFA, W: io::Write>(
+    dfa: &D,
+    classes: &ByteClasses,
+    wtr: W,
+) -> io::Result<()> {
+    write_graph(dfa, Some(classes), wtr)
+}
+
+fn write_graph<D: DFA, W: io::Write>(
+    dfa: &D,
+    classes: Option<&ByteClasses>,
+    mut wtr: W,
+) -> io::Result<()> {
+    writeln!(wtr, "digraph dfa {{")?;
+    writeln!(wtr, "    rankdir=LR;")?;
+    writeln!(wtr, "    node [shape=circle];")?;
+    writeln!(wtr, "    start [shape=point];")?;
+    writeln!(wtr, "    start -> {};", dfa.start_state().to_usize())?;
+    if dfa.is_dead_state(dfa.start_state()) {
+        writeln!(
+            wtr,
+            "    {} [style=dashed, color=gray, fontcolor=gray];",
+            dfa.start_state().to_usize(),
+        )?;
+    }
+    for id in reachable(dfa) {
+        if dfa.is_match_state(id) {
+            writeln!(wtr, "    {} [shape=doublecircle];", id.to_usize())?;
+        }
+        for (next, ranges) in transitions(dfa, id) {
+            let label: Vec<String> = ranges
+                .into_iter()
+                .map(|(start, end)| match classes {
+                    None => escape_range(start, end),
+                    // Since every class is a contiguous range of bytes, each
+                    // range of bytes is also a range of classes.
+                    Some(classes) => {
+                        let (start, end) =
+                            (classes.get(start), classes.get(end));
+                        if start == end {
+                            format!("#{}", start)
+                        } else {
+                            format!("#{}-#{}", start, end)
+                        }
+                    }
+                })
+                .collect();
+            writeln!(
+                wtr,
+                "    {} -> {} [label=\"{}\"];",
+                id.to_usize(),
+                next.to_usize(),
+                label.join(", "),
+            )?;
+        }
+    }
+    if let Some(classes) = classes {
+        let mut legend = String::new();
+        let mut start = 0;
+        for b in 0..256 {
+            let b = b as u8;
+            if b == 255 || classes.get(b) != classes.get(b + 1) {
+                legend.push_str(&format!(
+                    "#{}: {}\\l",
+                    classes.get(b),
+                    escape_range(start, b),
+                ));
+                start = b.wrapping_add(1);
+            }
+        }
+        writeln!(wtr, "    classes [shape=note, label=\"{}\"];", legend)?;
+    }
+    writeln!(wtr, "}}")?;
+    Ok(())
+}
+
+/// Format an inclusive range of bytes for use in a quoted DOT string.
+fn escape_range(start: u8, end: u8) -> String {
+    if start == end {
+        escape_byte(start)
+    } else {
+        format!("{}-{}", escape_byte(start), escape_byte(end))
+    }
+}
+
+/// Format a byte for use in a quoted DOT string, using its ASCII
+/// representation when it is printable and a hex escape otherwise.
+///
+/// Since Graphviz interprets some escape sequences in labels, backslashes
+/// are doubled so that they are shown as written.
+fn escape_byte(b: u8) -> String {
+    match b {
+        b'"' => "\\\"".to_string(),
+        b'\\' => "\\\\\\\\".to_string(),
+        b'-' | b',' => format!("\\\\{}", b as char),
+        0x21..=0x7E => (b as char).to_string(),
+        _ => format!("\\\\x{:02X}", b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dense::{self, DenseDFA};
+
+    fn dot(dfa: &DenseDFA<Vec<usize>, usize>, classes: bool) -> String {
+        let mut buf = vec![];
+        if classes {
+            dfa.to_dot_with_classes(&mut buf).unwrap();
+        } else {
+            dfa.to_dot(&mut buf).unwrap();
+        }
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn labels_edges() {
+        let dfa = dense::Builder::new()
+            .anchored(true)
+            .build("(?-u)[a-c\\x00\"]y")
+            .unwrap();
+        let start = dfa.start_state();
+        let next = dfa.next_state(start, b'a');
+        let end = dfa.next_state(next, b'y');
+
+        let got = dot(&dfa, false);
+        assert!(got.contains(&format!(
+            "{} -> {} [label=\"\\\\x00, \\\", a-c\"];",
+            start, next,
+        )), "{}", got);
+        assert!(got.contains(&format!("{} [shape=doublecircle];", end)));
+        assert!(!got.contains("classes"));
+        assert!(!got.contains("gray"));
+
+        let got = dot(&dfa, true);
+        assert!(got.contains(&format!(
+            "{} -> {} [label=\"#0, #2, #4\"];",
+            start, next,
+        )), "{}", got);
+        assert!(got.contains("#0: \\\\x00\\l#1: \\\\x01-!\\l#2: \\\"\\l"));
+        assert!(got.contains("#7: z-\\\\xFF\\l\"];"));
+
+        let none: &[&str] = &[];
+        let dead = dense::Builder::new()
+            .anchored(true)
+            .build_literals(none)
+            .unwrap();
+        assert!(dot(&dead, false).contains("gray"));
+    }
+}
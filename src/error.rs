@@ -1,6 +1,7 @@
 use std::error;
 use std::fmt;
 use std::result;
+use std::time::Duration;
 
 use regex_syntax;
 
@@ -13,7 +14,11 @@ pub struct Error {
 }
 
 /// The kind of error that occurred.
+///
+/// New kinds of errors may be added in the future without a breaking change,
+/// so matching on this type requires a wildcard arm.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// An error that occurred while parsing a regular expression. Note that
     /// this error may be printed over multiple lines, and is generally
@@ -28,6 +33,44 @@ pub enum ErrorKind {
     Unsupported(String),
     /// An error that occurred when attempting to serialize a DFA to bytes.
     Serialize(String),
+    /// An error that occurred when attempting to deserialize a DFA from
+    /// bytes that were validated and found not to correspond to a valid
+    /// serialized DFA. The message string describes the problem found.
+    Deserialize(String),
+    /// An error that occurs when deserializing a DFA that was serialized
+    /// with a version of the serialization format that is not supported.
+    ///
+    /// This typically means that the DFA was serialized by a different
+    /// version of this crate, and should be serialized again.
+    VersionMismatch {
+        /// The version supported by this crate.
+        expected: u16,
+        /// The version found in the serialized DFA.
+        found: u16,
+    },
+    /// An error that occurs when deserializing a DFA that was serialized
+    /// with an endianness different from that of the current target.
+    EndiannessMismatch,
+    /// An error that occurs when deserializing a DFA with a state identifier
+    /// representation whose size differs from the size of the state
+    /// identifiers in the serialized DFA.
+    StateIDSizeMismatch {
+        /// The size, in bytes, of the requested representation.
+        expected: usize,
+        /// The size, in bytes, of the serialized state identifiers.
+        found: usize,
+    },
+    /// An error that occurs when the checksum of a serialized DFA's
+    /// transition table does not match the checksum stored in its header.
+    ///
+    /// This typically means that the serialized DFA was corrupted after it
+    /// was written.
+    ChecksumMismatch {
+        /// The checksum stored in the header.
+        expected: u32,
+        /// The checksum of the transition table.
+        found: u32,
+    },
     /// An error that occurs when constructing a DFA would require the use of
     /// a state ID that overflows the chosen state ID representation. For
     /// example, if one is using `u8` for state IDs and builds a DFA with
@@ -50,7 +93,49 @@ pub enum ErrorKind {
         max: usize,
         /// The maximum ID required by premultiplication.
         requested_max: usize,
-    }
+    },
+    /// An error that occurs when the transition table of a DFA being built
+    /// would exceed the configured size limit.
+    ///
+    /// See
+    /// [`dense::Builder::dfa_size_limit`](dense/struct.Builder.html#method.dfa_size_limit).
+    DFAExceededSizeLimit {
+        /// The size limit, in bytes, that was exceeded.
+        limit: usize,
+    },
+    /// An error that occurs when the NFA compiled from a pattern would exceed
+    /// the configured size limit.
+    ///
+    /// See
+    /// [`dense::Builder::nfa_size_limit`](dense/struct.Builder.html#method.nfa_size_limit).
+    NFAExceededSizeLimit {
+        /// The size limit, in bytes, that was exceeded.
+        limit: usize,
+    },
+    /// An error that occurs when determinizing or minimizing a DFA takes
+    /// longer than the configured time limit.
+    ///
+    /// See
+    /// [`dense::Builder::time_limit`](dense/struct.Builder.html#method.time_limit).
+    TimeLimitExceeded {
+        /// The time limit that was exceeded.
+        limit: Duration,
+    },
+    /// An error that occurs when a progress callback asks for building a DFA
+    /// to stop.
+    ///
+    /// See
+    /// [`dense::Builder::progress`](dense/struct.Builder.html#method.progress).
+    Cancelled,
+    /// An error that occurs when a word given to a DAWG builder is not
+    /// greater than or equal to the word before it.
+    ///
+    /// See [`dawg::Builder::insert`](dawg/struct.Builder.html#method.insert).
+    UnsortedWord {
+        /// The number of words inserted before the word that is out of
+        /// order, which is its index in the list of words.
+        index: usize,
+    },
 }
 
 impl Error {
@@ -83,6 +168,29 @@ impl Error {
         Error { kind: ErrorKind::Serialize(message.to_string()) }
     }
 
+    pub(crate) fn deserialize(message: &str) -> Error {
+        Error { kind: ErrorKind::Deserialize(message.to_string()) }
+    }
+
+    pub(crate) fn version_mismatch(expected: u16, found: u16) -> Error {
+        Error { kind: ErrorKind::VersionMismatch { expected, found } }
+    }
+
+    pub(crate) fn endianness_mismatch() -> Error {
+        Error { kind: ErrorKind::EndiannessMismatch }
+    }
+
+    pub(crate) fn state_id_size_mismatch(
+        expected: usize,
+        found: usize,
+    ) -> Error {
+        Error { kind: ErrorKind::StateIDSizeMismatch { expected, found } }
+    }
+
+    pub(crate) fn checksum_mismatch(expected: u32, found: u32) -> Error {
+        Error { kind: ErrorKind::ChecksumMismatch { expected, found } }
+    }
+
     pub(crate) fn state_id_overflow(max: usize) -> Error {
         Error { kind: ErrorKind::StateIDOverflow { max } }
     }
@@ -93,6 +201,26 @@ impl Error {
     ) -> Error {
         Error { kind: ErrorKind::PremultiplyOverflow { max, requested_max } }
     }
+
+    pub(crate) fn dfa_exceeded_size_limit(limit: usize) -> Error {
+        Error { kind: ErrorKind::DFAExceededSizeLimit { limit } }
+    }
+
+    pub(crate) fn nfa_exceeded_size_limit(limit: usize) -> Error {
+        Error { kind: ErrorKind::NFAExceededSizeLimit { limit } }
+    }
+
+    pub(crate) fn time_limit_exceeded(limit: Duration) -> Error {
+        Error { kind: ErrorKind::TimeLimitExceeded { limit } }
+    }
+
+    pub(crate) fn cancelled() -> Error {
+        Error { kind: ErrorKind::Cancelled }
+    }
+
+    pub(crate) fn unsorted_word(index: usize) -> Error {
+        Error { kind: ErrorKind::UnsortedWord { index } }
+    }
 }
 
 impl error::Error for Error {
@@ -101,12 +229,32 @@ impl error::Error for Error {
             ErrorKind::Syntax(_) => "syntax error",
             ErrorKind::Unsupported(_) => "unsupported syntax",
             ErrorKind::Serialize(_) => "serialization error",
+            ErrorKind::Deserialize(_) => "deserialization error",
+            ErrorKind::VersionMismatch { .. } => {
+                "unsupported serialization format version"
+            }
+            ErrorKind::EndiannessMismatch => "endianness mismatch",
+            ErrorKind::StateIDSizeMismatch { .. } => {
+                "state id representation size mismatch"
+            }
+            ErrorKind::ChecksumMismatch { .. } => "checksum mismatch",
             ErrorKind::StateIDOverflow { .. } => {
                 "state id representation too small"
             }
             ErrorKind::PremultiplyOverflow { .. } => {
                 "state id representation too small for premultiplication"
             }
+            ErrorKind::DFAExceededSizeLimit { .. } => {
+                "DFA exceeded size limit"
+            }
+            ErrorKind::NFAExceededSizeLimit { .. } => {
+                "NFA exceeded size limit"
+            }
+            ErrorKind::TimeLimitExceeded { .. } => {
+                "DFA construction exceeded time limit"
+            }
+            ErrorKind::Cancelled => "DFA construction cancelled",
+            ErrorKind::UnsortedWord { .. } => "unsorted word",
         }
     }
 }
@@ -119,6 +267,41 @@ impl fmt::Display for Error {
             ErrorKind::Serialize(ref msg) => {
                 write!(f, "DFA serialization error: {}", msg)
             }
+            ErrorKind::Deserialize(ref msg) => {
+                write!(f, "DFA deserialization error: {}", msg)
+            }
+            ErrorKind::VersionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "DFA deserialization error: expected version {}, but \
+                     found unsupported version {} (the DFA must be \
+                     serialized again with this version of regex-automata)",
+                    expected, found,
+                )
+            }
+            ErrorKind::EndiannessMismatch => {
+                write!(
+                    f,
+                    "DFA deserialization error: the DFA was serialized with \
+                     a different endianness than the current target",
+                )
+            }
+            ErrorKind::StateIDSizeMismatch { expected, found } => {
+                write!(
+                    f,
+                    "DFA deserialization error: expected state identifiers \
+                     of {} bytes, but found state identifiers of {} bytes",
+                    expected, found,
+                )
+            }
+            ErrorKind::ChecksumMismatch { expected, found } => {
+                write!(
+                    f,
+                    "DFA deserialization error: expected a transition table \
+                     with checksum 0x{:08X}, but found checksum 0x{:08X}",
+                    expected, found,
+                )
+            }
             ErrorKind::StateIDOverflow { max } => {
                 write!(
                     f,
@@ -147,6 +330,41 @@ impl fmt::Display for Error {
                     )
                 }
             }
+            ErrorKind::DFAExceededSizeLimit { limit } => {
+                write!(
+                    f,
+                    "building the DFA failed because its transition table \
+                     exceeded the size limit of {} bytes",
+                    limit,
+                )
+            }
+            ErrorKind::NFAExceededSizeLimit { limit } => {
+                write!(
+                    f,
+                    "compiling the NFA failed because it exceeded the size \
+                     limit of {} bytes",
+                    limit,
+                )
+            }
+            ErrorKind::TimeLimitExceeded { limit } => {
+                write!(
+                    f,
+                    "building the DFA was stopped because it exceeded the \
+                     time limit of {:?}",
+                    limit,
+                )
+            }
+            ErrorKind::Cancelled => {
+                write!(f, "building the DFA was cancelled")
+            }
+            ErrorKind::UnsortedWord { index } => {
+                write!(
+                    f,
+                    "word {} is less than the word before it, but words \
+                     must be given in lexicographic order",
+                    index,
+                )
+            }
         }
     }
 }
@@ -0,0 +1,274 @@
+/*!
+Render a DFA as a self-contained, interactive HTML page.
+
+This is intended for debugging DFAs that are too large to comfortably
+inspect via their `Debug` output. The page lists every state reachable from
+the start state, with the transitions of each state grouped by their target
+state and written as byte ranges. Transitions to the dead state are omitted.
+Every target is a link to the corresponding state.
+
+If a sample input is given, then the page also traces the states visited by
+the DFA while consuming it. The trace can be stepped through one byte at a
+time, which highlights the current state and the byte that led to it.
+
+The page has no external dependencies, so it can be written to a file and
+opened directly in a browser.
+
+This module is only available when the `std` feature is enabled.
+
+# Example
+
+```
+use regex_automata::{html, DenseDFA};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let dfa = DenseDFA::new("foo[0-9]+")?;
+let page = html::render(&dfa, b"xfoo12");
+assert!(page.starts_with("<!DOCTYPE html>"));
+// std::fs::write("dfa.html", page)?;
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use dfa::DFA;
+use state_id::StateID;
+
+/// Render the given DFA as an HTML page, tracing the states visited while
+/// consuming the given sample input.
+///
+/// If `sample` is empty, then the trace consists of only the start state.
+/// Since the DFA is explored from its start state using only its transition
+/// function, this works with any implementation of the
+/// [`DFA`](../trait.DFA.html) trait.
+pub fn render<D: DFA>(dfa: &D, sample: &[u8]) -> String {
+    let states = reachable(dfa);
+    let mut trace = vec![dfa.start_state()];
+    for &b in sample {
+        let current = trace[trace.len() - 1];
+        if dfa.is_dead_state(current) {
+            break;
+        }
+        trace.push(dfa.next_state(current, b));
+    }
+
+    let mut out = String::new();
+    out.push_str(HEADER);
+    writeln!(out, "<p>{} states.</p>", states.len()).unwrap();
+    out.push_str("<div id=\"trace\"></div>\n<div id=\"states\">\n");
+    for &id in &states {
+        let mut classes = vec!["state"];
+        if id == dfa.start_state() {
+            classes.push("start");
+        }
+        if dfa.is_match_state(id) {
+            classes.push("match");
+        }
+        writeln!(
+            out,
+            "<div class=\"{}\" id=\"s{}\"><h2>{}</h2><ul>",
+            classes.join(" "),
+            id.to_usize(),
+            id.to_usize(),
+        ).unwrap();
+        for (next, ranges) in transitions(dfa, id) {
+            let ranges: Vec<String> = ranges
+                .into_iter()
+                .map(|(start, end)| {
+                    if start == end {
+                        escape_byte(start)
+                    } else {
+                        format!("{}-{}", escape_byte(start), escape_byte(end))
+                    }
+                })
+                .collect();
+            writeln!(
+                out,
+                "<li>{} &rarr; <a href=\"#s{}\">{}</a></li>",
+                ranges.join(", "),
+                next.to_usize(),
+                next.to_usize(),
+            ).unwrap();
+        }
+        out.push_str("</ul></div>\n");
+    }
+    out.push_str("</div>\n<script>\n");
+
+    let trace: Vec<String> =
+        trace.iter().map(|id| id.to_usize().to_string()).collect();
+    let sample: Vec<String> =
+        sample.iter().map(|&b| format!("\"{}\"", escape_js(b))).collect();
+    writeln!(out, "var trace = [{}];", trace.join(",")).unwrap();
+    writeln!(out, "var sample = [{}];", sample.join(",")).unwrap();
+    out.push_str(SCRIPT);
+    out.push_str("</script>\n</body>\n</html>\n");
+    out
+}
+
+/// Return every state reachable from the start state of the given DFA,
+/// excluding the dead state, in breadth first order.
+pub(crate) fn reachable<D: DFA>(dfa: &D) -> Vec<D::ID> {
+    let mut seen = HashMap::new();
+    let mut order = vec![];
+    let mut queue = VecDeque::new();
+    queue.push_back(dfa.start_state());
+    while let Some(id) = queue.pop_front() {
+        if dfa.is_dead_state(id) || seen.insert(id, ()).is_some() {
+            continue;
+        }
+        order.push(id);
+        for b in 0..256 {
+            queue.push_back(dfa.next_state(id, b as u8));
+        }
+    }
+    order
+}
+
+/// A sequence of inclusive byte ranges.
+pub(crate) type Ranges = Vec<(u8, u8)>;
+
+/// Return the transitions out of the given state, excluding those to the
+/// dead state, grouped by their target. Each target is paired with the
+/// inclusive byte ranges that lead to it, and targets are ordered by the
+/// first byte that leads to them.
+pub(crate) fn transitions<D: DFA>(
+    dfa: &D,
+    id: D::ID,
+) -> Vec<(D::ID, Ranges)> {
+    let mut groups: Vec<(D::ID, Ranges)> = vec![];
+    let mut index = HashMap::new();
+    for b in 0..256 {
+        let b = b as u8;
+        let next = dfa.next_state(id, b);
+        if dfa.is_dead_state(next) {
+            continue;
+        }
+        let i = *index.entry(next).or_insert_with(|| {
+            groups.push((next, vec![]));
+            groups.len() - 1
+        });
+        let ranges = &mut groups[i].1;
+        match ranges.last_mut() {
+            Some(&mut (_, ref mut end)) if *end as usize + 1 == b as usize => {
+                *end = b;
+            }
+            _ => ranges.push((b, b)),
+        }
+    }
+    groups
+}
+
+/// Format a byte for use in HTML, using its ASCII representation when it is
+/// printable and a hex escape otherwise.
+fn escape_byte(b: u8) -> String {
+    match b {
+        b'&' => "&amp;".to_string(),
+        b'<' => "&lt;".to_string(),
+        b'>' => "&gt;".to_string(),
+        b'"' => "&quot;".to_string(),
+        b'-' | b',' | b'\\' => format!("\\{}", b as char),
+        0x21..=0x7E => (b as char).to_string(),
+        _ => format!("\\x{:02X}", b),
+    }
+}
+
+/// Format a byte for use in a JavaScript string literal that is shown as
+/// text in the page.
+fn escape_js(b: u8) -> String {
+    match b {
+        b'"' | b'\\' => format!("\\{}", b as char),
+        b'<' => "\\u003C".to_string(),
+        0x21..=0x7E => (b as char).to_string(),
+        _ => format!("\\\\x{:02X}", b),
+    }
+}
+
+const HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>DFA</title>
+<style>
+body { font-family: monospace; margin: 1em; }
+.state { display: inline-block; vertical-align: top; margin: 0.25em;
+         padding: 0.25em 0.5em; border: 1px solid #999; }
+.state h2 { font-size: 1em; margin: 0; }
+.state ul { margin: 0; padding-left: 1em; }
+.start h2::before { content: "> "; }
+.match { border-width: 3px; }
+.match h2::after { content: " (match)"; }
+.visited { background: #eef; }
+.current { background: #fd8; }
+#trace { position: sticky; top: 0; background: #fff; padding: 0.5em 0; }
+#trace span { padding: 0 1px; }
+#trace .consumed { color: #999; }
+#trace .last { background: #fd8; }
+</style>
+</head>
+<body>
+"#;
+
+const SCRIPT: &str = r#"var step = 0;
+function show() {
+  var nodes = document.querySelectorAll(".state");
+  for (var i = 0; i < nodes.length; i++) {
+    nodes[i].classList.remove("visited", "current");
+  }
+  for (var i = 0; i <= step; i++) {
+    var visited = document.getElementById("s" + trace[i]);
+    if (visited) { visited.classList.add("visited"); }
+  }
+  var current = document.getElementById("s" + trace[step]);
+  if (current) {
+    current.classList.add("current");
+    current.scrollIntoView({block: "nearest"});
+  }
+  var div = document.getElementById("trace");
+  div.innerHTML = "";
+  var prev = document.createElement("button");
+  prev.textContent = "<";
+  prev.onclick = function() { if (step > 0) { step--; show(); } };
+  var next = document.createElement("button");
+  next.textContent = ">";
+  next.onclick = function() {
+    if (step < trace.length - 1) { step++; show(); }
+  };
+  div.appendChild(prev);
+  div.appendChild(next);
+  div.appendChild(document.createTextNode(
+    " step " + step + " of " + (trace.length - 1) +
+    ", state " + (current ? trace[step] : "dead") + ": "));
+  for (var i = 0; i < sample.length; i++) {
+    var span = document.createElement("span");
+    span.textContent = sample[i];
+    if (i < step - 1) { span.className = "consumed"; }
+    if (i == step - 1) { span.className = "last"; }
+    div.appendChild(span);
+  }
+}
+show();
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dense::DenseDFA;
+
+    #[test]
+    fn groups_transitions_by_target() {
+        let dfa = DenseDFA::new("[a-cx]y").unwrap();
+        let start = dfa.start_state();
+        let got = transitions(&dfa, start);
+        // Every byte is a transition out of an unanchored start state, so
+        // all we check is that `a-c` and `x` lead to the same state.
+        let group =
+            got.iter().find(|g| g.1.contains(&(b'a', b'c'))).unwrap();
+        assert!(group.1.contains(&(b'x', b'x')));
+
+        let page = render(&dfa, b"zay");
+        assert!(page.contains("var trace = ["));
+        assert!(page.contains("var sample = [\"z\",\"a\",\"y\"];"));
+    }
+}
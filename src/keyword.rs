@@ -0,0 +1,341 @@
+/*!
+A compact DFA for matching a set of exact keywords.
+
+A [`KeywordDFA`](struct.KeywordDFA.html) is a trie of keywords stored in a
+double-array representation. Each state is described by a single `base`
+offset and the transition on a byte `b` from a state `s` leads to the state
+`base[s] + b`, but only if that state records `s` as its parent. This packs
+the sparse transitions of a trie into two flat arrays, which typically uses
+far less memory than a dense DFA for the same keywords. Building a keyword
+DFA also avoids determinization entirely, and is therefore much faster for
+large keyword sets.
+
+Since a keyword DFA is a trie, it is always anchored and it always uses
+leftmost-longest match semantics. That is, the keyword reported by a search
+is the longest keyword that begins at the start of the search.
+
+# Example
+
+This shows how to build a regex from a forward and reverse keyword DFA.
+
+```
+use regex_automata::{keyword, KeywordDFA, Regex};
+
+let keywords = &["sam", "samwise", "frodo"];
+let fwd = KeywordDFA::new(keywords);
+let rev = keyword::Builder::new().reverse(true).build(keywords);
+let re = Regex::from_dfas(fwd, rev);
+
+assert_eq!(Some((0, 7)), re.find(b"samwise gamgee"));
+assert_eq!(Some((0, 3)), re.find(b"samuel"));
+assert_eq!(None, re.find(b"the frodo"));
+```
+*/
+
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+use dfa::DFA;
+use error::Result;
+use state_id::{StateID, dead_id, usize_to_state_id};
+
+/// The state identifier of the root of every keyword trie.
+const ROOT: usize = 1;
+
+/// An anchored DFA for a set of exact keywords, represented as a
+/// double-array trie.
+///
+/// The type parameter `S` is the representation used for this DFA's state
+/// identifiers, in the same way as for a
+/// [`DenseDFA`](enum.DenseDFA.html#state-size).
+///
+/// Keyword DFAs are built with a
+/// [`keyword::Builder`](keyword/struct.Builder.html) or, using the default
+/// configuration, with
+/// [`KeywordDFA::new`](struct.KeywordDFA.html#method.new).
+#[derive(Clone, Debug)]
+pub struct KeywordDFA<S: StateID = usize> {
+    /// The offset of the children of each state. The child of state `s` on
+    /// the byte `b`, if it exists, is `base[s] + b`.
+    base: Vec<S>,
+    /// The parent of each state. For unused slots, the dead state and the
+    /// root, this is the dead state.
+    check: Vec<S>,
+    /// Whether each state corresponds to the end of a keyword.
+    matches: Vec<bool>,
+}
+
+impl KeywordDFA {
+    /// Build a forward keyword DFA for the given keywords, using `usize` for
+    /// its state identifiers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, KeywordDFA};
+    ///
+    /// let dfa = KeywordDFA::new(&["foo", "foobar", "quux"]);
+    /// assert_eq!(Some(6), dfa.find(b"foobarbaz"));
+    /// assert_eq!(Some(3), dfa.find(b"foobaz"));
+    /// assert_eq!(None, dfa.find(b"bar"));
+    /// ```
+    pub fn new<I, P>(keywords: I) -> KeywordDFA
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        Builder::new().build(keywords)
+    }
+}
+
+impl<S: StateID> KeywordDFA<S> {
+    /// Returns the memory usage, in bytes, of this DFA.
+    ///
+    /// The memory usage is computed based on the number of bytes used to
+    /// represent this DFA's arrays. It does not include the size of the
+    /// `KeywordDFA` structure.
+    pub fn memory_usage(&self) -> usize {
+        self.base.len() * (2 * size_of::<S>() + size_of::<bool>())
+    }
+
+    /// Returns the number of states in this DFA, including unused slots in
+    /// its double array.
+    pub fn state_count(&self) -> usize {
+        self.base.len()
+    }
+}
+
+impl<S: StateID> DFA for KeywordDFA<S> {
+    type ID = S;
+
+    #[inline]
+    fn start_state(&self) -> S {
+        S::from_usize(ROOT)
+    }
+
+    #[inline]
+    fn is_match_state(&self, id: S) -> bool {
+        self.matches[id.to_usize()]
+    }
+
+    #[inline]
+    fn is_dead_state(&self, id: S) -> bool {
+        id == dead_id()
+    }
+
+    #[inline]
+    fn is_match_or_dead_state(&self, id: S) -> bool {
+        self.is_dead_state(id) || self.is_match_state(id)
+    }
+
+    #[inline]
+    fn is_anchored(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn next_state(&self, current: S, input: u8) -> S {
+        if current == dead_id() {
+            return current;
+        }
+        let next = self.base[current.to_usize()].to_usize() + input as usize;
+        match self.check.get(next) {
+            Some(&parent) if parent == current => S::from_usize(next),
+            _ => dead_id(),
+        }
+    }
+
+    #[inline]
+    unsafe fn next_state_unchecked(&self, current: S, input: u8) -> S {
+        self.next_state(current, input)
+    }
+}
+
+/// A builder for constructing a keyword DFA.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    reverse: bool,
+}
+
+impl Builder {
+    /// Create a new keyword DFA builder with the default configuration.
+    pub fn new() -> Builder {
+        Builder { reverse: false }
+    }
+
+    /// Build a keyword DFA for the given keywords, using `usize` for its
+    /// state identifiers.
+    pub fn build<I, P>(&self, keywords: I) -> KeywordDFA
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        // Every state identifier in a double array trie is bounded by the
+        // length of its arrays, which always fits in a usize.
+        self.build_with_size(keywords).unwrap()
+    }
+
+    /// Build a keyword DFA for the given keywords, using `S` for its state
+    /// identifiers.
+    ///
+    /// If the chosen state identifier representation is too small to
+    /// represent every slot in the DFA's double array, then this returns an
+    /// error.
+    pub fn build_with_size<S, I, P>(
+        &self,
+        keywords: I,
+    ) -> Result<KeywordDFA<S>>
+    where
+        S: StateID,
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut trie = Trie { nodes: vec![TrieNode::default()] };
+        for keyword in keywords {
+            let keyword = keyword.as_ref();
+            if self.reverse {
+                trie.insert(keyword.iter().rev().cloned());
+            } else {
+                trie.insert(keyword.iter().cloned());
+            }
+        }
+
+        let mut base = vec![0, 0];
+        let mut check = vec![0, 0];
+        let mut matches = vec![false, trie.nodes[0].is_match];
+        let mut used = vec![true, true];
+        // The first slot that might be unused, which lets us skip over the
+        // densely packed prefix of the double array when placing children.
+        let mut first_free = 2;
+        let mut queue = VecDeque::new();
+        queue.push_back((0, ROOT));
+        while let Some((node, id)) = queue.pop_front() {
+            let children = &trie.nodes[node].children;
+            if children.is_empty() {
+                continue;
+            }
+            while first_free < used.len() && used[first_free] {
+                first_free += 1;
+            }
+            let min = children[0].0 as usize;
+            let mut b = first_free.saturating_sub(min).max(2);
+            while children.iter().any(|&(byte, _)| {
+                used.get(b + byte as usize).cloned().unwrap_or(false)
+            }) {
+                b += 1;
+            }
+
+            let max = b + children[children.len() - 1].0 as usize;
+            if max >= used.len() {
+                base.resize(max + 1, 0);
+                check.resize(max + 1, 0);
+                matches.resize(max + 1, false);
+                used.resize(max + 1, false);
+            }
+            base[id] = b;
+            for &(byte, child) in children {
+                let slot = b + byte as usize;
+                used[slot] = true;
+                check[slot] = id;
+                matches[slot] = trie.nodes[child].is_match;
+                queue.push_back((child, slot));
+            }
+        }
+
+        let to_ids = |xs: Vec<usize>| -> Result<Vec<S>> {
+            xs.into_iter().map(usize_to_state_id).collect()
+        };
+        // Every state identifier must be representable, even if the largest
+        // base offset is not.
+        usize_to_state_id::<S>(base.len() - 1)?;
+        Ok(KeywordDFA { base: to_ids(base)?, check: to_ids(check)?, matches })
+    }
+
+    /// Build a DFA that matches each keyword in reverse.
+    ///
+    /// This is useful for building the reverse DFA of a
+    /// [`Regex`](../struct.Regex.html), which is used to find the start of
+    /// each match.
+    ///
+    /// This is disabled by default.
+    pub fn reverse(&mut self, yes: bool) -> &mut Builder {
+        self.reverse = yes;
+        self
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+/// A simple trie used as an intermediate representation while building a
+/// double array.
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Default)]
+struct TrieNode {
+    /// The children of this node, sorted by byte.
+    children: Vec<(u8, usize)>,
+    is_match: bool,
+}
+
+impl Trie {
+    fn insert<I: Iterator<Item = u8>>(&mut self, keyword: I) {
+        let mut node = 0;
+        for b in keyword {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&b, |&(byte, _)| byte)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.insert(i, (b, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].is_match = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn matches_longest_keyword() {
+        let keywords = &["a", "ab", "abc", "b", "bcd", "", "\u{2603}", "zzz"];
+        let fwd = Builder::new().build_with_size::<u16, _, _>(keywords);
+        let rev = Builder::new()
+            .reverse(true)
+            .build_with_size::<u16, _, _>(keywords);
+        let re = Regex::from_dfas(fwd.unwrap(), rev.unwrap());
+
+        let haystack = "abcdab\u{2603}zz".as_bytes();
+        for start in 0..haystack.len() + 1 {
+            let suffix = &haystack[start..];
+            let expected = keywords
+                .iter()
+                .filter(|k| suffix.starts_with(k.as_bytes()))
+                .map(|k| (0, k.len()))
+                .max_by_key(|&(_, end)| end);
+            assert_eq!(expected, re.find(suffix), "{}", start);
+        }
+    }
+
+    #[test]
+    fn state_id_overflow() {
+        let keywords: Vec<String> =
+            (0..1000).map(|i| i.to_string()).collect();
+        let builder = Builder::new();
+        assert!(builder.build_with_size::<u8, _, _>(&keywords).is_err());
+        assert!(builder.build_with_size::<u16, _, _>(&keywords).is_ok());
+    }
+}
@@ -0,0 +1,633 @@
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+
+#[cfg(feature = "sample")]
+use rand_core::RngCore;
+
+use classes::ByteClasses;
+use dfa::DFA;
+
+/// The states of a DFA that are reachable from its start state, with their
+/// transitions indexed by byte class.
+///
+/// This is the common representation used to answer questions about the set
+/// of strings that lead a DFA from its start state to a match state, such as
+/// sampling from that set.
+#[derive(Clone, Debug)]
+pub(crate) struct Graph {
+    /// The byte classes used to index transitions.
+    byte_classes: ByteClasses,
+    /// The inclusive range of bytes in each class.
+    classes: Vec<(u8, u8)>,
+    /// The transitions of every state, where the transition on class `c`
+    /// from state `s` is at `s * classes.len() + c`.
+    trans: Vec<usize>,
+    /// Whether each state is a match state.
+    is_match: Vec<bool>,
+}
+
+impl Graph {
+    /// Build the graph of the states reachable from the start state of the
+    /// given DFA. The start state is always state `0` of the graph.
+    ///
+    /// Every byte in the same class must lead to the same state from every
+    /// state of the DFA.
+    pub(crate) fn new<D: DFA + ?Sized>(
+        dfa: &D,
+        classes: &ByteClasses,
+    ) -> Graph {
+        let mut ranges: Vec<(u8, u8)> = vec![(0, 0)];
+        for b in 1..256 {
+            let b = b as u8;
+            if classes.get(b) == classes.get(b - 1) {
+                ranges.last_mut().unwrap().1 = b;
+            } else {
+                ranges.push((b, b));
+            }
+        }
+
+        let mut graph = Graph {
+            byte_classes: *classes,
+            classes: ranges,
+            trans: vec![],
+            is_match: vec![],
+        };
+        let mut ids: HashMap<D::ID, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+        ids.insert(dfa.start_state(), 0);
+        queue.push_back(dfa.start_state());
+        while let Some(id) = queue.pop_front() {
+            graph.is_match.push(dfa.is_match_state(id));
+            for i in 0..graph.classes.len() {
+                let next = dfa.next_state(id, graph.classes[i].0);
+                let len = ids.len();
+                let next_id = *ids.entry(next).or_insert_with(|| {
+                    queue.push_back(next);
+                    len
+                });
+                graph.trans.push(next_id);
+            }
+        }
+        graph
+    }
+
+    /// Returns the number of states in this graph.
+    pub(crate) fn len(&self) -> usize {
+        self.is_match.len()
+    }
+
+    /// Returns the state reached from the given state on any byte in the
+    /// given class.
+    fn next(&self, id: usize, class: usize) -> usize {
+        self.trans[id * self.classes.len() + class]
+    }
+
+    /// Returns whether a match state is reached from each state after
+    /// exactly `k` bytes, for every `k` from `0` up to and including
+    /// `max_len`.
+    fn exact_lengths(&self, max_len: usize) -> Vec<Vec<bool>> {
+        let mut layers = vec![self.is_match.clone()];
+        for k in 1..=max_len {
+            let layer = (0..self.len())
+                .map(|id| {
+                    (0..self.classes.len())
+                        .any(|class| layers[k - 1][self.next(id, class)])
+                })
+                .collect();
+            layers.push(layer);
+        }
+        layers
+    }
+
+    /// Returns the length of the shortest string that leads from the start
+    /// state to a match state, if there is one.
+    pub(crate) fn min_match_len(&self) -> Option<usize> {
+        self.distances()[0]
+    }
+
+    /// Returns the first string in shortlex order among the shortest strings
+    /// that lead from the start state to a match state, if there is one.
+    #[cfg(feature = "capi")]
+    pub(crate) fn shortest_match(&self) -> Option<Vec<u8>> {
+        let dist = self.distances();
+        let mut d = dist[0]?;
+        let (mut id, mut string) = (0, vec![]);
+        while d > 0 {
+            let class = (0..self.classes.len())
+                .find(|&class| dist[self.next(id, class)] == Some(d - 1))
+                .unwrap();
+            string.push(self.classes[class].0);
+            id = self.next(id, class);
+            d -= 1;
+        }
+        Some(string)
+    }
+
+    /// Returns the length of the longest string that leads from the start
+    /// state to a match state, or `None` if there is no such string or if
+    /// there are no bounds on its length.
+    pub(crate) fn max_match_len(&self) -> Option<usize> {
+        // Only states from which a match state can be reached matter. The
+        // longest string is unbounded precisely when there is a cycle
+        // through those states, so this searches them depth first, finding
+        // the longest string from each state once all of its successors are
+        // done.
+        let dist = self.distances();
+        dist[0]?;
+        let mut longest: Vec<Option<usize>> = vec![None; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = vec![(0, 0)];
+        on_stack[0] = true;
+        while let Some(&(id, class)) = stack.last() {
+            if class == self.classes.len() {
+                stack.pop();
+                on_stack[id] = false;
+                let mut max = if self.is_match[id] { Some(0) } else { None };
+                for class in 0..self.classes.len() {
+                    let next = self.next(id, class);
+                    if let Some(len) = longest[next] {
+                        max = Some(cmp::max(max.unwrap_or(0), len + 1));
+                    }
+                }
+                longest[id] = max;
+                continue;
+            }
+            stack.last_mut().unwrap().1 += 1;
+            let next = self.next(id, class);
+            if dist[next].is_none() {
+                continue;
+            }
+            if on_stack[next] {
+                return None;
+            }
+            if longest[next].is_none() {
+                on_stack[next] = true;
+                stack.push((next, 0));
+            }
+        }
+        longest[0]
+    }
+
+    /// Returns the number of strings of exactly `len` bytes that lead from
+    /// the start state to a match state, or `None` if the number does not
+    /// fit in a `u128`.
+    pub(crate) fn count_exact(&self, len: usize) -> Option<u128> {
+        // `counts[s]` is the number of strings of exactly `k` bytes that
+        // lead from `s` to a match state, where `None` means that the number
+        // overflowed. A count that overflowed only contributes to counts that
+        // must overflow too.
+        let mut counts: Vec<Option<u128>> = self
+            .is_match
+            .iter()
+            .map(|&m| Some(if m { 1 } else { 0 }))
+            .collect();
+        for _ in 0..len {
+            counts = (0..self.len())
+                .map(|id| {
+                    let mut count: u128 = 0;
+                    for class in 0..self.classes.len() {
+                        let next = counts[self.next(id, class)]?;
+                        let n = (self.class_len(class) as u128)
+                            .checked_mul(next)?;
+                        count = count.checked_add(n)?;
+                    }
+                    Some(count)
+                })
+                .collect();
+        }
+        counts[0]
+    }
+
+    /// Returns the number of bytes in the given class.
+    fn class_len(&self, class: usize) -> u64 {
+        let (start, end) = self.classes[class];
+        end as u64 - start as u64 + 1
+    }
+
+    /// Returns the minimum number of transitions needed to reach a match
+    /// state from each state, if a match state can be reached at all.
+    fn distances(&self) -> Vec<Option<usize>> {
+        let mut reverse = vec![vec![]; self.len()];
+        for id in 0..self.len() {
+            for class in 0..self.classes.len() {
+                reverse[self.next(id, class)].push(id);
+            }
+        }
+
+        let mut dist = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        for (id, &is_match) in self.is_match.iter().enumerate() {
+            if is_match {
+                dist[id] = Some(0);
+                queue.push_back(id);
+            }
+        }
+        while let Some(id) = queue.pop_front() {
+            let d = dist[id].unwrap() + 1;
+            for &prev in &reverse[id] {
+                if dist[prev].is_none() {
+                    dist[prev] = Some(d);
+                    queue.push_back(prev);
+                }
+            }
+        }
+        dist
+    }
+}
+
+/// An iterator over every string of at most a given length after which a
+/// DFA is in a match state.
+///
+/// Strings are yielded in shortlex order: shorter strings come first, and
+/// strings of the same length are in lexicographic order.
+///
+/// This is created by
+/// [`DenseDFA::strings`](enum.DenseDFA.html#method.strings).
+#[derive(Clone, Debug)]
+pub struct Strings {
+    graph: Graph,
+    /// Whether each state reaches a match state after exactly `k` bytes,
+    /// indexed by `k`.
+    exact: Vec<Vec<bool>>,
+    max_len: usize,
+    /// The length of the strings currently being enumerated.
+    len: usize,
+    /// The state after each prefix of `string`, along with the next byte to
+    /// try from that state.
+    stack: Vec<(usize, usize)>,
+    /// The bytes taken by the current path through the graph.
+    string: Vec<u8>,
+}
+
+impl Strings {
+    pub(crate) fn new(graph: Graph, max_len: usize) -> Strings {
+        let exact = graph.exact_lengths(max_len);
+        Strings {
+            graph,
+            exact,
+            max_len,
+            len: 0,
+            stack: vec![],
+            string: vec![],
+        }
+    }
+
+    /// Remove the top of the stack, and move on to the next length once
+    /// every string of the current length has been found.
+    fn pop(&mut self) {
+        self.stack.pop();
+        if self.stack.is_empty() {
+            self.len += 1;
+        } else {
+            self.string.pop();
+        }
+    }
+}
+
+impl Iterator for Strings {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        loop {
+            let (id, byte) = match self.stack.last() {
+                Some(&top) => top,
+                None => {
+                    if self.len > self.max_len {
+                        return None;
+                    }
+                    if self.exact[self.len][0] {
+                        self.stack.push((0, 0));
+                    } else {
+                        self.len += 1;
+                    }
+                    continue;
+                }
+            };
+            let remaining = self.len - self.string.len();
+            if remaining == 0 {
+                let string = self.string.clone();
+                self.pop();
+                return Some(string);
+            }
+            if byte > 255 {
+                self.pop();
+                continue;
+            }
+            // Every byte in a class leads to the same state, so a class that
+            // cannot lead to a match is skipped all at once.
+            let class = self.graph.byte_classes.get(byte as u8) as usize;
+            let next = self.graph.next(id, class);
+            if self.exact[remaining - 1][next] {
+                self.stack.last_mut().unwrap().1 = byte + 1;
+                self.string.push(byte as u8);
+                self.stack.push((next, 0));
+            } else {
+                let end = self.graph.classes[class].1 as usize;
+                self.stack.last_mut().unwrap().1 = end + 1;
+            }
+        }
+    }
+}
+
+/// Return a random string of at most `max_len` bytes that leads from the
+/// start state of the given graph to a match state, if one exists.
+///
+/// At each step, the walk either stops in a match state or follows a byte
+/// from which a match state can still be reached within the remaining
+/// length, where every such choice is equally likely.
+#[cfg(feature = "sample")]
+pub(crate) fn sample<R: RngCore>(
+    graph: &Graph,
+    rng: &mut R,
+    max_len: usize,
+) -> Option<Vec<u8>> {
+    let dist = graph.distances();
+    let reaches = |id: usize, len: usize| match dist[id] {
+        None => false,
+        Some(d) => d <= len,
+    };
+    if !reaches(0, max_len) {
+        return None;
+    }
+
+    let mut string = vec![];
+    let mut id = 0;
+    for remaining in (0..max_len).rev() {
+        let mut total = if graph.is_match[id] { 1 } else { 0 };
+        for class in 0..graph.classes.len() {
+            if reaches(graph.next(id, class), remaining) {
+                total += graph.class_len(class);
+            }
+        }
+        let mut choice = below(rng, total);
+        if graph.is_match[id] {
+            if choice == 0 {
+                return Some(string);
+            }
+            choice -= 1;
+        }
+        for class in 0..graph.classes.len() {
+            if !reaches(graph.next(id, class), remaining) {
+                continue;
+            }
+            if choice < graph.class_len(class) {
+                string.push(graph.classes[class].0 + choice as u8);
+                id = graph.next(id, class);
+                break;
+            }
+            choice -= graph.class_len(class);
+        }
+    }
+    debug_assert!(graph.is_match[id]);
+    Some(string)
+}
+
+/// Return a string chosen uniformly at random from all strings of at most
+/// `max_len` bytes that lead from the start state of the given graph to a
+/// match state, if there are any.
+///
+/// This requires space proportional to `max_len` times the number of states
+/// in the graph.
+#[cfg(feature = "sample")]
+pub(crate) fn sample_uniform<R: RngCore>(
+    graph: &Graph,
+    rng: &mut R,
+    max_len: usize,
+) -> Option<Vec<u8>> {
+    // `counts[k][s]` is proportional to the number of strings of at most `k`
+    // bytes that lead from `s` to a match state. Every count in the same
+    // layer is scaled by `2^scales[k]` to avoid overflowing an `f64`.
+    let mut counts: Vec<Vec<f64>> = vec![
+        graph.is_match.iter().map(|&m| if m { 1.0 } else { 0.0 }).collect(),
+    ];
+    let mut scales: Vec<i32> = vec![0];
+    for k in 1..=max_len {
+        let (prev, scale) = (&counts[k - 1], scales[k - 1]);
+        let stop = 2f64.powi(-scale);
+        let mut layer: Vec<f64> = (0..graph.len())
+            .map(|id| {
+                let mut count = if graph.is_match[id] { stop } else { 0.0 };
+                for class in 0..graph.classes.len() {
+                    count += graph.class_len(class) as f64
+                        * prev[graph.next(id, class)];
+                }
+                count
+            })
+            .collect();
+        let max = layer.iter().cloned().fold(0.0, f64::max);
+        let mut scale = scale;
+        if max > 2f64.powi(64) {
+            let shift = max.log2() as i32;
+            for count in &mut layer {
+                *count *= 2f64.powi(-shift);
+            }
+            scale += shift;
+        }
+        counts.push(layer);
+        scales.push(scale);
+    }
+    if counts[max_len][0] == 0.0 {
+        return None;
+    }
+
+    let mut string = vec![];
+    let mut id = 0;
+    for k in (1..=max_len).rev() {
+        let prev = &counts[k - 1];
+        let stop =
+            if graph.is_match[id] { 2f64.powi(-scales[k - 1]) } else { 0.0 };
+        let mut choice = uniform(rng) * counts[k][id] * 2f64.powi(
+            scales[k] - scales[k - 1],
+        );
+        if choice < stop {
+            return Some(string);
+        }
+        choice -= stop;
+        // Rounding may leave a small remainder after the last class, in
+        // which case the last class that can reach a match state is chosen.
+        let mut chosen = None;
+        for class in 0..graph.classes.len() {
+            let next = graph.next(id, class);
+            if prev[next] == 0.0 {
+                continue;
+            }
+            chosen = Some(class);
+            let weight = graph.class_len(class) as f64 * prev[next];
+            if choice < weight {
+                break;
+            }
+            choice -= weight;
+        }
+        let class = match chosen {
+            Some(class) => class,
+            None => return Some(string),
+        };
+        let offset = below(rng, graph.class_len(class)) as u8;
+        string.push(graph.classes[class].0 + offset);
+        id = graph.next(id, class);
+    }
+    Some(string)
+}
+
+/// Return a number chosen uniformly at random from `0..n`, where `n > 0`.
+#[cfg(feature = "sample")]
+fn below<R: RngCore>(rng: &mut R, n: u64) -> u64 {
+    // Reject the values at the top of the range that would make some
+    // numbers more likely than others.
+    let max = ::std::u64::MAX;
+    let zone = max - (max - n + 1) % n;
+    loop {
+        let x = rng.next_u64();
+        if x <= zone {
+            return x % n;
+        }
+    }
+}
+
+/// Return a number chosen uniformly at random from `[0, 1)`.
+#[cfg(feature = "sample")]
+fn uniform<R: RngCore>(rng: &mut R) -> f64 {
+    (rng.next_u64() >> 11) as f64 * 2f64.powi(-53)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "sample")]
+    use std::collections::HashMap;
+
+    #[cfg(feature = "sample")]
+    use rand_core::SeedableRng;
+    #[cfg(feature = "sample")]
+    use rand_xoshiro::Xoshiro256StarStar;
+
+    use dense;
+    use dfa::DFA;
+
+    fn anchored(pattern: &str) -> dense::DenseDFA<Vec<usize>, usize> {
+        dense::Builder::new()
+            .anchored(true)
+            .longest_match(true)
+            .build(pattern)
+            .unwrap()
+    }
+
+    #[test]
+    fn strings_in_shortlex_order() {
+        let strings = |pattern: &str, max_len: usize| -> Vec<String> {
+            anchored(pattern)
+                .strings(max_len)
+                .map(|s| String::from_utf8(s).unwrap())
+                .collect()
+        };
+        assert_eq!(
+            vec!["", "a", "b", "aa", "ab", "ba", "bb"],
+            strings("[ab]*", 2),
+        );
+        assert_eq!(
+            vec!["x", "ab", "ax", "bx", "abx"],
+            strings("(?:a|ab|b)?x|ab", 9),
+        );
+        assert_eq!(vec!["\u{394}"], strings("\u{394}|\u{10000}", 3));
+        assert!(strings("abc", 2).is_empty());
+
+        // Every string found must be accepted, and every string of at most
+        // two bytes that is accepted must be found.
+        let dfa = anchored("(?-u:[\\x00-\\x20])|a[^a]|[0-9]{3}");
+        let found: Vec<Vec<u8>> = dfa.strings(2).collect();
+        let mut expected = vec![];
+        for b in 0..256 {
+            expected.push(vec![b as u8]);
+        }
+        for b1 in 0..256 {
+            for b2 in 0..256 {
+                expected.push(vec![b1 as u8, b2 as u8]);
+            }
+        }
+        expected.retain(|s| dfa.find(s) == Some(s.len()));
+        assert_eq!(expected, found);
+        for len in 0..3 {
+            let count = found.iter().filter(|s| s.len() == len).count();
+            assert_eq!(Some(count as u128), dfa.count_matches_of_len(len));
+        }
+    }
+
+    #[test]
+    fn match_lengths() {
+        let lens = |pattern: &str| {
+            let dfa = anchored(pattern);
+            let sparse = dfa.to_sparse().unwrap();
+            assert_eq!(dfa.min_match_len(), sparse.min_match_len());
+            assert_eq!(dfa.max_match_len(), sparse.max_match_len());
+            assert_eq!(dfa.is_finite_language(), sparse.is_finite_language());
+            (
+                dfa.matches_empty(),
+                dfa.min_match_len(),
+                dfa.max_match_len(),
+                dfa.is_finite_language(),
+            )
+        };
+        assert_eq!((true, Some(0), Some(0), true), lens(""));
+        assert_eq!((false, Some(3), Some(3), true), lens("abc"));
+        assert_eq!((true, Some(0), Some(4), true), lens("(?:ab)?|a{4}"));
+        assert_eq!((true, Some(0), None, false), lens("a*"));
+        assert_eq!((false, Some(2), None, false), lens("ab|x+y"));
+        assert_eq!((false, Some(1), Some(4), true), lens("a|abcd|b{2,3}"));
+        assert_eq!(Some(4), lens("\\u{10000}|\\u{394}").2);
+
+        let none: &[&str] = &[];
+        let never = dense::Builder::new()
+            .anchored(true)
+            .build_literals(none)
+            .unwrap();
+        assert!(!never.matches_empty());
+        assert_eq!(None, never.min_match_len());
+        assert_eq!(None, never.max_match_len());
+        assert!(never.is_finite_language());
+
+        let unanchored = dense::Builder::new().build("abc").unwrap();
+        assert!(!unanchored.is_finite_language());
+    }
+
+    #[cfg(feature = "sample")]
+    #[test]
+    fn samples_are_accepted() {
+        let mut rng = Xoshiro256StarStar::seed_from_u64(1);
+        let patterns = &["", "a*", "[a-z]{3}[0-9]?", "(?:ab|c)+x", r"\w{2}"];
+        for pattern in patterns {
+            let dfa = anchored(pattern);
+            for _ in 0..100 {
+                let samples = vec![
+                    dfa.sample(&mut rng, 7),
+                    dfa.sample_uniform(&mut rng, 7),
+                ];
+                for s in samples.into_iter().flatten() {
+                    assert!(s.len() <= 7, "{:?}: {:?}", pattern, s);
+                    assert_eq!(Some(s.len()), dfa.find(&s), "{:?}", pattern);
+                }
+            }
+        }
+
+        let dfa = anchored("abc|[0-9]{5}");
+        assert_eq!(None, dfa.sample(&mut rng, 2));
+        assert_eq!(None, dfa.sample_uniform(&mut rng, 2));
+        assert_eq!(Some(b"abc".to_vec()), dfa.sample(&mut rng, 3));
+        assert_eq!(Some(b"abc".to_vec()), dfa.sample_uniform(&mut rng, 4));
+    }
+
+    #[cfg(feature = "sample")]
+    #[test]
+    fn sample_uniform_is_uniform() {
+        let mut rng = Xoshiro256StarStar::seed_from_u64(2);
+        // 1 + 2 + 4 + 8 strings of lengths 0 through 3.
+        let dfa = anchored("[ab]*");
+        let mut counts = HashMap::new();
+        for _ in 0..15_000 {
+            let s = dfa.sample_uniform(&mut rng, 3).unwrap();
+            *counts.entry(s).or_insert(0) += 1;
+        }
+        assert_eq!(15, counts.len());
+        for (s, &count) in &counts {
+            assert!(800 < count && count < 1200, "{:?}: {}", s, count);
+        }
+    }
+}
@@ -162,7 +162,10 @@ assert_eq!(matches, vec![(0, 10), (11, 21)]);
 
 Note that unlike dense DFAs, sparse DFAs have no alignment requirements.
 Conversely, dense DFAs must be be aligned to the same alignment as their
-state identifier representation.
+state identifier representation. When embedding a serialized dense DFA in a
+binary, use the
+[`include_aligned_bytes!`](macro.include_aligned_bytes.html)
+macro instead of `include_bytes!` to guarantee this.
 
 # Support for `no_std`
 
@@ -185,11 +188,63 @@ The intended workflow for `no_std` environments is thus as follows:
   as you would any regex.
 
 Deserialization can happen anywhere. For example, with bytes embedded into a
-binary or with a file memory mapped at runtime.
+binary or with a file memory mapped at runtime. When the `mmap` feature is
+enabled, [`DenseDFA::open`](enum.DenseDFA.html#method.open) does the latter.
 
 Note that the
 [`ucd-generate`](https://github.com/BurntSushi/ucd-generate)
 tool will do the first step for you with its `dfa` or `regex` sub-commands.
+This crate also provides a `regex-automata-compile` tool, built when the
+`compile` feature is enabled, that does the same with options mirroring those
+of [`RegexBuilder`](struct.RegexBuilder.html). For example,
+`regex-automata-compile -o out --minimize 'foo[0-9]+'` writes the forward and
+reverse DFAs of a regex, in both endiannesses, to the `out` directory.
+
+Alternatively, the [`codegen`](codegen/index.html) module can write DFAs as
+Rust source code with `static` transition tables. Including the generated code
+in a `no_std` binary requires no deserialization at all, and such code is
+independent of endianness and alignment.
+
+Programs written in other languages can use the C API in the
+[`capi`](capi/index.html) module, enabled by the `capi` feature, to compile,
+serialize, deserialize and search with regexes without reimplementing the
+serialization format.
+
+# Unicode data
+
+The `unicode` feature, which is enabled by default, includes the Unicode data
+tables used by the regex parser. These tables are only needed to compile
+regular expressions and account for a substantial portion of the size of a
+binary. Each table can be selected individually with one of the following
+features:
+
+* `unicode-age` for `\p{age:...}` classes.
+* `unicode-bool` for boolean properties such as `\p{Alphabetic}`.
+* `unicode-case` for case insensitive matching in Unicode mode.
+* `unicode-gencat` for general categories such as `\p{Letter}`.
+* `unicode-perl` for the Unicode aware Perl classes `\w`, `\s` and `\d`.
+* `unicode-script` for scripts such as `\p{Greek}`.
+* `unicode-segment` for segmentation properties such as `\p{gcb=Extend}`.
+
+Compiling a pattern that requires a disabled table fails with a syntax error
+that names the missing feature. ASCII-only classes such as `(?-u:\w)` and
+`[[:alpha:]]` are always available, and deserializing and searching DFAs never
+requires any Unicode data.
+
+# Serde
+
+When the `serde` feature is enabled, dense and sparse DFAs, along with regexes
+built from them, implement serde's `Serialize` and `Deserialize` traits. DFAs
+are serialized as the same bytes returned by their `to_bytes_native_endian`
+methods, and those bytes are fully validated when deserialized. This makes it
+possible to embed compiled regexes in larger configuration files, even when
+they come from an untrusted source. Deserialization always produces DFAs that
+own their transition tables.
+
+Validation checks that each DFA is well formed on its own, but it cannot check
+that the forward and reverse DFAs of a deserialized regex were built from the
+same pattern. A mismatched pair never causes a panic or memory unsafety, but
+searching with it may report incorrect matches or miss matches.
 
 # Syntax
 
@@ -289,39 +344,85 @@ With some of the downsides out of the way, here are some positive differences:
 extern crate core;
 
 extern crate byteorder;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "sample")]
+extern crate rand_core;
+#[cfg(all(test, feature = "sample"))]
+extern crate rand_xoshiro;
+#[cfg(feature = "conformance")]
+extern crate regex as regex_crate;
 #[cfg(feature = "std")]
 extern crate regex_syntax;
+#[cfg(feature = "serde")]
+extern crate serde;
 #[cfg(feature = "std")]
 extern crate utf8_ranges;
 
+pub use aligned::AlignedBytes;
+pub use classes::{ByteClassRepresentatives, ByteClasses};
 pub use dense::DenseDFA;
-pub use dfa::DFA;
+pub use dfa::{Chunks, DFA, Incremental, NoMatch, Suspended, Verdict};
 #[cfg(feature = "std")]
 pub use error::{Error, ErrorKind};
-pub use regex::Regex;
+#[cfg(feature = "std")]
+pub use keyword::KeywordDFA;
+pub use memory::MemoryUsage;
+pub use regex::{Regex, Span};
 #[cfg(feature = "std")]
 pub use regex::RegexBuilder;
 pub use sparse::SparseDFA;
 pub use state_id::StateID;
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+pub use state_id::U24;
 
+mod aligned;
+#[cfg(feature = "capi")]
+pub mod capi;
 mod classes;
 #[cfg(feature = "std")]
+pub mod codegen;
+#[cfg(feature = "std")]
+mod crc32;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod database;
+#[cfg(feature = "std")]
+pub mod dawg;
+#[cfg(feature = "std")]
 mod determinize;
+#[cfg(feature = "std")]
+pub mod dot;
 #[path = "dense.rs"]
 mod dense_imp;
 mod dfa;
 #[cfg(feature = "std")]
 mod error;
+#[cfg(feature = "std")]
+pub mod html;
+#[cfg(feature = "std")]
+pub mod keyword;
+#[cfg(feature = "std")]
+mod language;
+#[cfg(feature = "measure")]
+pub mod measure;
+mod memory;
 mod regex;
 #[cfg(feature = "std")]
 mod minimize;
 #[cfg(feature = "std")]
-mod nfa;
+pub mod nfa;
+#[cfg(feature = "serde")]
+mod serde_impls;
 #[path = "sparse.rs"]
 mod sparse_imp;
 #[cfg(feature = "std")]
 mod sparse_set;
+#[cfg(feature = "std")]
+pub mod stream;
 mod state_id;
+#[cfg(feature = "std")]
+mod table;
 
 /// Types and routines specific to dense DFAs.
 ///
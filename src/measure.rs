@@ -0,0 +1,260 @@
+/*!
+Routines for measuring the search throughput of regexes and DFAs.
+
+These are intended for programmatically comparing the search performance of
+different configurations, for example, dense versus sparse DFAs, or different
+state identifier representations, over a corpus that is representative of
+one's work load. They are not a substitute for a proper benchmarking harness,
+since they neither warm up caches nor compute any statistics over repeated
+runs.
+
+Besides timing, each measurement reports [`Counters`](struct.Counters.html)
+describing the work done by the DFAs, such as the number of transitions
+followed and the number of times a search stopped in a dead state. These are
+collected in a separate, untimed search, so that counting does not slow down
+the timed searches.
+
+This module is only available when the `measure` feature is enabled.
+
+# Example
+
+```
+use regex_automata::{measure, Regex};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let re = Regex::new("foo[0-9]+")?;
+let m = measure::regex(&re, b"foo1 foo12 foo123", 10);
+assert_eq!(170, m.bytes());
+assert_eq!(30, m.matches());
+println!("{:.0} bytes/sec", m.bytes_per_second());
+println!("{} transitions", m.counters().transitions());
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use dfa::DFA;
+use regex::Regex;
+
+/// The result of measuring the search throughput over a corpus.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Measurement {
+    bytes: u64,
+    matches: u64,
+    elapsed: Duration,
+    counters: Counters,
+}
+
+impl Measurement {
+    /// Returns the total number of bytes searched, summed over all
+    /// iterations.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the total number of matches found, summed over all
+    /// iterations.
+    pub fn matches(&self) -> u64 {
+        self.matches
+    }
+
+    /// Returns the total time spent searching.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the counters describing the work done by the DFAs, summed
+    /// over all iterations.
+    pub fn counters(&self) -> Counters {
+        self.counters
+    }
+
+    /// Returns the search throughput in bytes per second.
+    ///
+    /// If no measurable time elapsed, then this returns infinity.
+    pub fn bytes_per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs() as f64
+            + self.elapsed.subsec_nanos() as f64 / 1_000_000_000.0;
+        self.bytes as f64 / secs
+    }
+}
+
+/// Counters describing the work done by the DFAs during a search.
+///
+/// For a regex, these include the work done by both its forward and reverse
+/// DFAs.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::{dense, measure};
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new().anchored(true).build("ab+")?;
+/// let counters = measure::dfa(&dfa, b"abbc", 1).counters();
+/// // The transitions on `a`, `b`, `b` and finally `c`, which leads to the
+/// // dead state and ends the search.
+/// assert_eq!(4, counters.transitions());
+/// assert_eq!(2, counters.match_states());
+/// assert_eq!(1, counters.dead_states());
+/// # Ok(()) }; example().unwrap()
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Counters {
+    transitions: u64,
+    match_states: u64,
+    dead_states: u64,
+}
+
+impl Counters {
+    /// Returns the number of transitions followed, which is the number of
+    /// states visited after the start state of each search.
+    pub fn transitions(&self) -> u64 {
+        self.transitions
+    }
+
+    /// Returns the number of transitions that led to a match state.
+    pub fn match_states(&self) -> u64 {
+        self.match_states
+    }
+
+    /// Returns the number of transitions that led to the dead state, each
+    /// of which stops a search.
+    pub fn dead_states(&self) -> u64 {
+        self.dead_states
+    }
+
+    fn times(self, n: u64) -> Counters {
+        Counters {
+            transitions: self.transitions * n,
+            match_states: self.match_states * n,
+            dead_states: self.dead_states * n,
+        }
+    }
+}
+
+/// A DFA that counts the transitions of another DFA.
+struct Counted<'a, D: 'a> {
+    dfa: &'a D,
+    counters: &'a Cell<Counters>,
+}
+
+impl<'a, D: DFA> Counted<'a, D> {
+    fn count(&self, next: D::ID) -> D::ID {
+        let mut counters = self.counters.get();
+        counters.transitions += 1;
+        if self.dfa.is_match_or_dead_state(next) {
+            if self.dfa.is_dead_state(next) {
+                counters.dead_states += 1;
+            } else {
+                counters.match_states += 1;
+            }
+        }
+        self.counters.set(counters);
+        next
+    }
+}
+
+impl<'a, D: DFA> DFA for Counted<'a, D> {
+    type ID = D::ID;
+
+    fn start_state(&self) -> D::ID {
+        self.dfa.start_state()
+    }
+
+    fn is_match_state(&self, id: D::ID) -> bool {
+        self.dfa.is_match_state(id)
+    }
+
+    fn is_dead_state(&self, id: D::ID) -> bool {
+        self.dfa.is_dead_state(id)
+    }
+
+    fn is_match_or_dead_state(&self, id: D::ID) -> bool {
+        self.dfa.is_match_or_dead_state(id)
+    }
+
+    fn is_anchored(&self) -> bool {
+        self.dfa.is_anchored()
+    }
+
+    fn next_state(&self, current: D::ID, input: u8) -> D::ID {
+        self.count(self.dfa.next_state(current, input))
+    }
+
+    unsafe fn next_state_unchecked(&self, current: D::ID, input: u8) -> D::ID {
+        self.count(self.dfa.next_state_unchecked(current, input))
+    }
+}
+
+/// Measure the throughput of finding all non-overlapping matches of the
+/// given regex in `corpus`, repeated `iterations` times.
+///
+/// The matches are found in precisely the same way as
+/// [`Regex::find_iter`](../struct.Regex.html#method.find_iter).
+pub fn regex<D: DFA>(
+    re: &Regex<D>,
+    corpus: &[u8],
+    iterations: usize,
+) -> Measurement {
+    let counters = Cell::new(Counters::default());
+    let counted = Regex::from_dfas(
+        Counted { dfa: re.forward(), counters: &counters },
+        Counted { dfa: re.reverse(), counters: &counters },
+    );
+    counted.find_iter(corpus).count();
+    let mut m = measure(corpus, iterations, || re.find_iter(corpus).count());
+    m.counters = counters.get().times(iterations as u64);
+    m
+}
+
+/// Measure the throughput of finding the ends of all non-overlapping matches
+/// of the given DFA in `corpus`, repeated `iterations` times.
+///
+/// Each search begins where the previous match ended, as reported by
+/// [`DFA::find_at`](../trait.DFA.html#method.find_at). Since no reverse DFA
+/// is run, this measures the throughput of a forward DFA on its own.
+pub fn dfa<D: DFA>(dfa: &D, corpus: &[u8], iterations: usize) -> Measurement {
+    let counters = Cell::new(Counters::default());
+    find_ends(&Counted { dfa, counters: &counters }, corpus);
+    let mut m = measure(corpus, iterations, || find_ends(dfa, corpus));
+    m.counters = counters.get().times(iterations as u64);
+    m
+}
+
+/// Return the number of non-overlapping match ends found by the given DFA.
+fn find_ends<D: DFA>(dfa: &D, corpus: &[u8]) -> usize {
+    let mut count = 0;
+    let mut start = 0;
+    while start <= corpus.len() {
+        let end = match dfa.find_at(corpus, start) {
+            None => break,
+            Some(end) => end,
+        };
+        count += 1;
+        start = if end == start { end + 1 } else { end };
+    }
+    count
+}
+
+/// Run the given search, which returns the number of matches found, the
+/// given number of times and measure its total running time.
+fn measure<F: FnMut() -> usize>(
+    corpus: &[u8],
+    iterations: usize,
+    mut search: F,
+) -> Measurement {
+    let mut matches = 0;
+    let start = Instant::now();
+    for _ in 0..iterations {
+        matches += search() as u64;
+    }
+    Measurement {
+        bytes: corpus.len() as u64 * iterations as u64,
+        matches,
+        elapsed: start.elapsed(),
+        counters: Counters::default(),
+    }
+}
@@ -0,0 +1,58 @@
+/// A breakdown of the memory used by a DFA or a regex.
+///
+/// This is returned by the `memory_breakdown` methods of
+/// [`DenseDFA`](enum.DenseDFA.html#method.memory_breakdown),
+/// [`SparseDFA`](enum.SparseDFA.html#method.memory_breakdown) and
+/// [`Regex`](struct.Regex.html#method.memory_breakdown).
+///
+/// Of the components listed here, only the transition table grows with the
+/// size of a DFA, and it is the only component stored on the heap, unless
+/// the DFA borrows its transition table. The byte classes and match state
+/// metadata take a fixed number of bytes that are stored inline, but are
+/// included so that the total accounts for all of the data a search uses.
+/// As with the `memory_usage` methods, this does not include the rest of the
+/// stack size of a DFA.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MemoryUsage {
+    pub(crate) transitions: usize,
+    pub(crate) byte_classes: usize,
+    pub(crate) match_states: usize,
+    pub(crate) reverse: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total number of bytes used by every component.
+    pub fn total(&self) -> usize {
+        self.transitions + self.byte_classes + self.match_states + self.reverse
+    }
+
+    /// Returns the number of bytes used by the transition table.
+    ///
+    /// For a regex, this is the transition table of its forward DFA.
+    pub fn transitions(&self) -> usize {
+        self.transitions
+    }
+
+    /// Returns the number of bytes used by the map from bytes to byte
+    /// classes.
+    ///
+    /// For a regex, this is the map of its forward DFA.
+    pub fn byte_classes(&self) -> usize {
+        self.byte_classes
+    }
+
+    /// Returns the number of bytes used to determine which states are match
+    /// states.
+    ///
+    /// For a regex, this is the metadata of its forward DFA.
+    pub fn match_states(&self) -> usize {
+        self.match_states
+    }
+
+    /// Returns the total number of bytes used by the reverse DFA of a regex.
+    ///
+    /// This is always zero for a DFA on its own.
+    pub fn reverse(&self) -> usize {
+        self.reverse
+    }
+}
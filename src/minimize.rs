@@ -3,7 +3,8 @@ use std::fmt;
 use std::mem;
 use std::rc::Rc;
 
-use dense;
+use dense::{self, Deadline};
+use error::Result;
 use state_id::{StateID, dead_id};
 
 type DFARepr<S> = dense::Repr<Vec<S>, S>;
@@ -41,6 +42,7 @@ pub(crate) struct Minimizer<'a, S: 'a> {
     in_transitions: Vec<Vec<Vec<S>>>,
     partitions: Vec<StateSet<S>>,
     waiting: Vec<StateSet<S>>,
+    deadline: Option<Deadline>,
 }
 
 impl<'a, S: StateID> fmt::Debug for Minimizer<'a, S> {
@@ -50,6 +52,7 @@ impl<'a, S: StateID> fmt::Debug for Minimizer<'a, S> {
             .field("in_transitions", &self.in_transitions)
             .field("partitions", &self.partitions)
             .field("waiting", &self.waiting)
+            .field("deadline", &self.deadline)
             .finish()
     }
 }
@@ -75,16 +78,30 @@ impl<'a, S: StateID> Minimizer<'a, S> {
         let partitions = Minimizer::initial_partitions(dfa);
         let waiting = vec![partitions[0].clone()];
 
-        Minimizer { dfa, in_transitions, partitions, waiting }
+        Minimizer { dfa, in_transitions, partitions, waiting, deadline: None }
     }
 
-    pub fn run(mut self) {
+    /// Stop minimizing with an error once the given deadline passes. The DFA
+    /// is only modified after the minimal partitioning of its states has
+    /// been found, so it is left unchanged when this happens.
+    pub fn deadline(
+        mut self,
+        deadline: Option<Deadline>,
+    ) -> Minimizer<'a, S> {
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn run(mut self) -> Result<()> {
         let mut incoming = StateSet::empty();
         let mut scratch1 = StateSet::empty();
         let mut scratch2 = StateSet::empty();
         let mut newparts = vec![];
 
         while let Some(set) = self.waiting.pop() {
+            if let Some(ref deadline) = self.deadline {
+                deadline.check()?;
+            }
             for b in (0..self.dfa.alphabet_len()).map(|b| b as u8) {
                 self.find_incoming_to(b, &set, &mut incoming);
 
@@ -194,6 +211,7 @@ impl<'a, S: StateID> Minimizer<'a, S> {
                 self.dfa.set_max_match_state(new_id);
             }
         }
+        Ok(())
     }
 
     fn find_waiting(&self, set: &StateSet<S>) -> Option<usize> {
@@ -1,6 +1,58 @@
+/*!
+The Thompson NFA from which every DFA in this crate is determinized.
+
+An [`NFA`](struct.NFA.html) is a list of [`State`](enum.State.html)s indexed
+by [`StateID`](type.StateID.html). Every state either consumes a single byte
+in a range, has epsilon transitions to a prioritized list of other states, or
+is the sole match state. Since this crate does not support anchors or word
+boundaries, there are no look-around states: a pattern that uses them fails
+to compile.
+
+NFAs are useful for analyses of a pattern that are awkward to express over a
+DFA, such as reachability or literal extraction, and for building several
+DFAs with different configurations without parsing a pattern again. An NFA
+can be compiled from a pattern with
+[`dense::Builder::build_nfa`](../dense/struct.Builder.html#method.build_nfa),
+which respects the parser options of the builder, or from a
+`regex_syntax::hir::Hir` with [`NFABuilder`](struct.NFABuilder.html). It
+can then be determinized with
+[`dense::Builder::build_from_nfa`](../dense/struct.Builder.html#method.build_from_nfa).
+
+This module is only available when the `std` feature is enabled.
+
+# Example
+
+This counts the states that consume a byte, and then builds two DFAs from
+the same NFA.
+
+```
+use regex_automata::{DFA, dense};
+use regex_automata::nfa::State;
+
+# fn example() -> Result<(), regex_automata::Error> {
+let nfa = dense::Builder::new().anchored(true).build_nfa("foo[0-9]+")?;
+let ranges = (0..nfa.len())
+    .filter(|&id| match *nfa.state(id) {
+        State::Range { .. } => true,
+        _ => false,
+    })
+    .count();
+assert!(ranges >= 4);
+
+let dfa = dense::Builder::new().build_from_nfa(&nfa)?;
+let min = dense::Builder::new().minimize(true).build_from_nfa(&nfa)?;
+assert_eq!(Some(5), dfa.find(b"foo12"));
+assert_eq!(Some(5), min.find(b"foo12"));
+# Ok(()) }; example().unwrap()
+```
+*/
+
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
 use std::iter;
+use std::mem;
+use std::str;
 
 use regex_syntax::hir::{self, Hir, HirKind};
 
@@ -23,6 +75,12 @@ pub struct NFA {
     anchored: bool,
     /// The starting state of this NFA.
     start: StateID,
+    /// The number of states that make up the unanchored prefix of this NFA.
+    ///
+    /// The unanchored prefix is always compiled first, so its states are
+    /// precisely the states with an ID less than this. This is `0` for
+    /// anchored NFAs.
+    prefix_len: StateID,
     /// The state list. This list is guaranteed to be indexable by the starting
     /// state ID, and it is also guaranteed to contain exactly one `Match`
     /// state.
@@ -43,11 +101,21 @@ pub struct NFA {
 pub enum State {
     /// A state that transitions to `next` if and only if the current input
     /// byte is in the range `[start, end]` (inclusive).
-    Range { start: u8, end: u8, next: StateID },
+    Range {
+        /// The first byte in the range.
+        start: u8,
+        /// The last byte in the range.
+        end: u8,
+        /// The state to transition to.
+        next: StateID,
+    },
     /// An alternation such that there exists an epsilon transition to all
     /// states in `alternates`, where matches found via earlier transitions
     /// are preferred over later transitions.
-    Union { alternates: Vec<StateID> },
+    Union {
+        /// The states to transition to, in order of preference.
+        alternates: Vec<StateID>,
+    },
     /// A match state. There is exactly one such occurrence of this state in
     /// an NFA.
     Match,
@@ -64,12 +132,28 @@ impl NFA {
         self.states.len()
     }
 
+    /// Returns true if and only if this NFA has no states. Since every NFA
+    /// has a match state, this always returns false.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
     /// Return the ID of the initial state of this NFA.
     pub fn start(&self) -> StateID {
         self.start
     }
 
+    /// Returns true if and only if the given state belongs to the unanchored
+    /// prefix of this NFA, which permits a match to begin at any position.
+    pub fn is_unanchored_prefix(&self, id: StateID) -> bool {
+        id < self.prefix_len
+    }
+
     /// Return the NFA state corresponding to the given ID.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the given ID is not less than `len()`.
     pub fn state(&self, id: StateID) -> &State {
         &self.states[id]
     }
@@ -77,7 +161,7 @@ impl NFA {
     /// Return the set of equivalence classes for this NFA. The slice returned
     /// always has length 256 and maps each possible byte value to its
     /// corresponding equivalence class ID (which is never more than 255).
-    pub fn byte_classes(&self) -> &ByteClasses {
+    pub(crate) fn byte_classes(&self) -> &ByteClasses {
         &self.byte_classes
     }
 }
@@ -111,12 +195,17 @@ impl State {
     }
 }
 
-/// A builder for compiling an NFA.
+/// A builder for compiling an NFA from the high level intermediate
+/// representation of a regular expression.
+///
+/// To compile an NFA from a pattern string, use
+/// [`dense::Builder::build_nfa`](../dense/struct.Builder.html#method.build_nfa).
 #[derive(Clone, Debug)]
 pub struct NFABuilder {
     anchored: bool,
     allow_invalid_utf8: bool,
     reverse: bool,
+    size_limit: Option<usize>,
 }
 
 impl NFABuilder {
@@ -126,6 +215,7 @@ impl NFABuilder {
             anchored: false,
             allow_invalid_utf8: false,
             reverse: false,
+            size_limit: None,
         }
     }
 
@@ -142,6 +232,7 @@ impl NFABuilder {
         let compiler = NFACompiler {
             states: RefCell::new(vec![]),
             reverse: self.reverse,
+            size_limit: self.size_limit,
         };
 
         let mut start = compiler.add_empty();
@@ -155,11 +246,129 @@ impl NFABuilder {
             compiler.patch(start, compiled.start);
             start = compiled.end;
         }
+        let prefix_end = compiler.states.borrow().len();
         let compiled = compiler.compile(&expr)?;
         let match_id = compiler.add_match();
         compiler.patch(start, compiled.start);
         compiler.patch(compiled.end, match_id);
-        Ok(NFA { anchored: self.anchored, ..compiler.to_nfa() })
+        compiler.check_size_limit()?;
+        Ok(NFA { anchored: self.anchored, ..compiler.to_nfa(prefix_end) })
+    }
+
+    /// Compile an NFA that matches any of the given literals.
+    ///
+    /// Each literal that is valid UTF-8 is matched as a sequence of
+    /// codepoints, and every other literal as a sequence of bytes. When
+    /// `case_insensitive` is enabled, each codepoint also matches its simple
+    /// case foldings, or only the other case of an ASCII letter when the
+    /// `unicode-case` feature is disabled. Bytes that are not ASCII are
+    /// always matched exactly.
+    ///
+    /// Earlier literals are preferred over later literals, as in an
+    /// alternation. If there are no literals, then the NFA never matches.
+    pub fn build_literals<I, P>(
+        &self,
+        literals: I,
+        case_insensitive: bool,
+    ) -> Result<NFA>
+    where I: IntoIterator<Item=P>, P: AsRef<[u8]>
+    {
+        let alternates: Vec<Hir> = literals
+            .into_iter()
+            .map(|lit| literal_hir(lit.as_ref(), case_insensitive))
+            .collect();
+        if alternates.is_empty() {
+            let fail = hir::Class::Bytes(hir::ClassBytes::empty());
+            return self.build(Hir::class(fail));
+        }
+        self.build(Hir::alternation(alternates))
+    }
+
+    /// Compile an NFA that matches every string whose Levenshtein distance
+    /// from `query` is at most `distance`.
+    ///
+    /// The distance is measured in Unicode codepoints, unless invalid UTF-8
+    /// is permitted, in which case insertions and substitutions may match
+    /// any single byte.
+    ///
+    /// The NFA has one state for every prefix of the query combined with
+    /// every number of edits used so far. Among the ways of matching the same
+    /// input, an exact match of the next query codepoint is preferred,
+    /// followed by a deletion, a substitution and finally an insertion. This
+    /// makes leftmost-first searches avoid absorbing surrounding input into
+    /// a match when they don't need to.
+    pub fn build_levenshtein(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<NFA> {
+        let compiler = NFACompiler {
+            states: RefCell::new(vec![]),
+            reverse: self.reverse,
+            size_limit: self.size_limit,
+        };
+        let compile = |expr: Hir| {
+            if self.reverse {
+                compiler.compile(&reverse_hir(expr))
+            } else {
+                compiler.compile(&expr)
+            }
+        };
+
+        let mut start = compiler.add_empty();
+        if !self.anchored {
+            let compiled =
+                if self.allow_invalid_utf8 {
+                    compiler.compile_unanchored_prefix_invalid_utf8()
+                } else {
+                    compiler.compile_unanchored_prefix_valid_utf8()
+                }?;
+            compiler.patch(start, compiled.start);
+            start = compiled.end;
+        }
+        let prefix_end = compiler.states.borrow().len();
+
+        let mut chars: Vec<char> = query.chars().collect();
+        if self.reverse {
+            chars.reverse();
+        }
+        let edits = distance as usize;
+        // nodes[i][e] is the state reached after matching the first `i`
+        // codepoints of the query using exactly `e` edits.
+        let nodes: Vec<Vec<StateID>> = (0..chars.len() + 1)
+            .map(|_| (0..edits + 1).map(|_| compiler.add_union()).collect())
+            .collect();
+        let match_id = compiler.add_match();
+        compiler.patch(start, nodes[0][0]);
+        for i in 0..chars.len() + 1 {
+            for e in 0..edits + 1 {
+                let node = nodes[i][e];
+                if i < chars.len() {
+                    let lit = hir::Literal::Unicode(chars[i]);
+                    let exact = compile(Hir::literal(lit))?;
+                    compiler.patch(node, exact.start);
+                    compiler.patch(exact.end, nodes[i + 1][e]);
+                } else {
+                    compiler.patch(node, match_id);
+                }
+                if e == edits {
+                    continue;
+                }
+                if i < chars.len() {
+                    // Deleting a codepoint from the query consumes no input.
+                    compiler.patch(node, nodes[i + 1][e + 1]);
+
+                    let any = compile(Hir::any(self.allow_invalid_utf8))?;
+                    compiler.patch(node, any.start);
+                    compiler.patch(any.end, nodes[i + 1][e + 1]);
+                }
+                let any = compile(Hir::any(self.allow_invalid_utf8))?;
+                compiler.patch(node, any.start);
+                compiler.patch(any.end, nodes[i][e + 1]);
+            }
+        }
+        compiler.check_size_limit()?;
+        Ok(NFA { anchored: self.anchored, ..compiler.to_nfa(prefix_end) })
     }
 
     /// Set whether matching must be anchored at the beginning of the input.
@@ -198,6 +407,22 @@ impl NFABuilder {
         self.reverse = yes;
         self
     }
+
+    /// Set a limit, in bytes, on the approximate size of the NFA.
+    ///
+    /// When set, compilation stops with an error as soon as the NFA's states
+    /// use more than this many bytes, not including the heap memory used by
+    /// the alternates of each union.
+    pub fn size_limit(&mut self, limit: Option<usize>) -> &mut NFABuilder {
+        self.size_limit = limit;
+        self
+    }
+}
+
+impl Default for NFABuilder {
+    fn default() -> NFABuilder {
+        NFABuilder::new()
+    }
 }
 
 /// A compiler that converts a regex AST (well, a high-level IR) to an NFA via
@@ -224,6 +449,8 @@ struct NFACompiler {
     /// does need to know to reverse UTF-8 automata since the HIR is expressed
     /// in terms of Unicode codepoints.
     reverse: bool,
+    /// The maximum size, in bytes, of the states compiled so far.
+    size_limit: Option<usize>,
 }
 
 /// A "builder" intermediate state representation for an NFA that is only used
@@ -267,8 +494,11 @@ struct ThompsonRef {
 }
 
 impl NFACompiler {
-    /// Convert the current intermediate NFA to its final compiled form.
-    fn to_nfa(&self) -> NFA {
+    /// Convert the intermediate states compiled so far to a final NFA.
+    ///
+    /// Every intermediate state before `prefix_end` must belong to the
+    /// unanchored prefix.
+    fn to_nfa(&self, prefix_end: usize) -> NFA {
         let bstates = self.states.borrow();
         let mut states = vec![];
         let mut remap = vec![0; bstates.len()];
@@ -280,7 +510,11 @@ impl NFACompiler {
         // transitions, which are expressed in terms of state IDs. The new
         // set of states will be smaller because of partial epsilon removal,
         // so the state IDs will not be the same.
+        let mut prefix_len = 0;
         for (id, bstate) in bstates.iter().enumerate() {
+            if id == prefix_end {
+                prefix_len = states.len();
+            }
             match *bstate {
                 BState::Empty { mut next } => {
                     // Since we're removing empty states, we need to handle
@@ -327,10 +561,20 @@ impl NFACompiler {
         }
         // The compiler always begins the NFA at the first state.
         let byte_classes = byteset.byte_classes();
-        NFA { anchored: false, start: remap[0], states, byte_classes }
+        NFA {
+            anchored: false,
+            start: remap[0],
+            prefix_len,
+            states,
+            byte_classes,
+        }
     }
 
     fn compile(&self, expr: &Hir) -> Result<ThompsonRef> {
+        // This is checked for every sub-expression, and in particular for
+        // every copy of a repeated sub-expression, so that compilation stops
+        // before an oversized NFA uses much more memory than the limit.
+        self.check_size_limit()?;
         match *expr.kind() {
             HirKind::Empty => {
                 let id = self.add_empty();
@@ -349,6 +593,9 @@ impl NFACompiler {
                 Ok(self.compile_range(b, b))
             }
             HirKind::Class(hir::Class::Bytes(ref cls)) => {
+                if cls.ranges().is_empty() {
+                    return Ok(self.compile_fail());
+                }
                 let it = cls
                     .iter()
                     .map(|rng| Ok(self.compile_range(rng.start(), rng.end())));
@@ -367,6 +614,9 @@ impl NFACompiler {
                 self.compile_concat(exprs.iter().map(|e| self.compile(e)))
             }
             HirKind::Alternation(ref exprs) => {
+                if let Some(literals) = alternation_literals(exprs) {
+                    return Ok(self.compile_literal_trie(&literals));
+                }
                 self.compile_alternation(exprs.iter().map(|e| self.compile(e)))
             }
             HirKind::Anchor(_) => {
@@ -396,6 +646,13 @@ impl NFACompiler {
         Ok(ThompsonRef { start, end })
     }
 
+    /// Compile a sub-expression that never matches, such as an empty class.
+    fn compile_fail(&self) -> ThompsonRef {
+        // A union without alternates has no transitions. Its end must be a
+        // separate state since patching a union adds an alternate to it.
+        ThompsonRef { start: self.add_union(), end: self.add_empty() }
+    }
+
     fn compile_alternation<I>(
         &self,
         it: I,
@@ -546,6 +803,9 @@ impl NFACompiler {
     ) -> Result<ThompsonRef> {
         use utf8_ranges::Utf8Sequences;
 
+        if cls.ranges().is_empty() {
+            return Ok(self.compile_fail());
+        }
         let it = cls
             .iter()
             .flat_map(|rng| Utf8Sequences::new(rng.start(), rng.end()))
@@ -572,6 +832,58 @@ impl NFACompiler {
         self.compile_alternation(it)
     }
 
+    /// Compile an alternation of literals by sharing their common prefixes
+    /// and suffixes.
+    ///
+    /// Compiling each literal separately results in an NFA whose size is
+    /// proportional to the total length of all literals, and since every
+    /// literal begins in the same place, determinization must then track all
+    /// of them at once. For large keyword sets, this makes determinization
+    /// very slow. A trie keeps the number of NFA states active at any given
+    /// position small. Sharing equivalent suffixes of the trie, such as file
+    /// extensions or domain names, further avoids copying those suffixes
+    /// into every DFA state that can reach them.
+    fn compile_literal_trie(&self, literals: &[Vec<u8>]) -> ThompsonRef {
+        let mut trie = LiteralTrie::new();
+        for lit in literals {
+            trie.add(lit);
+        }
+
+        let canonical = trie.share_suffixes();
+
+        let end = self.add_empty();
+        let mut ids = vec![0; trie.states.len()];
+        for (id, state) in trie.states.iter().enumerate() {
+            if canonical[id] == id {
+                ids[id] =
+                    if state.len() == 1 {
+                        self.add_empty()
+                    } else {
+                        self.add_union()
+                    };
+            }
+        }
+        for id in 0..ids.len() {
+            ids[id] = ids[canonical[id]];
+        }
+        for (id, state) in trie.states.iter().enumerate() {
+            if canonical[id] != id {
+                continue;
+            }
+            for (i, chunk) in state.chunks.iter().enumerate() {
+                if i > 0 {
+                    self.patch(ids[id], end);
+                }
+                for &(byte, next) in chunk {
+                    let range = self.add_range(byte, byte);
+                    self.patch(ids[id], range);
+                    self.patch(range, ids[next]);
+                }
+            }
+        }
+        ThompsonRef { start: ids[0], end }
+    }
+
     fn compile_range(&self, start: u8, end: u8) -> ThompsonRef {
         let id = self.add_range(start, end);
         ThompsonRef { start: id, end: id }
@@ -616,6 +928,17 @@ impl NFACompiler {
         }
     }
 
+    /// Return an error if the states compiled so far exceed the size limit.
+    fn check_size_limit(&self) -> Result<()> {
+        if let Some(limit) = self.size_limit {
+            let size = self.states.borrow().len() * mem::size_of::<BState>();
+            if size > limit {
+                return Err(Error::nfa_exceeded_size_limit(limit));
+            }
+        }
+        Ok(())
+    }
+
     fn add_empty(&self) -> StateID {
         let id = self.states.borrow().len();
         self.states.borrow_mut().push(BState::Empty { next: 0 });
@@ -650,6 +973,150 @@ impl NFACompiler {
     }
 }
 
+/// A trie of literals that preserves leftmost-first match priority.
+///
+/// Each state's transitions are split into chunks, where consecutive chunks
+/// are separated by a match of a literal that ends at that state. Literals
+/// are added in priority order, and a literal may only share transitions in
+/// the last chunk of each state it passes through. This guarantees that a
+/// literal that ends at a state is preferred over every literal added after
+/// it that continues past that state, while still being less preferred than
+/// the literals added before it that continue past that state.
+///
+/// Under leftmost-first semantics, a literal that passes through a match can
+/// never match, since the earlier literal is a prefix of it. Such literals
+/// are still kept, since they matter when the NFA is determinized with
+/// leftmost-longest semantics, as is done for reverse searches.
+#[derive(Debug)]
+struct LiteralTrie {
+    states: Vec<LiteralTrieState>,
+}
+
+#[derive(Debug)]
+struct LiteralTrieState {
+    /// Transitions on single bytes to other trie states. Every adjacent pair
+    /// of chunks is separated by a match. The transitions within any one
+    /// chunk are always on distinct bytes.
+    chunks: Vec<Vec<(u8, usize)>>,
+}
+
+impl LiteralTrie {
+    fn new() -> LiteralTrie {
+        LiteralTrie { states: vec![LiteralTrieState::new()] }
+    }
+
+    fn add(&mut self, literal: &[u8]) {
+        let mut id = 0;
+        for &byte in literal {
+            let found = self.states[id]
+                .last_chunk()
+                .iter()
+                .find(|&&(b, _)| b == byte)
+                .map(|&(_, next)| next);
+            id = match found {
+                Some(next) => next,
+                None => {
+                    let next = self.states.len();
+                    self.states.push(LiteralTrieState::new());
+                    self.states[id].last_chunk_mut().push((byte, next));
+                    next
+                }
+            };
+        }
+        // If the last thing in this state is already a match, then this
+        // literal is a duplicate of an earlier literal.
+        let state = &mut self.states[id];
+        if state.chunks.len() == 1 || !state.last_chunk().is_empty() {
+            state.chunks.push(vec![]);
+        }
+    }
+
+    /// Returns, for each state in this trie, the ID of an equivalent state
+    /// that should be used in its place.
+    ///
+    /// Two states are equivalent when they have the same chunks of
+    /// transitions, up to the order of transitions within each chunk, and
+    /// when the targets of their transitions are themselves equivalent. Since
+    /// both states then match the same literals with the same priorities,
+    /// only one of them needs to be compiled.
+    fn share_suffixes(&self) -> Vec<usize> {
+        let mut canonical: Vec<usize> = (0..self.states.len()).collect();
+        let mut seen: HashMap<Vec<Vec<(u8, usize)>>, usize> = HashMap::new();
+        // The ID of a state is always greater than the ID of its parent, so
+        // visiting states in reverse guarantees that all of a state's
+        // children have been visited before it.
+        for id in (0..self.states.len()).rev() {
+            let key: Vec<Vec<(u8, usize)>> = self.states[id]
+                .chunks
+                .iter()
+                .map(|chunk| {
+                    let mut chunk: Vec<(u8, usize)> = chunk
+                        .iter()
+                        .map(|&(byte, next)| (byte, canonical[next]))
+                        .collect();
+                    chunk.sort();
+                    chunk
+                })
+                .collect();
+            canonical[id] = *seen.entry(key).or_insert(id);
+        }
+        canonical
+    }
+}
+
+impl LiteralTrieState {
+    fn new() -> LiteralTrieState {
+        LiteralTrieState { chunks: vec![vec![]] }
+    }
+
+    fn last_chunk(&self) -> &[(u8, usize)] {
+        self.chunks.last().unwrap()
+    }
+
+    fn last_chunk_mut(&mut self) -> &mut Vec<(u8, usize)> {
+        self.chunks.last_mut().unwrap()
+    }
+
+    /// Returns the total number of transitions and matches in this state.
+    fn len(&self) -> usize {
+        let transitions: usize = self.chunks.iter().map(|c| c.len()).sum();
+        transitions + self.chunks.len() - 1
+    }
+}
+
+/// If every expression in the given alternation is a literal string, then
+/// return the bytes of each such string, in order.
+fn alternation_literals(exprs: &[Hir]) -> Option<Vec<Vec<u8>>> {
+    fn push_literal(expr: &Hir, buf: &mut Vec<u8>) -> bool {
+        match *expr.kind() {
+            HirKind::Empty => true,
+            HirKind::Literal(hir::Literal::Unicode(ch)) => {
+                let mut tmp = [0; 4];
+                buf.extend_from_slice(ch.encode_utf8(&mut tmp).as_bytes());
+                true
+            }
+            HirKind::Literal(hir::Literal::Byte(b)) => {
+                buf.push(b);
+                true
+            }
+            HirKind::Concat(ref exprs) => {
+                exprs.iter().all(|e| push_literal(e, buf))
+            }
+            _ => false,
+        }
+    }
+
+    let mut literals = vec![];
+    for expr in exprs {
+        let mut buf = vec![];
+        if !push_literal(expr, &mut buf) {
+            return None;
+        }
+        literals.push(buf);
+    }
+    Some(literals)
+}
+
 /// A byte class set keeps track of an *approximation* of equivalence classes
 /// of bytes during NFA construction. That is, every byte in an equivalence
 /// class cannot discriminate between a match and a non-match.
@@ -728,6 +1195,46 @@ impl fmt::Debug for NFA {
     }
 }
 
+/// Return an HIR expression that matches the given literal.
+///
+/// See `NFABuilder::build_literals` for how literals are interpreted.
+fn literal_hir(lit: &[u8], case_insensitive: bool) -> Hir {
+    let exprs = match str::from_utf8(lit) {
+        Ok(lit) => {
+            lit.chars().map(|c| char_hir(c, case_insensitive)).collect()
+        }
+        Err(_) => {
+            lit.iter()
+                .map(|&b| {
+                    if b <= 0x7F {
+                        char_hir(b as char, case_insensitive)
+                    } else {
+                        Hir::literal(hir::Literal::Byte(b))
+                    }
+                })
+                .collect()
+        }
+    };
+    Hir::concat(exprs)
+}
+
+/// Return an HIR expression that matches the given codepoint, along with its
+/// simple case foldings if `case_insensitive` is enabled.
+fn char_hir(c: char, case_insensitive: bool) -> Hir {
+    if !case_insensitive {
+        return Hir::literal(hir::Literal::Unicode(c));
+    }
+    let mut cls =
+        hir::ClassUnicode::new(vec![hir::ClassUnicodeRange::new(c, c)]);
+    if cls.try_case_fold_simple().is_err() {
+        // Without Unicode case tables, only ASCII letters are folded.
+        for &c in &[c.to_ascii_lowercase(), c.to_ascii_uppercase()] {
+            cls.push(hir::ClassUnicodeRange::new(c, c));
+        }
+    }
+    Hir::class(hir::Class::Unicode(cls))
+}
+
 /// Reverse the given HIR expression.
 fn reverse_hir(expr: Hir) -> Hir {
     match expr.into_kind() {
@@ -965,10 +1472,46 @@ mod tests {
 
     #[test]
     fn compile_alternation() {
+        assert_eq!(build(r"a|b+").states, &[
+            s_byte(b'a', 4),
+            s_byte(b'b', 2),
+            s_union(&[1, 4]),
+            s_union(&[0, 1]),
+            s_match(),
+        ]);
+    }
+
+    #[test]
+    fn compile_literal_alternation() {
         assert_eq!(build(r"a|b").states, &[
+            s_union(&[1, 2]),
             s_byte(b'a', 3),
             s_byte(b'b', 3),
-            s_union(&[0, 1]),
+            s_match(),
+        ]);
+        // `ab` must be preferred over `a`.
+        assert_eq!(build(r"ab|a").states, &[
+            s_union(&[2, 3]),
+            s_byte(b'a', 0),
+            s_byte(b'b', 3),
+            s_match(),
+        ]);
+        // `a` must be preferred over `ac`, but not over `ab`.
+        assert_eq!(build(r"ab|a|ac").states, &[
+            s_union(&[2, 4, 3]),
+            s_byte(b'a', 0),
+            s_byte(b'b', 4),
+            s_byte(b'c', 4),
+            s_match(),
+        ]);
+        // Duplicate literals are redundant.
+        assert_eq!(build(r"ab|ab").states, build(r"ab").states);
+        // Common suffixes are shared.
+        assert_eq!(build(r"xa|ya").states, &[
+            s_union(&[1, 2]),
+            s_byte(b'x', 3),
+            s_byte(b'y', 3),
+            s_byte(b'a', 4),
             s_match(),
         ]);
     }
@@ -1,11 +1,25 @@
 #[cfg(feature = "std")]
-use dense::{self, DenseDFA};
-use dfa::DFA;
+use std::cmp::Reverse;
 #[cfg(feature = "std")]
-use error::Result;
+use std::collections::BinaryHeap;
 #[cfg(feature = "std")]
-use sparse::SparseDFA;
+use std::panic;
+#[cfg(feature = "std")]
+use std::thread;
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+#[cfg(feature = "std")]
+use regex_syntax::hir::Hir;
+
+#[cfg(feature = "std")]
+use dense::{self, MatchKind};
+use dense::DenseDFA;
+use dfa::{Chunks, DFA};
 #[cfg(feature = "std")]
+use error::Result;
+use memory::MemoryUsage;
+use sparse::SparseDFA;
 use state_id::StateID;
 
 /// A regular expression that uses deterministic finite automata for fast
@@ -288,15 +302,11 @@ impl<D: DFA> Regex<D> {
         input: &[u8],
         start: usize,
     ) -> Option<(usize, usize)> {
-        let end = match self.forward().find_at(input, start) {
-            None => return None,
-            Some(end) => end,
-        };
-        let start = self
-            .reverse()
-            .rfind(&input[start..end])
-            .map(|i| start + i)
-            .expect("reverse search must match if forward search does");
+        let end = self.forward().find_at(input, start)?;
+        // The reverse search always matches when the regex was built by this
+        // crate, but a mismatched pair given to `from_dfas` may not. Report
+        // no match rather than panicking in that case.
+        let start = self.reverse().rfind(&input[start..end])? + start;
         Some((start, end))
     }
 
@@ -330,12 +340,298 @@ impl<D: DFA> Regex<D> {
         Matches::new(self, input)
     }
 
+    /// Returns an iterator over the end offsets of non-overlapping matches
+    /// in the given bytes, using the same semantics as `shortest_match`.
+    ///
+    /// Each search stops as soon as any match is found, and the next search
+    /// begins where the previous match ended. This is faster than `find_iter`
+    /// since neither the leftmost first end nor the start of each match is
+    /// computed. The trade off is that the offsets reported only
+    /// approximately locate each match, and a single leftmost first match may
+    /// be reported as several shorter matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// let text = b"foo1 foo12 foo123";
+    /// let ends: Vec<usize> = re.shortest_match_iter(text).collect();
+    /// assert_eq!(ends, vec![4, 9, 15]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn shortest_match_iter<'r, 't>(
+        &'r self,
+        input: &'t [u8],
+    ) -> ShortestMatches<'r, 't, D> {
+        ShortestMatches::new(self, input)
+    }
+
+    /// Returns an iterator over the spans of the given bytes that lie between
+    /// all non-overlapping leftmost first matches.
+    ///
+    /// This is akin to splitting the given bytes by this regex, except that
+    /// the offsets of each piece are returned instead of the pieces
+    /// themselves. In particular, if `N` matches are found, then this always
+    /// yields exactly `N + 1` spans, some of which may be empty (for example,
+    /// when the bytes begin with a match or when two matches are adjacent).
+    ///
+    /// The matches themselves are determined in precisely the same way as
+    /// by [`find_iter`](struct.Regex.html#method.find_iter).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("[0-9]+")?;
+    /// let text = b"a1bc22";
+    /// let gaps: Vec<(usize, usize)> = re.gap_iter(text).collect();
+    /// assert_eq!(gaps, vec![(0, 1), (2, 4), (6, 6)]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn gap_iter<'r, 't>(
+        &'r self,
+        input: &'t [u8],
+    ) -> Gaps<'r, 't, D> {
+        Gaps::new(self, input)
+    }
+
+    /// Returns an iterator that partitions the given bytes into matched and
+    /// unmatched spans, in the order in which they appear.
+    ///
+    /// Each span yielded is tagged as being either a match or a gap between
+    /// matches. Taken together, the spans yielded cover all of the given
+    /// bytes without overlapping. Empty gaps are never yielded, but empty
+    /// matches are.
+    ///
+    /// The matches themselves are determined in precisely the same way as
+    /// by [`find_iter`](struct.Regex.html#method.find_iter).
+    ///
+    /// # Example
+    ///
+    /// This example shows how one might render matched and unmatched regions
+    /// of some text differently in a single pass.
+    ///
+    /// ```
+    /// use regex_automata::{Regex, Span};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("[0-9]+")?;
+    /// let text = b"a1bc22";
+    ///
+    /// let mut rendered = vec![];
+    /// for span in re.span_iter(text) {
+    ///     let piece = &text[span.start()..span.end()];
+    ///     if span.is_match() {
+    ///         rendered.push(b'[');
+    ///         rendered.extend_from_slice(piece);
+    ///         rendered.push(b']');
+    ///     } else {
+    ///         rendered.extend_from_slice(piece);
+    ///     }
+    /// }
+    /// assert_eq!(rendered, b"a[1]bc[22]");
+    ///
+    /// let spans: Vec<Span> = re.span_iter(text).collect();
+    /// assert_eq!(spans, vec![
+    ///     Span::Gap(0, 1),
+    ///     Span::Match(1, 2),
+    ///     Span::Gap(2, 4),
+    ///     Span::Match(4, 6),
+    /// ]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn span_iter<'r, 't>(
+        &'r self,
+        input: &'t [u8],
+    ) -> Spans<'r, 't, D> {
+        Spans::new(self, input)
+    }
+
+    /// Returns an iterator over every match in the given bytes, including
+    /// matches that overlap.
+    ///
+    /// One match is reported for every position at which the forward DFA of
+    /// this regex enters a match state, in ascending order of end offset.
+    /// The start of each match is the earliest position from which a match
+    /// ending at that offset begins.
+    ///
+    /// A forward DFA built with the default leftmost first semantics prunes
+    /// every match that is lower priority than a match already found, which
+    /// hides most overlapping matches. To find all of them, the forward DFA
+    /// should be built with
+    /// [`MatchKind::All`](dense/enum.MatchKind.html#variant.All).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    /// use regex_automata::dense::{self, MatchKind};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let pattern = "[a-z]+ing|sing";
+    /// let fwd = dense::Builder::new()
+    ///     .match_kind(MatchKind::All)
+    ///     .build(pattern)?;
+    /// let rev = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .reverse(true)
+    ///     .longest_match(true)
+    ///     .build(pattern)?;
+    /// let re = Regex::from_dfas(fwd, rev);
+    ///
+    /// let text = b"singing";
+    /// let matches: Vec<(usize, usize)> =
+    ///     re.find_overlapping_iter(text).collect();
+    /// assert_eq!(matches, vec![(0, 4), (0, 7)]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn find_overlapping_iter<'r, 't>(
+        &'r self,
+        input: &'t [u8],
+    ) -> OverlappingMatches<'r, 't, D> {
+        OverlappingMatches::new(self, input)
+    }
+
+    /// Returns the start and end offset of the leftmost first match in a
+    /// haystack that is split into two slices, such as the contents of a ring
+    /// buffer.
+    ///
+    /// The haystack searched is `head` followed by `tail`, and the offsets
+    /// returned are relative to the start of `head`. Matches may span the
+    /// boundary between the two slices. This avoids copying both slices into
+    /// one contiguous buffer before searching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// assert_eq!(Some((3, 8)), re.find_ring(b"zzzfo", b"o12zzz"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn find_ring(
+        &self,
+        head: &[u8],
+        tail: &[u8],
+    ) -> Option<(usize, usize)> {
+        self.find_ring_at(head, tail, 0)
+    }
+
+    /// Returns the same as `find_ring`, but starts the search at the given
+    /// offset, relative to the start of `head`.
+    ///
+    /// The significance of the starting point is that it takes the surrounding
+    /// context into consideration. For example, if the DFA is anchored, then
+    /// a match can only occur when `start == 0`.
+    pub fn find_ring_at(
+        &self,
+        head: &[u8],
+        tail: &[u8],
+        start: usize,
+    ) -> Option<(usize, usize)> {
+        let end = ring_find_at(self.forward(), head, tail, start)?;
+        let start = ring_rfind(self.reverse(), head, tail, start, end)?;
+        Some((start, end))
+    }
+
+    /// Returns an iterator over all non-overlapping leftmost first matches
+    /// in a haystack that is split into two slices, such as the contents of a
+    /// ring buffer.
+    ///
+    /// See [`find_ring`](struct.Regex.html#method.find_ring) for how the
+    /// haystack and the offsets reported are defined. Otherwise, this behaves
+    /// exactly like `find_iter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// let matches: Vec<(usize, usize)> =
+    ///     re.find_ring_iter(b"foo1 fo", b"o12 foo123").collect();
+    /// assert_eq!(matches, vec![(0, 4), (5, 10), (11, 17)]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn find_ring_iter<'r, 't>(
+        &'r self,
+        head: &'t [u8],
+        tail: &'t [u8],
+    ) -> RingMatches<'r, 't, D> {
+        RingMatches::new(self, head, tail)
+    }
+
+    /// Returns true if and only if the input yielded by the given chunks
+    /// matches this regex.
+    ///
+    /// The chunks are searched as if they were concatenated into one
+    /// contiguous slice, such as the leaves of a rope or a queue of network
+    /// buffers, without copying them. See
+    /// [`Chunks`](trait.Chunks.html) for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+bar")?;
+    /// let chunks: &[&[u8]] = &[b"xfo", b"o12", b"3bar"];
+    /// assert!(re.is_match_chunks(chunks.iter()));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn is_match_chunks<C: Chunks>(&self, chunks: C) -> bool {
+        self.forward().is_match_chunks(chunks)
+    }
+
+    /// Returns the first position at which a match is found in the input
+    /// yielded by the given chunks, using the same semantics as
+    /// `shortest_match`.
+    ///
+    /// The offset returned is relative to the start of the first chunk and
+    /// is reported as a `u64`, so that it cannot overflow when searching
+    /// more input than fits in the address space.
+    ///
+    /// There is no chunked equivalent of `find`. Finding the start of a
+    /// match requires searching backwards from its end, but a `Chunks`
+    /// cursor only moves forward and may reuse its buffer, so the input
+    /// before the end of a match may no longer be available.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// let chunks: &[&[u8]] = &[b"xxf", b"o", b"o12", b"3yy"];
+    /// assert_eq!(Some(6), re.shortest_match_chunks(chunks.iter()));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn shortest_match_chunks<C: Chunks>(&self, chunks: C) -> Option<u64> {
+        self.forward().shortest_match_chunks(chunks)
+    }
+
     /// Build a new regex from its constituent forward and reverse DFAs.
     ///
     /// This is useful when deserializing a regex from some arbitrary
     /// memory region. This is also useful for building regexes from other
     /// types of DFAs.
     ///
+    /// The reverse DFA should be an anchored reverse DFA built from the same
+    /// pattern as the forward DFA. If the two DFAs do not agree, then searches
+    /// may report incorrect matches or miss matches, but they never panic. In
+    /// particular, a match found by the forward DFA for which the reverse DFA
+    /// finds no starting position is reported as no match.
+    ///
     /// # Example
     ///
     /// This example is a bit a contrived. The usual use of these methods
@@ -403,6 +699,115 @@ impl<D: DFA> Regex<D> {
     }
 }
 
+impl<T: AsRef<[S]>, S: StateID> Regex<DenseDFA<T, S>> {
+    /// Returns the memory usage, in bytes, of the transition tables of both
+    /// of this regex's DFAs.
+    ///
+    /// This does **not** include the stack size used up by this regex. To
+    /// compute that, use `std::mem::size_of::<Regex>()`.
+    pub fn memory_usage(&self) -> usize {
+        self.forward.memory_usage() + self.reverse.memory_usage()
+    }
+
+    /// Returns the memory used by each component of this regex, in bytes.
+    ///
+    /// The components of the forward DFA are reported individually, while
+    /// the reverse DFA is reported as a whole. See
+    /// [`MemoryUsage`](struct.MemoryUsage.html) for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = Regex::new("foo[0-9]+")?;
+    /// let usage = re.memory_breakdown();
+    /// let forward = re.forward().memory_breakdown();
+    /// assert_eq!(forward.transitions(), usage.transitions());
+    /// assert_eq!(re.reverse().memory_breakdown().total(), usage.reverse());
+    /// assert!(usage.total() > re.memory_usage());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn memory_breakdown(&self) -> MemoryUsage {
+        MemoryUsage {
+            reverse: self.reverse.memory_breakdown().total(),
+            ..self.forward.memory_breakdown()
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>, S: StateID> Regex<SparseDFA<T, S>> {
+    /// Returns the memory usage, in bytes, of the transition tables of both
+    /// of this regex's DFAs.
+    ///
+    /// This does **not** include the stack size used up by this regex. To
+    /// compute that, use `std::mem::size_of::<Regex>()`.
+    pub fn memory_usage(&self) -> usize {
+        self.forward.memory_usage() + self.reverse.memory_usage()
+    }
+
+    /// Returns the memory used by each component of this regex, in bytes.
+    ///
+    /// The components of the forward DFA are reported individually, while
+    /// the reverse DFA is reported as a whole. See
+    /// [`MemoryUsage`](struct.MemoryUsage.html) for details.
+    pub fn memory_breakdown(&self) -> MemoryUsage {
+        MemoryUsage {
+            reverse: self.reverse.memory_breakdown().total(),
+            ..self.forward.memory_breakdown()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: DFA> Regex<D> {
+    /// Returns an iterator over the non-overlapping matches of each of the
+    /// given regexes in the given bytes, merged into a single stream ordered
+    /// by position.
+    ///
+    /// Each item is the index of the regex that produced the match (in the
+    /// order in which the regexes were given) paired with the start and end
+    /// offsets of the match, exactly as reported by
+    /// [`find_iter`](struct.Regex.html#method.find_iter). Matches are
+    /// yielded in ascending order of their start offset, with ties broken by
+    /// the index of the regex and then by the end offset.
+    ///
+    /// Every regex is searched independently, so matches from different
+    /// regexes may overlap. At most one pending match is kept per regex,
+    /// and each regex is only searched again once its pending match has been
+    /// yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::Regex;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let res = vec![Regex::new("[0-9]+")?, Regex::new("[a-z]+[0-9]")?];
+    /// let text = b"12 ab3 45";
+    /// let matches: Vec<(usize, (usize, usize))> =
+    ///     Regex::merged_find_iter(&res, text).collect();
+    /// assert_eq!(matches, vec![
+    ///     (0, (0, 2)),
+    ///     (1, (3, 6)),
+    ///     (0, (5, 6)),
+    ///     (0, (7, 9)),
+    /// ]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn merged_find_iter<'r, 't, I>(
+        regexes: I,
+        input: &'t [u8],
+    ) -> MergedMatches<'r, 't, D>
+    where
+        I: IntoIterator<Item = &'r Regex<D>>,
+        D: 'r,
+    {
+        MergedMatches::new(regexes, input)
+    }
+}
+
 /// An iterator over all non-overlapping matches for a particular search.
 ///
 /// The iterator yields a `(usize, usize)` value until no more matches could be
@@ -462,6 +867,466 @@ impl<'r, 't, D: DFA> Iterator for Matches<'r, 't, D> {
     }
 }
 
+/// An iterator over all non-overlapping matches in a haystack that is split
+/// into two slices.
+///
+/// The iterator yields a `(usize, usize)` value until no more matches could be
+/// found. Offsets are relative to the start of the first slice, where the
+/// second slice is treated as immediately following the first.
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression value itself.
+/// * `'t` is the lifetime of the text being searched.
+#[derive(Clone, Debug)]
+pub struct RingMatches<'r, 't, D: DFA + 'r> {
+    re: &'r Regex<D>,
+    head: &'t [u8],
+    tail: &'t [u8],
+    last_end: usize,
+    last_match: Option<usize>,
+}
+
+impl<'r, 't, D: DFA> RingMatches<'r, 't, D> {
+    fn new(
+        re: &'r Regex<D>,
+        head: &'t [u8],
+        tail: &'t [u8],
+    ) -> RingMatches<'r, 't, D> {
+        RingMatches { re, head, tail, last_end: 0, last_match: None }
+    }
+}
+
+impl<'r, 't, D: DFA> Iterator for RingMatches<'r, 't, D> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.last_end > self.head.len() + self.tail.len() {
+            return None;
+        }
+        let (s, e) =
+            match self.re.find_ring_at(self.head, self.tail, self.last_end) {
+                None => return None,
+                Some((s, e)) => (s, e),
+            };
+        if s == e {
+            // See `Matches` for how empty matches are handled.
+            self.last_end = e + 1;
+            if Some(e) == self.last_match {
+                return self.next();
+            }
+        } else {
+            self.last_end = e;
+        }
+        self.last_match = Some(e);
+        Some((s, e))
+    }
+}
+
+/// Returns the bytes in the range `[start, end)` of the haystack formed by
+/// `head` followed by `tail`.
+fn ring_range<'t>(
+    head: &'t [u8],
+    tail: &'t [u8],
+    start: usize,
+    end: usize,
+) -> ::core::iter::Chain<
+    ::core::slice::Iter<'t, u8>,
+    ::core::slice::Iter<'t, u8>,
+> {
+    let (hstart, hend) = (start.min(head.len()), end.min(head.len()));
+    let (tstart, tend) =
+        (start.saturating_sub(head.len()), end.saturating_sub(head.len()));
+    head[hstart..hend].iter().chain(tail[tstart..tend].iter())
+}
+
+/// Like `DFA::find_at`, but searches the haystack formed by `head` followed
+/// by `tail`.
+fn ring_find_at<D: DFA>(
+    dfa: &D,
+    head: &[u8],
+    tail: &[u8],
+    start: usize,
+) -> Option<usize> {
+    if dfa.is_anchored() && start > 0 {
+        return None;
+    }
+
+    let mut state = dfa.start_state();
+    let mut last_match =
+        if dfa.is_dead_state(state) {
+            return None;
+        } else if dfa.is_match_state(state) {
+            Some(start)
+        } else {
+            None
+        };
+    let len = head.len() + tail.len();
+    for (i, &b) in ring_range(head, tail, start, len).enumerate() {
+        state = dfa.next_state(state, b);
+        if dfa.is_match_or_dead_state(state) {
+            if dfa.is_dead_state(state) {
+                return last_match;
+            }
+            last_match = Some(start + i + 1);
+        }
+    }
+    last_match
+}
+
+/// Like `DFA::rfind`, but searches the range `[start, end)` of the haystack
+/// formed by `head` followed by `tail`.
+fn ring_rfind<D: DFA>(
+    dfa: &D,
+    head: &[u8],
+    tail: &[u8],
+    start: usize,
+    end: usize,
+) -> Option<usize> {
+    let mut state = dfa.start_state();
+    let mut last_match =
+        if dfa.is_dead_state(state) {
+            return None;
+        } else if dfa.is_match_state(state) {
+            Some(end)
+        } else {
+            None
+        };
+    let bytes = ring_range(head, tail, start, end);
+    for (i, &b) in (start..end).rev().zip(bytes.rev()) {
+        state = dfa.next_state(state, b);
+        if dfa.is_match_or_dead_state(state) {
+            if dfa.is_dead_state(state) {
+                return last_match;
+            }
+            last_match = Some(i);
+        }
+    }
+    last_match
+}
+
+/// An iterator over the end offsets of non-overlapping matches found using
+/// earliest match semantics.
+///
+/// The iterator yields a `usize` for each match, corresponding to the offset
+/// at which the match was detected.
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression value itself.
+/// * `'t` is the lifetime of the text being searched.
+#[derive(Clone, Debug)]
+pub struct ShortestMatches<'r, 't, D: DFA + 'r> {
+    re: &'r Regex<D>,
+    text: &'t [u8],
+    last_end: usize,
+    last_match: Option<usize>,
+}
+
+impl<'r, 't, D: DFA> ShortestMatches<'r, 't, D> {
+    fn new(re: &'r Regex<D>, text: &'t [u8]) -> ShortestMatches<'r, 't, D> {
+        ShortestMatches { re, text, last_end: 0, last_match: None }
+    }
+}
+
+impl<'r, 't, D: DFA> Iterator for ShortestMatches<'r, 't, D> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.last_end > self.text.len() {
+            return None;
+        }
+        let end = self.re.shortest_match_at(self.text, self.last_end)?;
+        if end == self.last_end {
+            // See `Matches` for how empty matches are handled.
+            self.last_end = end + 1;
+            if Some(end) == self.last_match {
+                return self.next();
+            }
+        } else {
+            self.last_end = end;
+        }
+        self.last_match = Some(end);
+        Some(end)
+    }
+}
+
+/// An iterator over the spans between non-overlapping matches for a
+/// particular search.
+///
+/// The iterator yields a `(usize, usize)` value for each span between
+/// consecutive matches, along with the span before the first match and the
+/// span after the last match. The first `usize` is the start of the span
+/// (inclusive) while the second `usize` is the end of the span (exclusive).
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression value itself.
+/// * `'t` is the lifetime of the text being searched.
+#[derive(Clone, Debug)]
+pub struct Gaps<'r, 't, D: DFA + 'r> {
+    matches: Matches<'r, 't, D>,
+    last_end: usize,
+    done: bool,
+}
+
+impl<'r, 't, D: DFA> Gaps<'r, 't, D> {
+    fn new(re: &'r Regex<D>, text: &'t [u8]) -> Gaps<'r, 't, D> {
+        Gaps { matches: Matches::new(re, text), last_end: 0, done: false }
+    }
+}
+
+impl<'r, 't, D: DFA> Iterator for Gaps<'r, 't, D> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.done {
+            return None;
+        }
+        match self.matches.next() {
+            None => {
+                self.done = true;
+                Some((self.last_end, self.matches.text.len()))
+            }
+            Some((s, e)) => {
+                let gap = (self.last_end, s);
+                self.last_end = e;
+                Some(gap)
+            }
+        }
+    }
+}
+
+/// A span of bytes that was either matched by a regex or that lies between
+/// two matches.
+///
+/// This is yielded by the iterator returned by
+/// [`Regex::span_iter`](struct.Regex.html#method.span_iter). In both cases,
+/// the first `usize` is the start of the span (inclusive) while the second
+/// `usize` is the end of the span (exclusive).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Span {
+    /// A span of bytes matched by a regex.
+    Match(usize, usize),
+    /// A non-empty span of bytes that was not matched by a regex.
+    Gap(usize, usize),
+}
+
+impl Span {
+    /// Returns the start offset of this span (inclusive).
+    pub fn start(&self) -> usize {
+        match *self {
+            Span::Match(s, _) | Span::Gap(s, _) => s,
+        }
+    }
+
+    /// Returns the end offset of this span (exclusive).
+    pub fn end(&self) -> usize {
+        match *self {
+            Span::Match(_, e) | Span::Gap(_, e) => e,
+        }
+    }
+
+    /// Returns true if and only if this span corresponds to a match.
+    pub fn is_match(&self) -> bool {
+        match *self {
+            Span::Match(..) => true,
+            Span::Gap(..) => false,
+        }
+    }
+}
+
+/// An iterator that partitions the text being searched into matched and
+/// unmatched spans.
+///
+/// The iterator yields a [`Span`](enum.Span.html) for every match and every
+/// non-empty gap between matches, in the order in which they appear.
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression value itself.
+/// * `'t` is the lifetime of the text being searched.
+#[derive(Clone, Debug)]
+pub struct Spans<'r, 't, D: DFA + 'r> {
+    matches: Matches<'r, 't, D>,
+    last_end: usize,
+    pending: Option<(usize, usize)>,
+    done: bool,
+}
+
+impl<'r, 't, D: DFA> Spans<'r, 't, D> {
+    fn new(re: &'r Regex<D>, text: &'t [u8]) -> Spans<'r, 't, D> {
+        Spans {
+            matches: Matches::new(re, text),
+            last_end: 0,
+            pending: None,
+            done: false,
+        }
+    }
+}
+
+impl<'r, 't, D: DFA> Iterator for Spans<'r, 't, D> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        if let Some((s, e)) = self.pending.take() {
+            return Some(Span::Match(s, e));
+        }
+        if self.done {
+            return None;
+        }
+        match self.matches.next() {
+            None => {
+                self.done = true;
+                let len = self.matches.text.len();
+                if self.last_end < len {
+                    Some(Span::Gap(self.last_end, len))
+                } else {
+                    None
+                }
+            }
+            Some((s, e)) => {
+                let gap_start = self.last_end;
+                self.last_end = e;
+                if gap_start < s {
+                    self.pending = Some((s, e));
+                    Some(Span::Gap(gap_start, s))
+                } else {
+                    Some(Span::Match(s, e))
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over all matches for a particular search, including matches
+/// that overlap.
+///
+/// The iterator yields a `(usize, usize)` value for every position at which a
+/// match ends. The first `usize` is the start of the match (inclusive) while
+/// the second `usize` is the end of the match (exclusive).
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression value itself.
+/// * `'t` is the lifetime of the text being searched.
+#[derive(Clone, Debug)]
+pub struct OverlappingMatches<'r, 't, D: DFA + 'r> {
+    re: &'r Regex<D>,
+    text: &'t [u8],
+    /// The current state of the forward DFA, or `None` before the start
+    /// state has been inspected.
+    state: Option<D::ID>,
+    /// The number of bytes of text consumed by the forward DFA.
+    pos: usize,
+}
+
+impl<'r, 't, D: DFA> OverlappingMatches<'r, 't, D> {
+    fn new(
+        re: &'r Regex<D>,
+        text: &'t [u8],
+    ) -> OverlappingMatches<'r, 't, D> {
+        OverlappingMatches { re, text, state: None, pos: 0 }
+    }
+
+    /// Return the match ending at the given offset, which must be an offset
+    /// at which the forward DFA is in a match state.
+    ///
+    /// This returns `None` only when the reverse DFA does not agree with the
+    /// forward DFA, which ends the iteration.
+    fn match_ending_at(&self, end: usize) -> Option<(usize, usize)> {
+        let start = self.re.reverse().rfind(&self.text[..end])?;
+        Some((start, end))
+    }
+}
+
+impl<'r, 't, D: DFA> Iterator for OverlappingMatches<'r, 't, D> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let fwd = self.re.forward();
+        let mut state = match self.state {
+            Some(state) => state,
+            None => {
+                let state = fwd.start_state();
+                self.state = Some(state);
+                if fwd.is_match_state(state) {
+                    return self.match_ending_at(0);
+                }
+                state
+            }
+        };
+        while self.pos < self.text.len() {
+            if fwd.is_dead_state(state) {
+                return None;
+            }
+            state = unsafe {
+                fwd.next_state_unchecked(state, self.text[self.pos])
+            };
+            self.pos += 1;
+            self.state = Some(state);
+            if fwd.is_match_state(state) {
+                return self.match_ending_at(self.pos);
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over the non-overlapping matches of several regexes, merged
+/// into a single stream ordered by position.
+///
+/// The iterator yields a `(usize, (usize, usize))` value for every match,
+/// where the first `usize` is the index of the regex that produced the match
+/// and the pair is the start (inclusive) and end (exclusive) of the match.
+///
+/// The lifetime variables are as follows:
+///
+/// * `'r` is the lifetime of the regular expression values themselves.
+/// * `'t` is the lifetime of the text being searched.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct MergedMatches<'r, 't, D: DFA + 'r> {
+    matches: Vec<Matches<'r, 't, D>>,
+    /// The pending match of every regex that has not yet been exhausted,
+    /// keyed so that the smallest key is the next match to yield.
+    pending: BinaryHeap<Reverse<(usize, usize, usize)>>,
+}
+
+#[cfg(feature = "std")]
+impl<'r, 't, D: DFA> MergedMatches<'r, 't, D> {
+    fn new<I>(regexes: I, text: &'t [u8]) -> MergedMatches<'r, 't, D>
+    where
+        I: IntoIterator<Item = &'r Regex<D>>,
+    {
+        let mut matches: Vec<Matches<'r, 't, D>> =
+            regexes.into_iter().map(|re| Matches::new(re, text)).collect();
+        let mut pending = BinaryHeap::with_capacity(matches.len());
+        for (i, it) in matches.iter_mut().enumerate() {
+            if let Some((s, e)) = it.next() {
+                pending.push(Reverse((s, i, e)));
+            }
+        }
+        MergedMatches { matches, pending }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'r, 't, D: DFA> Iterator for MergedMatches<'r, 't, D> {
+    type Item = (usize, (usize, usize));
+
+    fn next(&mut self) -> Option<(usize, (usize, usize))> {
+        let (s, i, e) = match self.pending.pop() {
+            None => return None,
+            Some(Reverse(next)) => next,
+        };
+        if let Some((next_s, next_e)) = self.matches[i].next() {
+            self.pending.push(Reverse((next_s, i, next_e)));
+        }
+        Some((i, (s, e)))
+    }
+}
+
 /// A builder for a regex based on deterministic finite automatons.
 ///
 /// This builder permits configuring several aspects of the construction
@@ -494,22 +1359,52 @@ impl RegexBuilder {
     ///
     /// If there was a problem parsing or compiling the pattern, then an error
     /// is returned.
+    ///
+    /// When the `parallel` feature is enabled, the forward and reverse DFAs
+    /// are built concurrently, with the reverse DFA built on a new thread.
+    /// This can nearly halve the time it takes to build large regexes.
     pub fn build(
         &self,
         pattern: &str,
     ) -> Result<Regex> {
-        self.build_with_size::<usize>(pattern)
+        if cfg!(feature = "parallel") {
+            self.build_parallel(pattern)
+        } else {
+            self.build_with_size::<usize>(pattern)
+        }
     }
 
     /// Build a regex from the given pattern using sparse DFAs.
     ///
     /// If there was a problem parsing or compiling the pattern, then an error
     /// is returned.
+    ///
+    /// Like `build`, this builds the forward and reverse DFAs concurrently
+    /// when the `parallel` feature is enabled.
     pub fn build_sparse(
         &self,
         pattern: &str,
     ) -> Result<Regex<SparseDFA<Vec<u8>, usize>>> {
-        self.build_with_size_sparse::<usize>(pattern)
+        let re = self.build(pattern)?;
+        let fwd = re.forward().to_sparse()?;
+        let rev = re.reverse().to_sparse()?;
+        Ok(Regex::from_dfas(fwd, rev))
+    }
+
+    /// Build a regex from the given pattern, building the reverse DFA on a
+    /// new thread while the forward DFA is built on the current thread.
+    fn build_parallel(&self, pattern: &str) -> Result<Regex> {
+        let mut reverse = self.dfa.clone();
+        reverse.anchored(true).reverse(true).longest_match(true);
+        let owned = pattern.to_string();
+        let handle = thread::spawn(move || reverse.build(&owned));
+
+        let forward = self.dfa.build(pattern);
+        let reverse = match handle.join() {
+            Ok(reverse) => reverse,
+            Err(panic) => panic::resume_unwind(panic),
+        };
+        Ok(Regex::from_dfas(forward?, reverse?))
     }
 
     /// Build a regex from the given pattern using a specific representation
@@ -548,6 +1443,126 @@ impl RegexBuilder {
         Ok(Regex::from_dfas(forward, reverse))
     }
 
+    /// Build a regex that matches any of the given literals.
+    ///
+    /// The literals never need to be escaped. See
+    /// [`dense::Builder::build_literals`](dense/struct.Builder.html#method.build_literals)
+    /// for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::RegexBuilder;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = RegexBuilder::new().build_literals(&["1+1", "2"])?;
+    /// let matches: Vec<(usize, usize)> = re.find_iter(b"1+1=2").collect();
+    /// assert_eq!(matches, vec![(0, 3), (4, 5)]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_literals<I, P>(&self, literals: I) -> Result<Regex>
+    where I: IntoIterator<Item=P>, P: AsRef<[u8]>
+    {
+        self.build_literals_with_size::<usize, I, P>(literals)
+    }
+
+    /// Build a regex that matches any of the given literals using a specific
+    /// representation for the underlying DFA state IDs.
+    pub fn build_literals_with_size<S, I, P>(
+        &self,
+        literals: I,
+    ) -> Result<Regex<DenseDFA<Vec<S>, S>>>
+    where S: StateID, I: IntoIterator<Item=P>, P: AsRef<[u8]>
+    {
+        // The literals are collected since they are compiled twice.
+        let literals: Vec<P> = literals.into_iter().collect();
+        let forward = self.dfa.build_literals_with_size(&literals)?;
+        let reverse = self.dfa
+            .clone()
+            .anchored(true)
+            .reverse(true)
+            .longest_match(true)
+            .build_literals_with_size(&literals)?;
+        Ok(Regex::from_dfas(forward, reverse))
+    }
+
+    /// Build a regex from the given high level intermediate representation of
+    /// a regular expression.
+    ///
+    /// The parser options of this builder are ignored. See
+    /// [`dense::Builder::build_from_hir`](dense/struct.Builder.html#method.build_from_hir)
+    /// for more details.
+    ///
+    /// If there was a problem compiling the HIR, then an error is returned.
+    pub fn build_from_hir(&self, hir: &Hir) -> Result<Regex> {
+        self.build_from_hir_with_size::<usize>(hir)
+    }
+
+    /// Build a regex from the given high level intermediate representation of
+    /// a regular expression using a specific representation for the
+    /// underlying DFA state IDs.
+    pub fn build_from_hir_with_size<S: StateID>(
+        &self,
+        hir: &Hir,
+    ) -> Result<Regex<DenseDFA<Vec<S>, S>>> {
+        let forward = self.dfa.build_from_hir_with_size(hir)?;
+        let reverse = self.dfa
+            .clone()
+            .anchored(true)
+            .reverse(true)
+            .longest_match(true)
+            .build_from_hir_with_size(hir)?;
+        Ok(Regex::from_dfas(forward, reverse))
+    }
+
+    /// Build a regex that matches every string within the given Levenshtein
+    /// distance of `query`.
+    ///
+    /// The query is always treated literally. When this builder is anchored,
+    /// the forward DFA is built with all-matches semantics regardless of the
+    /// configured match semantics, so that every string within the distance
+    /// is matched. See
+    /// [`dense::Builder::build_levenshtein`](dense/struct.Builder.html#method.build_levenshtein)
+    /// for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::RegexBuilder;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = RegexBuilder::new().build_levenshtein("foobar", 1)?;
+    /// let text = b"foobar fooxbar fobr foo bar";
+    /// let matches: Vec<(usize, usize)> = re.find_iter(text).collect();
+    /// assert_eq!(matches, vec![(0, 6), (7, 14), (20, 27)]);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn build_levenshtein(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<Regex> {
+        self.build_levenshtein_with_size::<usize>(query, distance)
+    }
+
+    /// Build a regex that matches every string within the given Levenshtein
+    /// distance of `query` using a specific representation for the
+    /// underlying DFA state IDs.
+    pub fn build_levenshtein_with_size<S: StateID>(
+        &self,
+        query: &str,
+        distance: u32,
+    ) -> Result<Regex<DenseDFA<Vec<S>, S>>> {
+        let forward = self.dfa.build_levenshtein_with_size(query, distance)?;
+        let reverse = self.dfa
+            .clone()
+            .anchored(true)
+            .reverse(true)
+            .longest_match(true)
+            .build_levenshtein_with_size(query, distance)?;
+        Ok(Regex::from_dfas(forward, reverse))
+    }
+
     /// Build a regex from the given pattern using a specific representation
     /// for the underlying DFA state IDs using sparse DFAs.
     pub fn build_with_size_sparse<S: StateID>(
@@ -572,6 +1587,51 @@ impl RegexBuilder {
         self
     }
 
+    /// Set the match semantics of the forward DFA.
+    ///
+    /// With [`MatchKind::All`](dense/enum.MatchKind.html#variant.All), no
+    /// match is pruned based on the order of alternations or the greediness
+    /// of repetitions, which is needed to report every match with
+    /// [`Regex::find_overlapping_iter`](struct.Regex.html#method.find_overlapping_iter).
+    /// Note though that an unanchored forward DFA built this way usually
+    /// only stops at the end of the input, so the leftmost first searches of
+    /// the resulting regex, such as `find`, are not meaningful for it.
+    ///
+    /// With
+    /// [`MatchKind::LeftmostLongest`](dense/enum.MatchKind.html#variant.LeftmostLongest),
+    /// the searches of the resulting regex report leftmost-longest matches,
+    /// as in POSIX, instead of leftmost first matches.
+    ///
+    /// The reverse DFA is unaffected, since it always finds the earliest
+    /// start of a match.
+    ///
+    /// By default this is set to `MatchKind::LeftmostFirst`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::RegexBuilder;
+    /// use regex_automata::dense::MatchKind;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let re = RegexBuilder::new()
+    ///     .match_kind(MatchKind::All)
+    ///     .build("Sam|Samwise")?;
+    /// let matches: Vec<(usize, usize)> =
+    ///     re.find_overlapping_iter(b"Samwise").collect();
+    /// assert_eq!(matches, vec![(0, 3), (0, 7)]);
+    ///
+    /// let re = RegexBuilder::new()
+    ///     .match_kind(MatchKind::LeftmostLongest)
+    ///     .build("Sam|Samwise")?;
+    /// assert_eq!(Some((0, 7)), re.find(b"Samwise"));
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn match_kind(&mut self, kind: MatchKind) -> &mut RegexBuilder {
+        self.dfa.match_kind(kind);
+        self
+    }
+
     /// Enable or disable the case insensitive flag by default.
     ///
     /// By default this is disabled. It may alternatively be selectively
@@ -762,6 +1822,55 @@ impl RegexBuilder {
         self.dfa.byte_classes(yes);
         self
     }
+
+    /// Set a limit, in bytes, on the size of the transition table of each of
+    /// the underlying DFAs.
+    ///
+    /// When set, building the regex fails as soon as determinizing either
+    /// DFA exceeds this limit. See
+    /// [`dense::Builder::dfa_size_limit`](dense/struct.Builder.html#method.dfa_size_limit)
+    /// for more details.
+    ///
+    /// By default there is no limit.
+    pub fn dfa_size_limit(
+        &mut self,
+        limit: Option<usize>,
+    ) -> &mut RegexBuilder {
+        self.dfa.dfa_size_limit(limit);
+        self
+    }
+
+    /// Set a limit, in bytes, on the approximate size of each NFA compiled
+    /// while building the regex.
+    ///
+    /// See
+    /// [`dense::Builder::nfa_size_limit`](dense/struct.Builder.html#method.nfa_size_limit)
+    /// for more details.
+    ///
+    /// By default there is no limit.
+    pub fn nfa_size_limit(
+        &mut self,
+        limit: Option<usize>,
+    ) -> &mut RegexBuilder {
+        self.dfa.nfa_size_limit(limit);
+        self
+    }
+
+    /// Set a limit on the time spent determinizing and minimizing each of
+    /// the DFAs that make up the regex.
+    ///
+    /// See
+    /// [`dense::Builder::time_limit`](dense/struct.Builder.html#method.time_limit)
+    /// for more details.
+    ///
+    /// By default there is no limit.
+    pub fn time_limit(
+        &mut self,
+        limit: Option<Duration>,
+    ) -> &mut RegexBuilder {
+        self.dfa.time_limit(limit);
+        self
+    }
 }
 
 #[cfg(feature = "std")]
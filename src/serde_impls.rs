@@ -0,0 +1,178 @@
+use std::cmp;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use dense::DenseDFA;
+use dfa::DFA;
+use regex::Regex;
+use sparse::SparseDFA;
+use state_id::StateID;
+
+/// Serializes a dense DFA as the bytes returned by
+/// [`to_bytes_native_endian`](enum.DenseDFA.html#method.to_bytes_native_endian).
+impl<T: AsRef<[S]>, S: StateID> Serialize for DenseDFA<T, S> {
+    fn serialize<Z: Serializer>(
+        &self,
+        serializer: Z,
+    ) -> Result<Z::Ok, Z::Error> {
+        let bytes =
+            self.to_bytes_native_endian().map_err(ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// Deserializes a dense DFA using
+/// [`from_bytes_any_endian`](enum.DenseDFA.html#method.from_bytes_any_endian),
+/// which validates the serialized bytes and accepts DFAs serialized on a
+/// target with either endianness.
+impl<'de, S: StateID> Deserialize<'de> for DenseDFA<Vec<S>, S> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DenseDFA<Vec<S>, S>, D::Error> {
+        let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+        DenseDFA::from_bytes_any_endian(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes a sparse DFA as the bytes returned by
+/// [`to_bytes_native_endian`](enum.SparseDFA.html#method.to_bytes_native_endian).
+impl<T: AsRef<[u8]>, S: StateID> Serialize for SparseDFA<T, S> {
+    fn serialize<Z: Serializer>(
+        &self,
+        serializer: Z,
+    ) -> Result<Z::Ok, Z::Error> {
+        let bytes =
+            self.to_bytes_native_endian().map_err(ser::Error::custom)?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// Deserializes a sparse DFA using
+/// [`try_from_bytes`](enum.SparseDFA.html#method.try_from_bytes), which
+/// validates the serialized bytes. Since sparse DFAs are not converted
+/// between endiannesses, the DFA must have been serialized on a target with
+/// the same endianness.
+impl<'de, S: StateID> Deserialize<'de> for SparseDFA<Vec<u8>, S> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<SparseDFA<Vec<u8>, S>, D::Error> {
+        let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+        SparseDFA::try_from_bytes(&bytes)
+            .map(|dfa| dfa.to_owned())
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Serializes a regex as a pair of its forward and reverse DFAs.
+impl<D: DFA + Serialize> Serialize for Regex<D> {
+    fn serialize<Z: Serializer>(
+        &self,
+        serializer: Z,
+    ) -> Result<Z::Ok, Z::Error> {
+        (self.forward(), self.reverse()).serialize(serializer)
+    }
+}
+
+/// Deserializes a regex from a pair of its forward and reverse DFAs.
+///
+/// An error is returned if the reverse DFA is not anchored, since it could
+/// not have been built as the reverse DFA of any regex. Beyond that, the two
+/// DFAs are not checked to correspond to the same regex. If they do not,
+/// then searching with the regex may panic.
+impl<'de, D: DFA + Deserialize<'de>> Deserialize<'de> for Regex<D> {
+    fn deserialize<Z: Deserializer<'de>>(
+        deserializer: Z,
+    ) -> Result<Regex<D>, Z::Error> {
+        let (forward, reverse) = <(D, D)>::deserialize(deserializer)?;
+        if !reverse.is_anchored() {
+            return Err(de::Error::custom(
+                "the reverse DFA of a regex must be anchored",
+            ));
+        }
+        Ok(Regex::from_dfas(forward, reverse))
+    }
+}
+
+/// A visitor for the bytes of a serialized DFA.
+///
+/// Besides byte strings, this accepts sequences of bytes, since some formats,
+/// such as JSON, serialize byte strings that way.
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the bytes of a serialized DFA")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Vec<u8>, A::Error> {
+        // Don't trust the size hint too much, since it comes from the input.
+        let hint = cmp::min(seq.size_hint().unwrap_or(0), 1 << 16);
+        let mut bytes = Vec::with_capacity(hint);
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde::Deserialize;
+
+    use dense::DenseDFA;
+    use regex::Regex;
+
+    fn deserialize(
+        forward: &[u8],
+        reverse: &[u8],
+    ) -> Result<Regex<DenseDFA<Vec<usize>, usize>>, Error> {
+        let parts = vec![forward, reverse].into_iter();
+        Regex::deserialize(SeqDeserializer::<_, Error>::new(parts))
+    }
+
+    #[test]
+    fn regex_roundtrip() {
+        let re = Regex::new("foo[0-9]+").unwrap();
+        let forward = re.forward().to_bytes_native_endian().unwrap();
+        let reverse = re.reverse().to_bytes_native_endian().unwrap();
+        let re = deserialize(&forward, &reverse).unwrap();
+        assert_eq!(Some((1, 6)), re.find(b"xfoo12x"));
+    }
+
+    #[test]
+    fn regex_unanchored_reverse() {
+        let re = Regex::new("foo[0-9]+").unwrap();
+        let forward = re.forward().to_bytes_native_endian().unwrap();
+        assert!(deserialize(&forward, &forward).is_err());
+    }
+
+    #[test]
+    fn regex_mismatched_never_panics() {
+        let a = Regex::new("a").unwrap();
+        let b = Regex::new("b").unwrap();
+        let forward = a.forward().to_bytes_native_endian().unwrap();
+        let reverse = b.reverse().to_bytes_native_endian().unwrap();
+        let re = deserialize(&forward, &reverse).unwrap();
+        assert!(re.is_match(b"xa"));
+        assert_eq!(None, re.find(b"xa"));
+        assert_eq!(None, re.find_ring(b"x", b"a"));
+        assert_eq!(0, re.find_iter(b"aa").count());
+        assert_eq!(0, re.find_overlapping_iter(b"aa").count());
+    }
+}
@@ -1,6 +1,8 @@
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 #[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
 use core::fmt;
 #[cfg(feature = "std")]
 use core::iter;
@@ -12,14 +14,26 @@ use byteorder::{ByteOrder, NativeEndian};
 use byteorder::{BigEndian, LittleEndian};
 
 use classes::ByteClasses;
+#[cfg(feature = "std")]
+use codegen;
+#[cfg(feature = "std")]
+use crc32::crc32;
 use dense;
 use dfa::DFA;
 #[cfg(feature = "std")]
+use dot;
+#[cfg(feature = "std")]
 use error::{Error, Result};
+use memory::MemoryUsage;
 #[cfg(feature = "std")]
 use state_id::{StateID, dead_id, usize_to_state_id, write_state_id_bytes};
 #[cfg(not(feature = "std"))]
 use state_id::{StateID, dead_id};
+#[cfg(feature = "std")]
+use table;
+
+/// The version of the sparse DFA serialization format.
+const VERSION: u16 = 2;
 
 /// A sparse table-based deterministic finite automaton (DFA).
 ///
@@ -238,6 +252,25 @@ impl<T: AsRef<[u8]>, S: StateID> SparseDFA<T, S> {
         self.repr().memory_usage()
     }
 
+    /// Returns the memory used by each component of this DFA, in bytes.
+    ///
+    /// The transition table reported here is the same as the one reported
+    /// by [`memory_usage`](enum.SparseDFA.html#method.memory_usage). See
+    /// [`MemoryUsage`](../struct.MemoryUsage.html) for details about the
+    /// other components.
+    pub fn memory_breakdown(&self) -> MemoryUsage {
+        self.repr().memory_breakdown()
+    }
+
+    /// Returns the equivalence classes of bytes used by this DFA.
+    ///
+    /// If this DFA does not use byte classes, then every byte is in a class
+    /// of its own. See [`ByteClasses`](../struct.ByteClasses.html) for an
+    /// example.
+    pub fn byte_classes(&self) -> &ByteClasses {
+        &self.repr().byte_classes
+    }
+
     fn repr(&self) -> &Repr<T, S> {
         match *self {
             SparseDFA::Standard(ref r) => &r.0,
@@ -310,7 +343,7 @@ impl<T: AsRef<[u8]>, S: StateID> SparseDFA<T, S> {
     /// Serialize a sparse DFA to raw bytes in little endian format.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_little_endian(&self) -> Result<Vec<u8>> {
@@ -320,7 +353,7 @@ impl<T: AsRef<[u8]>, S: StateID> SparseDFA<T, S> {
     /// Serialize a sparse DFA to raw bytes in big endian format.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_big_endian(&self) -> Result<Vec<u8>> {
@@ -334,12 +367,115 @@ impl<T: AsRef<[u8]>, S: StateID> SparseDFA<T, S> {
     /// same platform.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     pub fn to_bytes_native_endian(&self) -> Result<Vec<u8>> {
         self.repr().to_bytes::<NativeEndian>()
     }
+
+    /// Write this DFA to `wtr` as a graph in the DOT language used by
+    /// Graphviz, labeling each edge with the byte ranges that lead to its
+    /// target.
+    ///
+    /// This is the same as [`dot::write`](../dot/fn.write.html). See the
+    /// [`dot`](../dot/index.html) module for details.
+    pub fn to_dot<W: io::Write>(&self, wtr: W) -> io::Result<()> {
+        dot::write(self, wtr)
+    }
+
+    /// Like [`to_dot`](enum.SparseDFA.html#method.to_dot), but label each
+    /// edge with the byte classes of this DFA that lead to its target, and
+    /// add a legend listing the bytes in each class.
+    ///
+    /// If this DFA does not use byte classes, then every byte is in a class
+    /// of its own.
+    pub fn to_dot_with_classes<W: io::Write>(
+        &self,
+        wtr: W,
+    ) -> io::Result<()> {
+        dot::write_with_classes(self, &self.repr().byte_classes, wtr)
+    }
+
+    /// Write this DFA to `wtr` as Rust source code defining a static DFA
+    /// named `name`.
+    ///
+    /// This is the implementation of
+    /// [`codegen::write_sparse`](codegen/fn.write_sparse.html).
+    pub(crate) fn write_rust<W: io::Write>(
+        &self,
+        name: &str,
+        wtr: W,
+    ) -> io::Result<()> {
+        let r = self.repr();
+        let id_type = codegen::id_type::<S>()?;
+        let table = |little: bool| {
+            let mut buf = vec![0; r.trans().len()];
+            if little {
+                r.write_transitions::<LittleEndian>(&mut buf);
+            } else {
+                r.write_transitions::<BigEndian>(&mut buf);
+            }
+            buf.iter().map(|b| b.to_string()).collect()
+        };
+        codegen::Source {
+            kind: "SparseDFA",
+            summary: format!(
+                "a sparse DFA with {} states and {} byte classes",
+                r.state_count,
+                r.byte_classes.alphabet_len(),
+            ),
+            table_type: "u8",
+            id_type,
+            fields: vec![
+                (r.anchored.to_string(), "anchored"),
+                (codegen::id_literal(r.start), "start state"),
+                (r.state_count.to_string(), "state count"),
+                (codegen::id_literal(r.max_match), "max match state"),
+            ],
+            byte_classes: &r.byte_classes,
+            tables: vec![
+                (Some("target_endian = \"little\""), table(true)),
+                (Some("target_endian = \"big\""), table(false)),
+            ],
+        }
+        .write(name, wtr)
+    }
+
+    /// Returns an iterator over every state in this DFA, including the dead
+    /// state and states that are not reachable from the start state.
+    ///
+    /// Each state is yielded along with an iterator over its transitions,
+    /// and states are yielded in the order in which they appear in the
+    /// transition table. The identifiers yielded can be given to the methods
+    /// of the [`DFA`](../trait.DFA.html) trait, such as `is_match_state` and
+    /// `next_state`.
+    ///
+    /// # Example
+    ///
+    /// This finds the state with the most transitions.
+    ///
+    /// ```
+    /// use regex_automata::dense;
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let dfa = dense::Builder::new()
+    ///     .anchored(true)
+    ///     .build("[a-c]x|[0-9]+")?
+    ///     .to_sparse()?;
+    /// let busiest = dfa.states().map(|(_, t)| t.count()).max();
+    /// assert_eq!(Some(2), busiest);
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    pub fn states(&self) -> States<'_, T, S> {
+        States { it: self.repr().states() }
+    }
+
+    /// Returns the total number of states in this DFA, including the dead
+    /// state. Every DFA has at least one state, even the empty DFA.
+    pub fn state_count(&self) -> usize {
+        self.repr().state_count
+    }
 }
 
 impl<'a, S: StateID> SparseDFA<&'a [u8], S> {
@@ -410,6 +546,114 @@ impl<'a, S: StateID> SparseDFA<&'a [u8], S> {
     pub unsafe fn from_bytes(buf: &'a [u8]) -> SparseDFA<&'a [u8], S> {
         Repr::from_bytes(buf).into_sparse_dfa()
     }
+
+    /// Deserialize a sparse DFA with a specific state identifier
+    /// representation, after exhaustively validating that the given bytes
+    /// correspond to a valid serialized sparse DFA.
+    ///
+    /// Unlike [`from_bytes`](enum.SparseDFA.html#method.from_bytes), this
+    /// routine is safe to call with *untrusted* bytes. In addition to the
+    /// checks done by `from_bytes`, this walks the entire transition table,
+    /// checking that every state is completely contained in it and has
+    /// sorted, non-overlapping input ranges, that the number of states
+    /// matches the state count in the header, that the start state, the
+    /// maximum match state and the target of every transition is the
+    /// identifier of a state, and that the dead state only ever transitions
+    /// to itself. Finally, the checksum of the transition table is verified.
+    /// If any of these checks fail, then an error is returned instead of
+    /// panicking. Header mismatches are reported in the same way as
+    /// [`DenseDFA::try_from_bytes`](enum.DenseDFA.html#method.try_from_bytes).
+    ///
+    /// Deserializing a DFA using this routine allocates memory proportional
+    /// to the number of states in the DFA while validating it, and takes
+    /// time proportional to the size of the DFA. If the bytes given are
+    /// always trusted, then `from_bytes` can be used to avoid this cost.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use regex_automata::{DFA, SparseDFA};
+    ///
+    /// # fn example() -> Result<(), regex_automata::Error> {
+    /// let sparse = SparseDFA::new("foo[0-9]+")?;
+    /// let bytes = sparse.to_u16()?.to_bytes_native_endian()?;
+    /// let dfa: SparseDFA<&[u8], u16> = SparseDFA::try_from_bytes(&bytes)?;
+    ///
+    /// assert_eq!(Some(8), dfa.find(b"foo12345"));
+    ///
+    /// // Truncated or corrupted bytes are rejected with an error.
+    /// let truncated = &bytes[..bytes.len() - 1];
+    /// assert!(SparseDFA::<&[u8], u16>::try_from_bytes(truncated).is_err());
+    /// let mut corrupt = bytes.clone();
+    /// let last = corrupt.len() - 1;
+    /// corrupt[last] ^= 0xFF;
+    /// assert!(SparseDFA::<&[u8], u16>::try_from_bytes(&corrupt).is_err());
+    /// # Ok(()) }; example().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn try_from_bytes(buf: &'a [u8]) -> Result<SparseDFA<&'a [u8], S>> {
+        Repr::try_from_bytes(buf).map(|r| r.into_sparse_dfa())
+    }
+
+    /// Build a sparse DFA from its raw parts, borrowing the given transition
+    /// table.
+    ///
+    /// This is a `const fn`, which permits a DFA to be stored in a `static`
+    /// without any deserialization at runtime. It is what Rust code written
+    /// by the [`codegen`](codegen/index.html) module uses to construct its
+    /// DFAs, and is generally not meant to be called directly.
+    ///
+    /// The parts given must be exactly as they are stored in a DFA built by
+    /// this crate. In particular, the transition table must be encoded in
+    /// the endianness of the current target, and every state identifier is
+    /// the offset of a state in the transition table.
+    ///
+    /// # Safety
+    ///
+    /// This routine is unsafe because it permits callers to provide an
+    /// arbitrary transition table with possibly incorrect transitions. None
+    /// of the parts given are checked. Given an incorrect transition table,
+    /// it is possible for the search routines to access out-of-bounds memory
+    /// because of explicit bounds check elision.
+    pub const unsafe fn from_parts(
+        anchored: bool,
+        start: S,
+        state_count: usize,
+        max_match: S,
+        byte_classes: [u8; 256],
+        trans: &'a [u8],
+    ) -> SparseDFA<&'a [u8], S> {
+        let singleton = byte_classes[255] == 255;
+        let repr = Repr {
+            anchored,
+            start,
+            state_count,
+            max_match,
+            byte_classes: ByteClasses::from_array(byte_classes),
+            trans,
+        };
+        if singleton {
+            SparseDFA::Standard(Standard(repr))
+        } else {
+            SparseDFA::ByteClass(ByteClass(repr))
+        }
+    }
+}
+
+/// Formats this DFA as a table with one line per state.
+///
+/// This uses the same format as the
+/// [`Display`](enum.DenseDFA.html#impl-Display) implementation for dense
+/// DFAs. Since the identifier of each state in a sparse DFA is its offset in
+/// the DFA's transition table, identifiers are usually not consecutive.
+///
+/// This is meant for reading by humans, and its format may change.
+#[cfg(feature = "std")]
+impl<T: AsRef<[u8]>, S: StateID> fmt::Display for SparseDFA<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let classes = &self.repr().byte_classes;
+        table::write(f, "SparseDFA", self, self.states(), classes)
+    }
 }
 
 impl<T: AsRef<[u8]>, S: StateID> DFA for SparseDFA<T, S> {
@@ -687,6 +931,15 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
         self.trans().len()
     }
 
+    fn memory_breakdown(&self) -> MemoryUsage {
+        MemoryUsage {
+            transitions: self.memory_usage(),
+            byte_classes: size_of::<ByteClasses>(),
+            match_states: size_of::<S>(),
+            reverse: 0,
+        }
+    }
+
     fn start_state(&self) -> S {
         self.start
     }
@@ -762,7 +1015,7 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
     /// Serialize a sparse DFA to raw bytes using the provided endianness.
     ///
     /// If the state identifier representation of this DFA has a size different
-    /// than 1, 2, 4 or 8 bytes, then this returns an error. All
+    /// than 1, 2, 3, 4 or 8 bytes, then this returns an error. All
     /// implementations of `StateID` provided by this crate satisfy this
     /// requirement.
     ///
@@ -779,7 +1032,7 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
             // For version number.
             + 2
             // Size of state ID representation, in bytes.
-            // Must be 1, 2, 4 or 8.
+            // Must be 1, 2, 3, 4 or 8.
             + 2
             // For DFA misc options. (Currently unused.)
             + 2
@@ -789,6 +1042,10 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
             + 8
             // For max match state.
             + 8
+            // For CRC-32 checksum of transition table.
+            + 4
+            // Reserved, must be zero.
+            + 4
             // For byte class map.
             + 256
             // For transition table.
@@ -806,13 +1063,13 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
         A::write_u16(&mut buf[i..], 0xFEFF);
         i += 2;
         // version number
-        A::write_u16(&mut buf[i..], 1);
+        A::write_u16(&mut buf[i..], VERSION);
         i += 2;
         // size of state ID
         let state_size = size_of::<S>();
-        if ![1, 2, 4, 8].contains(&state_size) {
+        if ![1, 2, 3, 4, 8].contains(&state_size) {
             return Err(Error::serialize(&format!(
-                "state size of {} not supported, must be 1, 2, 4 or 8",
+                "state size of {} not supported, must be 1, 2, 3, 4 or 8",
                 state_size
             )));
         }
@@ -837,12 +1094,34 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
             self.max_match.to_usize() as u64,
         );
         i += 8;
+        // checksum of transition table, written after the table below
+        let checksum_at = i;
+        i += 4;
+        // reserved
+        i += 4;
         // byte class map
         for b in (0..256).map(|b| b as u8) {
             buf[i] = self.byte_classes.get(b);
             i += 1;
         }
         // transition table
+        let trans_at = i;
+        i += self.write_transitions::<A>(&mut buf[i..]);
+
+        assert_eq!(size, i, "expected to consume entire buffer");
+        let checksum = crc32(&buf[trans_at..]);
+        A::write_u32(&mut buf[checksum_at..], checksum);
+
+        Ok(buf)
+    }
+
+    /// Write the transition table of this DFA to `buf` in the given
+    /// endianness, and return the number of bytes written.
+    ///
+    /// `buf` must be at least as long as the transition table.
+    #[cfg(feature = "std")]
+    fn write_transitions<A: ByteOrder>(&self, buf: &mut [u8]) -> usize {
+        let mut i = 0;
         for (_, state) in self.states() {
             A::write_u16(&mut buf[i..], state.ntrans as u16);
             i += 2;
@@ -853,10 +1132,7 @@ impl<T: AsRef<[u8]>, S: StateID> Repr<T, S> {
                 i += size_of::<S>();
             }
         }
-
-        assert_eq!(size, i, "expected to consume entire buffer");
-
-        Ok(buf)
+        i
     }
 }
 
@@ -884,10 +1160,10 @@ impl<'a, S: StateID> Repr<&'a [u8], S> {
         // check that the version number is supported
         let version = NativeEndian::read_u16(buf);
         buf = &buf[2..];
-        if version != 1 {
+        if version != VERSION {
             panic!(
-                "expected version 1, but found unsupported version {}",
-                version,
+                "expected version {}, but found unsupported version {}",
+                VERSION, version,
             );
         }
 
@@ -918,6 +1194,9 @@ impl<'a, S: StateID> Repr<&'a [u8], S> {
         let max_match = S::from_usize(NativeEndian::read_u64(buf) as usize);
         buf = &buf[8..];
 
+        // skip over the checksum and the reserved bytes
+        buf = &buf[8..];
+
         // read byte classes
         let byte_classes = ByteClasses::from_slice(&buf[..256]);
         buf = &buf[256..];
@@ -933,6 +1212,188 @@ impl<'a, S: StateID> Repr<&'a [u8], S> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<'a, S: StateID> Repr<&'a [u8], S> {
+    /// The implementation for deserializing a sparse DFA from untrusted raw
+    /// bytes.
+    ///
+    /// This checks everything that `from_bytes` does, but returns an error
+    /// instead of panicking, and also validates the entire transition table.
+    fn try_from_bytes(buf: &'a [u8]) -> Result<Repr<&'a [u8], S>> {
+        let label = b"rust-regex-automata-sparse-dfa\x00";
+        let header_len = label.len() + 296;
+        if buf.len() < header_len {
+            return Err(Error::deserialize(&format!(
+                "expected at least {} bytes for the SparseDFA header, \
+                 but only have {}",
+                header_len, buf.len(),
+            )));
+        }
+        if buf[..label.len()] != label[..] {
+            return Err(Error::deserialize("could not find SparseDFA label"));
+        }
+        let mut rest = &buf[label.len()..];
+
+        // check that current endianness is same as endianness of DFA
+        let endian_check = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if endian_check == 0xFFFE {
+            return Err(Error::endianness_mismatch());
+        } else if endian_check != 0xFEFF {
+            return Err(Error::deserialize(&format!(
+                "invalid endianness check, expected 0xFEFF but got 0x{:X}",
+                endian_check,
+            )));
+        }
+
+        // check that the version number is supported
+        let version = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if version != VERSION {
+            return Err(Error::version_mismatch(VERSION, version));
+        }
+
+        // read size of state
+        let state_size = NativeEndian::read_u16(rest) as usize;
+        rest = &rest[2..];
+        if state_size != size_of::<S>() {
+            return Err(Error::state_id_size_mismatch(
+                size_of::<S>(),
+                state_size,
+            ));
+        }
+
+        // read miscellaneous options
+        let opts = NativeEndian::read_u16(rest);
+        rest = &rest[2..];
+        if opts & !dense::MASK_ANCHORED != 0 {
+            return Err(Error::deserialize(&format!(
+                "unrecognized SparseDFA options 0x{:X}", opts,
+            )));
+        }
+
+        let start = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let state_count = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let max_match = NativeEndian::read_u64(rest);
+        rest = &rest[8..];
+        let checksum = NativeEndian::read_u32(rest);
+        rest = &rest[4..];
+        if NativeEndian::read_u32(rest) != 0 {
+            return Err(Error::deserialize(
+                "reserved SparseDFA header bytes are not zero",
+            ));
+        }
+        rest = &rest[4..];
+
+        // check that byte classes map every byte to a class in the alphabet
+        let byte_classes = ByteClasses::from_slice(&rest[..256]);
+        rest = &rest[256..];
+        let alphabet_len = byte_classes.alphabet_len();
+        if (0..256).any(|b| byte_classes.get(b as u8) as usize >= alphabet_len)
+        {
+            return Err(Error::deserialize(
+                "byte class map contains a class outside of the alphabet",
+            ));
+        }
+
+        // Find where every state starts, checking that each one is
+        // completely contained in the transition table and that its input
+        // ranges are sorted and do not overlap, since searching stops at the
+        // first range that contains the input byte.
+        let mut ids = vec![];
+        let mut pos = 0;
+        while pos < rest.len() {
+            if pos > S::max_id() {
+                return Err(Error::deserialize(&format!(
+                    "SparseDFA state at offset {} cannot be identified by \
+                     the requested state identifier representation",
+                    pos,
+                )));
+            }
+            if rest.len() - pos < 2 {
+                return Err(Error::deserialize(&format!(
+                    "SparseDFA state at offset {} is truncated", pos,
+                )));
+            }
+            let ntrans = NativeEndian::read_u16(&rest[pos..]) as usize;
+            let len = 2 + ntrans * 2 + ntrans * state_size;
+            if rest.len() - pos < len {
+                return Err(Error::deserialize(&format!(
+                    "SparseDFA state at offset {} is truncated", pos,
+                )));
+            }
+            let ranges = &rest[pos + 2..pos + 2 + ntrans * 2];
+            for i in 0..ntrans {
+                let (start, end) = (ranges[i * 2], ranges[i * 2 + 1]);
+                let sorted = i == 0 || ranges[i * 2 - 1] < start;
+                if start > end || !sorted {
+                    return Err(Error::deserialize(&format!(
+                        "input ranges of SparseDFA state at offset {} are \
+                         not sorted or overlap",
+                        pos,
+                    )));
+                }
+            }
+            ids.push(pos as u64);
+            pos += len;
+        }
+        if ids.len() as u64 != state_count {
+            return Err(Error::deserialize(&format!(
+                "SparseDFA state count {} does not match the {} states \
+                 found in its transition table",
+                state_count, ids.len(),
+            )));
+        }
+
+        let is_valid_id = |id: u64| ids.binary_search(&id).is_ok();
+        if !is_valid_id(start) {
+            return Err(Error::deserialize(&format!(
+                "invalid start state {}", start,
+            )));
+        }
+        if !is_valid_id(max_match) {
+            return Err(Error::deserialize(&format!(
+                "invalid maximum match state {}", max_match,
+            )));
+        }
+        let dfa = Repr {
+            anchored: opts & dense::MASK_ANCHORED > 0,
+            start: S::from_usize(start as usize),
+            state_count: state_count as usize,
+            max_match: S::from_usize(max_match as usize),
+            byte_classes,
+            trans: rest,
+        };
+        for (id, state) in dfa.states() {
+            for i in 0..state.ntrans {
+                let next = state.next_at(i).to_usize();
+                let bad = if id == dead_id() {
+                    next != 0
+                } else {
+                    !is_valid_id(next as u64)
+                };
+                if bad {
+                    return Err(Error::deserialize(&format!(
+                        "transition {} of SparseDFA state {} points to \
+                         invalid state {}",
+                        i, id.to_usize(), next,
+                    )));
+                }
+            }
+        }
+
+        // Checking the checksum last means that a transition table that is
+        // corrupt in a way the checks above can detect is reported as such.
+        let found = crc32(rest);
+        if found != checksum {
+            return Err(Error::checksum_mismatch(checksum, found));
+        }
+        Ok(dfa)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<S: StateID> Repr<Vec<u8>, S> {
     /// The implementation for constructing a sparse DFA from a dense DFA.
@@ -956,6 +1417,17 @@ impl<S: StateID> Repr<Vec<u8>, S> {
         // In the second pass, we fill in the transitions based on the map
         // built in the first pass.
 
+        //
+        // If the dense DFA doesn't use byte classes, then we compute them from
+        // its transition table anyway, since expressing the ranges over
+        // classes can only decrease the number of transitions in each state.
+
+        let dense_classes = !dfa.byte_classes().is_singleton();
+        let byte_classes = if dense_classes {
+            dfa.byte_classes().clone()
+        } else {
+            transition_classes(dfa)
+        };
         let mut trans = Vec::with_capacity(size_of::<A>() * dfa.state_count());
         let mut remap: Vec<A> = vec![dead_id(); dfa.state_count()];
         for (old_id, state) in dfa.states() {
@@ -967,7 +1439,8 @@ impl<S: StateID> Repr<Vec<u8>, S> {
             trans.push(0);
 
             let mut trans_count = 0;
-            for (b1, b2, _) in state.sparse_transitions() {
+            let ranges = class_ranges(&state, &byte_classes, dense_classes);
+            for (b1, b2, _) in ranges {
                 trans_count += 1;
                 trans.push(b1);
                 trans.push(b2);
@@ -985,14 +1458,15 @@ impl<S: StateID> Repr<Vec<u8>, S> {
             start: remap[dfa.state_id_to_index(dfa.start_state())],
             state_count: dfa.state_count(),
             max_match: remap[dfa.state_id_to_index(dfa.max_match_state())],
-            byte_classes: dfa.byte_classes().clone(),
+            byte_classes,
             trans: trans,
         };
         for (old_id, old_state) in dfa.states() {
             let new_id = remap[dfa.state_id_to_index(old_id)];
             let mut new_state = new.state_mut(new_id);
-            let sparse = old_state.sparse_transitions();
-            for (i, (_, _, next)) in sparse.enumerate() {
+            let sparse =
+                class_ranges(&old_state, &byte_classes, dense_classes);
+            for (i, (_, _, next)) in sparse.into_iter().enumerate() {
                 let next = remap[dfa.state_id_to_index(next)];
                 new_state.set_next_at(i, next);
             }
@@ -1013,6 +1487,73 @@ impl<S: StateID> Repr<Vec<u8>, S> {
     }
 }
 
+/// Compute the equivalence classes of bytes that no state in the given dense
+/// DFA can distinguish, such that each class is a contiguous range of bytes.
+///
+/// This is used when converting a dense DFA without byte classes to a sparse
+/// DFA.
+#[cfg(feature = "std")]
+fn transition_classes<T: AsRef<[S]>, S: StateID>(
+    dfa: &dense::Repr<T, S>,
+) -> ByteClasses {
+    let mut boundaries = [false; 256];
+    for (_, state) in dfa.states() {
+        let mut prev = None;
+        for (b, next) in state.transitions() {
+            if prev.is_some() && prev != Some(next) {
+                boundaries[b as usize] = true;
+            }
+            prev = Some(next);
+        }
+    }
+    let mut classes = ByteClasses::empty();
+    let mut class = 0;
+    for (b, &boundary) in boundaries.iter().enumerate().skip(1) {
+        if boundary {
+            class += 1;
+        }
+        classes.set(b as u8, class);
+    }
+    classes
+}
+
+/// Return the non-dead transitions of the given dense state as inclusive
+/// ranges of equivalence classes in `classes`.
+///
+/// When `dense_classes` is true, the dense state's transitions are already
+/// defined over `classes`. Otherwise, they are defined over bytes and each
+/// class in `classes` must be a contiguous range of bytes that the state
+/// cannot distinguish.
+#[cfg(feature = "std")]
+fn class_ranges<S: StateID>(
+    state: &dense::State<S>,
+    classes: &ByteClasses,
+    dense_classes: bool,
+) -> Vec<(u8, u8, S)> {
+    if dense_classes {
+        return state.sparse_transitions().collect();
+    }
+    let mut ranges: Vec<(u8, u8, S)> = vec![];
+    let mut prev = None;
+    for (b, next) in state.transitions() {
+        let class = classes.get(b);
+        if prev == Some(class) {
+            continue;
+        }
+        prev = Some(class);
+        match ranges.last_mut() {
+            Some(last) if last.2 == next && last.1 + 1 == class => {
+                last.1 = class;
+                continue;
+            }
+            _ => {}
+        }
+        ranges.push((class, class, next));
+    }
+    ranges.retain(|&(_, _, next)| next != dead_id());
+    ranges
+}
+
 #[cfg(feature = "std")]
 impl<T: AsRef<[u8]>, S: StateID> fmt::Debug for Repr<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -1077,6 +1618,75 @@ impl<'a, T: AsRef<[u8]>, S: StateID> Iterator for StateIter<'a, T, S> {
     }
 }
 
+/// An iterator over all states in a sparse DFA.
+///
+/// Each item is a pair of a state identifier and an iterator over the
+/// transitions out of that state.
+///
+/// This is created by
+/// [`SparseDFA::states`](enum.SparseDFA.html#method.states). `'a`
+/// corresponds to the lifetime of the DFA, `T` corresponds to the type of
+/// its transition table and `S` corresponds to its state identifier
+/// representation.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct States<'a, T: AsRef<[u8]> + 'a, S: StateID + 'a = usize> {
+    it: StateIter<'a, T, S>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: AsRef<[u8]>, S: StateID> Iterator for States<'a, T, S> {
+    type Item = (S, Transitions<'a, S>);
+
+    fn next(&mut self) -> Option<(S, Transitions<'a, S>)> {
+        let dfa = self.it.dfa;
+        let classes = &dfa.byte_classes;
+        self.it.next().map(|(id, state)| {
+            (id, Transitions { classes, state, index: 0 })
+        })
+    }
+}
+
+/// An iterator over the transitions out of a single state of a sparse DFA.
+///
+/// Each item is a triple of an inclusive range of bytes and the identifier of
+/// the state that every byte in the range leads to. Ranges are yielded in
+/// ascending order. Transitions to the dead state are not yielded.
+///
+/// This is created by
+/// [`SparseDFA::states`](enum.SparseDFA.html#method.states).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Transitions<'a, S: StateID + 'a = usize> {
+    classes: &'a ByteClasses,
+    state: State<'a, S>,
+    /// The index of the next transition of `state` to yield.
+    index: usize,
+}
+
+#[cfg(feature = "std")]
+impl<'a, S: StateID> Iterator for Transitions<'a, S> {
+    type Item = (u8, u8, S);
+
+    fn next(&mut self) -> Option<(u8, u8, S)> {
+        while self.index < self.state.ntrans {
+            let i = self.index;
+            self.index += 1;
+            let next = self.state.next_at(i);
+            if next == dead_id() {
+                continue;
+            }
+            // The transitions of a DFA with byte classes are ranges of
+            // classes, and every class is a contiguous range of bytes.
+            let (start, end) = self.state.range(i);
+            let (start, _) = self.classes.bytes(start);
+            let (_, end) = self.classes.bytes(end);
+            return Some((start, end, next));
+        }
+        None
+    }
+}
+
 /// A representation of a sparse DFA state that can be cheaply materialized
 /// from a state identifier.
 #[derive(Clone)]
@@ -46,6 +46,12 @@ impl SparseSet {
         self.dense.get(i) == Some(&value)
     }
 
+    /// Return the elements of this set in the order in which they were
+    /// inserted.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.dense
+    }
+
     pub fn clear(&mut self) {
         self.dense.clear();
     }
@@ -1,6 +1,10 @@
 use core::fmt::Debug;
 use core::hash::Hash;
 use core::mem::size_of;
+use core::num::{NonZeroU16, NonZeroU8};
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+use core::num::NonZeroU32;
+use core::slice;
 
 use byteorder::{ByteOrder, NativeEndian};
 
@@ -62,7 +66,8 @@ mod std {
     /// specified endianness. The given slice must have length at least
     /// `size_of::<S>()`.
     ///
-    /// The given state identifier representation must have size 1, 2, 4 or 8.
+    /// The given state identifier representation must have size 1, 2, 3, 4
+    /// or 8.
     pub fn write_state_id_bytes<E: ByteOrder, S: StateID>(
         slice: &mut [u8],
         id: S,
@@ -70,6 +75,7 @@ mod std {
         assert!(
             1 == size_of::<S>()
             || 2 == size_of::<S>()
+            || 3 == size_of::<S>()
             || 4 == size_of::<S>()
             || 8 == size_of::<S>()
         );
@@ -77,6 +83,7 @@ mod std {
         match size_of::<S>() {
             1 => slice[0] = id.to_usize() as u8,
             2 => E::write_u16(slice, id.to_usize() as u16),
+            3 => E::write_uint(slice, id.to_usize() as u64, 3),
             4 => E::write_u32(slice, id.to_usize() as u32),
             8 => E::write_u64(slice, id.to_usize() as u64),
             _ => unreachable!(),
@@ -84,12 +91,47 @@ mod std {
     }
 }
 
+/// Read an identifier from the given slice of bytes using the specified
+/// endianness. The given slice must have length at least `size_of::<S>()`.
+///
+/// The given state identifier representation must have size 1, 2, 3, 4 or 8.
+#[inline]
+pub fn read_state_id_bytes<E: ByteOrder, S: StateID>(slice: &[u8]) -> S {
+    match size_of::<S>() {
+        1 => S::from_usize(slice[0] as usize),
+        2 => S::from_usize(E::read_u16(slice) as usize),
+        3 => S::from_usize(E::read_uint(slice, 3) as usize),
+        4 => S::from_usize(E::read_u32(slice) as usize),
+        8 => S::from_usize(E::read_u64(slice) as usize),
+        _ => unreachable!(),
+    }
+}
+
 /// Return the unique identifier for a DFA's dead state in the chosen
 /// representation indicated by `S`.
 pub fn dead_id<S: StateID>() -> S {
     S::from_usize(0)
 }
 
+/// Returns true if and only if every state identifier in the representation
+/// indicated by `S` is stored in memory exactly as `StateID::write_bytes`
+/// writes it.
+///
+/// A transition table can only be borrowed directly from serialized bytes
+/// when this is true. It is false for the `NonZero` representations, which
+/// store each identifier plus one.
+pub fn is_zero_copy<S: StateID>() -> bool {
+    let id = S::from_usize(1);
+    let mut buf = [0; 8];
+    id.write_bytes(&mut buf);
+    // SAFETY: Every representation provided by this crate is an integer or
+    // an array of bytes, neither of which has any padding.
+    let raw = unsafe {
+        slice::from_raw_parts(&id as *const S as *const u8, size_of::<S>())
+    };
+    raw == &buf[..size_of::<S>()]
+}
+
 /// A trait describing the representation of a DFA's state identifier.
 ///
 /// The purpose of this trait is to safely express both the possible state
@@ -99,8 +141,39 @@ pub fn dead_id<S: StateID>() -> S {
 ///
 /// In general, one should not need to implement this trait explicitly. In
 /// particular, this crate provides implementations for `u8`, `u16`, `u32`,
-/// `u64` and `usize`. (`u32` and `u64` are only provided for targets that can
-/// represent all corresponding values in a `usize`.)
+/// `u64` and `usize`, along with [`U24`](struct.U24.html), a three byte
+/// representation. (`u32`, `u64` and `U24` are only provided for targets that
+/// can represent all corresponding values in a `usize`.)
+///
+/// Implementations are also provided for `NonZeroU8`, `NonZeroU16` and
+/// `NonZeroU32`. These store every identifier plus one, so that the dead
+/// state, whose identifier is always `0`, is never stored as zero. In
+/// exchange for supporting one fewer state than the corresponding integer
+/// type, an `Option` of one of these takes no more space than the identifier
+/// itself, which makes maps from states to optional states half the size.
+/// Since their in-memory representation differs from their serialized
+/// representation, dense DFAs using them cannot be deserialized without
+/// copying. Convert such a DFA to the corresponding integer type before
+/// serializing it instead.
+///
+/// ```
+/// use std::mem::size_of;
+/// use std::num::NonZeroU16;
+/// use regex_automata::{DFA, dense};
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new()
+///     .anchored(true)
+///     .build_with_size::<NonZeroU16>("foo[0-9]+")?;
+/// assert_eq!(Some(8), dfa.find(b"foo12345"));
+///
+/// // The dead state is stored as `1`.
+/// let dead = dfa.next_state(dfa.start_state(), b'!');
+/// assert!(dfa.is_dead_state(dead));
+/// assert_eq!(1, dead.get());
+/// assert_eq!(2, size_of::<Option<NonZeroU16>>());
+/// # Ok(()) }; example().unwrap()
+/// ```
 ///
 /// # Safety
 ///
@@ -259,3 +332,186 @@ unsafe impl StateID for u64 {
         NativeEndian::write_u64(slice, self)
     }
 }
+
+/// A 24 bit state identifier, stored in three bytes.
+///
+/// This fills the gap between `u16` and `u32`: it can represent up to
+/// 16,777,216 state identifiers, which is enough for DFAs with far more than
+/// 65,536 states, while using a quarter less memory per transition than
+/// `u32`. This applies both to a DFA's transition table in memory and to its
+/// serialized form. In exchange, reading a transition requires assembling the
+/// identifier from its bytes, which makes searching slightly slower.
+///
+/// Since this type has an alignment of `1`, a DFA using it can be
+/// deserialized from a buffer with any alignment.
+///
+/// # Example
+///
+/// ```
+/// use regex_automata::{DFA, U24, dense};
+///
+/// # fn example() -> Result<(), regex_automata::Error> {
+/// let dfa = dense::Builder::new().build_with_size::<U24>("foo[0-9]+")?;
+/// assert_eq!(Some(8), dfa.find(b"foo12345"));
+///
+/// let u32_dfa = dfa.to_u32()?;
+/// assert_eq!(dfa.memory_usage() * 4, u32_dfa.memory_usage() * 3);
+/// # Ok(()) }; example().unwrap()
+/// ```
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+pub struct U24([u8; 3]);
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl U24 {
+    /// Create a new 24 bit state identifier from the given number.
+    ///
+    /// Unlike `StateID::from_usize`, this can be used in constant
+    /// expressions, such as the ones written by the
+    /// [`codegen`](codegen/index.html) module.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the given number does not fit in 24 bits.
+    pub const fn new(n: u32) -> U24 {
+        assert!(n < 1 << 24, "state identifier does not fit in 24 bits");
+        let b = n.to_ne_bytes();
+        if cfg!(target_endian = "little") {
+            U24([b[0], b[1], b[2]])
+        } else {
+            U24([b[1], b[2], b[3]])
+        }
+    }
+}
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl Debug for U24 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}", self.to_usize())
+    }
+}
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl PartialOrd for U24 {
+    fn partial_cmp(&self, other: &U24) -> Option<::core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl Ord for U24 {
+    fn cmp(&self, other: &U24) -> ::core::cmp::Ordering {
+        // The bytes are in native endian order, so they cannot be compared
+        // directly.
+        self.to_usize().cmp(&other.to_usize())
+    }
+}
+
+// The bytes of a `U24` are stored in native endian order, so that the
+// in-memory representation of a transition table is the same as the one
+// written by `write_bytes`. Deserializing a DFA relies on this.
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+unsafe impl StateID for U24 {
+    #[inline]
+    fn from_usize(n: usize) -> U24 {
+        let mut bytes = [0; 3];
+        NativeEndian::write_uint(&mut bytes, n as u64, 3);
+        U24(bytes)
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        NativeEndian::read_uint(&self.0, 3) as usize
+    }
+
+    #[inline]
+    fn max_id() -> usize { (1 << 24) - 1 }
+
+    #[inline]
+    fn read_bytes(slice: &[u8]) -> Self {
+        U24([slice[0], slice[1], slice[2]])
+    }
+
+    #[inline]
+    fn write_bytes(self, slice: &mut [u8]) {
+        slice[..3].copy_from_slice(&self.0);
+    }
+}
+
+// The `NonZero` representations store `n + 1` for every identifier `n`. This
+// can only wrap to zero when `n > max_id`, which implementors may assume
+// never happens, so any identifier is returned in that case.
+
+unsafe impl StateID for NonZeroU8 {
+    #[inline]
+    fn from_usize(n: usize) -> NonZeroU8 {
+        let id = (n as u8).wrapping_add(1);
+        NonZeroU8::new(id).unwrap_or(NonZeroU8::new(1).unwrap())
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize { self.get() as usize - 1 }
+
+    #[inline]
+    fn max_id() -> usize { u8::MAX as usize - 1 }
+
+    #[inline]
+    fn read_bytes(slice: &[u8]) -> Self {
+        NonZeroU8::from_usize(slice[0] as usize)
+    }
+
+    #[inline]
+    fn write_bytes(self, slice: &mut [u8]) {
+        slice[0] = self.to_usize() as u8;
+    }
+}
+
+unsafe impl StateID for NonZeroU16 {
+    #[inline]
+    fn from_usize(n: usize) -> NonZeroU16 {
+        let id = (n as u16).wrapping_add(1);
+        NonZeroU16::new(id).unwrap_or(NonZeroU16::new(1).unwrap())
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize { self.get() as usize - 1 }
+
+    #[inline]
+    fn max_id() -> usize { u16::MAX as usize - 1 }
+
+    #[inline]
+    fn read_bytes(slice: &[u8]) -> Self {
+        NonZeroU16::from_usize(NativeEndian::read_u16(slice) as usize)
+    }
+
+    #[inline]
+    fn write_bytes(self, slice: &mut [u8]) {
+        NativeEndian::write_u16(slice, self.to_usize() as u16)
+    }
+}
+
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+unsafe impl StateID for NonZeroU32 {
+    #[inline]
+    fn from_usize(n: usize) -> NonZeroU32 {
+        let id = (n as u32).wrapping_add(1);
+        NonZeroU32::new(id).unwrap_or(NonZeroU32::new(1).unwrap())
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize { self.get() as usize - 1 }
+
+    #[inline]
+    fn max_id() -> usize { u32::MAX as usize - 1 }
+
+    #[inline]
+    fn read_bytes(slice: &[u8]) -> Self {
+        NonZeroU32::from_usize(NativeEndian::read_u32(slice) as usize)
+    }
+
+    #[inline]
+    fn write_bytes(self, slice: &mut [u8]) {
+        NativeEndian::write_u32(slice, self.to_usize() as u32)
+    }
+}
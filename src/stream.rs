@@ -0,0 +1,259 @@
+/*!
+Search for matches in data read from an
+[`io::Read`](https://doc.rust-lang.org/std/io/trait.Read.html) source.
+
+The searches in this module only use a forward DFA, and therefore report the
+end offset of each match. Offsets are absolute positions in the stream, and
+are reported as `u64` so that they can exceed the size of the address space.
+The end offsets reported are precisely the end offsets that
+[`Regex::find_iter`](../struct.Regex.html#method.find_iter) would report if
+the entire stream were in memory, where the forward DFA of the regex is the
+DFA given here.
+
+Only a small buffer is kept in memory. The buffer grows beyond a single
+chunk only while the DFA needs to look past the end of a match to determine
+whether the match can be extended, since those bytes must be searched again
+once the match is reported.
+
+Finding the start of each match would require keeping every byte since the
+end of the previous match, which is unbounded. Callers that need the start
+of a match can keep a window of recent input of their own and search it in
+reverse.
+
+This module is only available when the `std` feature is enabled.
+
+# Example
+
+```
+use std::io;
+use regex_automata::{stream, Regex};
+
+# fn example() -> Result<(), regex_automata::Error> {
+let re = Regex::new("foo[0-9]+")?;
+let rdr: &[u8] = b"foo1 foo12 foo123";
+let ends: io::Result<Vec<u64>> =
+    stream::find_end_iter(re.forward(), rdr).collect();
+assert_eq!(ends.unwrap(), vec![4, 10, 17]);
+# Ok(()) }; example().unwrap()
+```
+*/
+
+use std::io;
+
+use dfa::DFA;
+
+/// The number of bytes requested from the underlying reader at a time.
+const CHUNK_SIZE: usize = 8 * (1 << 10);
+
+/// Returns an iterator over the end offsets of non-overlapping matches of the
+/// given DFA in the data read from `rdr`.
+///
+/// Reads are issued in chunks of a fixed size. If the reader is already
+/// buffered, then wrapping it in a `BufReader` first is unnecessary.
+///
+/// An error from the underlying reader is yielded by the iterator and ends
+/// the search. Reads that fail with `io::ErrorKind::Interrupted` are retried.
+pub fn find_end_iter<D: DFA, R: io::Read>(
+    dfa: D,
+    rdr: R,
+) -> EndMatches<D, R> {
+    EndMatches::new(dfa, rdr)
+}
+
+/// An iterator over the end offsets of non-overlapping matches in a stream.
+///
+/// This iterator yields an `io::Result<u64>` for every match, where each
+/// offset is the absolute position in the stream at which the match ends.
+///
+/// This is created by [`find_end_iter`](fn.find_end_iter.html).
+#[derive(Debug)]
+pub struct EndMatches<D: DFA, R> {
+    dfa: D,
+    rdr: R,
+    /// The bytes read from the stream that may still be searched.
+    buf: Vec<u8>,
+    /// The absolute stream offset of `buf[0]`.
+    offset: u64,
+    /// The absolute stream offset at which the next search begins.
+    next_start: u64,
+    /// The end of the most recently reported match.
+    last_match: Option<u64>,
+    eof: bool,
+    done: bool,
+}
+
+impl<D: DFA, R: io::Read> EndMatches<D, R> {
+    fn new(dfa: D, rdr: R) -> EndMatches<D, R> {
+        EndMatches {
+            dfa,
+            rdr,
+            buf: Vec::with_capacity(CHUNK_SIZE),
+            offset: 0,
+            next_start: 0,
+            last_match: None,
+            eof: false,
+            done: false,
+        }
+    }
+
+    /// Return the absolute offset of the end of the buffered input.
+    fn buf_end(&self) -> u64 {
+        self.offset + self.buf.len() as u64
+    }
+
+    /// Read the next chunk of the stream into the buffer, first discarding
+    /// every byte before `keep`.
+    fn fill(&mut self, keep: u64) -> io::Result<()> {
+        let discard = (keep - self.offset) as usize;
+        self.buf.drain(..discard);
+        self.offset = keep;
+
+        let len = self.buf.len();
+        self.buf.resize(len + CHUNK_SIZE, 0);
+        let result = loop {
+            match self.rdr.read(&mut self.buf[len..]) {
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                result => break result,
+            }
+        };
+        let n = match result {
+            Ok(n) => n,
+            Err(err) => {
+                self.buf.truncate(len);
+                return Err(err);
+            }
+        };
+        self.buf.truncate(len + n);
+        if n == 0 {
+            self.eof = true;
+        }
+        Ok(())
+    }
+
+    /// Run a single search beginning at `start`, and return the absolute end
+    /// offset of the match found, if any.
+    fn find_at(&mut self, start: u64) -> io::Result<Option<u64>> {
+        while !self.eof && self.buf_end() < start {
+            let end = self.buf_end();
+            self.fill(end)?;
+        }
+        if start > self.buf_end() {
+            return Ok(None);
+        }
+
+        let mut state = self.dfa.start_state();
+        let mut last_match =
+            if self.dfa.is_dead_state(state) {
+                return Ok(None);
+            } else if self.dfa.is_match_state(state) {
+                Some(start)
+            } else {
+                None
+            };
+        let mut at = start;
+        loop {
+            let from = (at - self.offset) as usize;
+            for (i, &b) in self.buf[from..].iter().enumerate() {
+                state = unsafe { self.dfa.next_state_unchecked(state, b) };
+                if self.dfa.is_match_or_dead_state(state) {
+                    if self.dfa.is_dead_state(state) {
+                        return Ok(last_match);
+                    }
+                    last_match = Some(at + i as u64 + 1);
+                }
+            }
+            at = self.buf_end();
+            if self.eof {
+                return Ok(last_match);
+            }
+            // Bytes after the most recent match must be kept since the next
+            // search begins there. Without a match, no byte searched so far
+            // is needed again.
+            self.fill(last_match.unwrap_or(at))?;
+        }
+    }
+}
+
+impl<D: DFA, R: io::Read> Iterator for EndMatches<D, R> {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<io::Result<u64>> {
+        loop {
+            if self.done || (self.dfa.is_anchored() && self.next_start > 0) {
+                return None;
+            }
+            let start = self.next_start;
+            let end = match self.find_at(start) {
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(Some(end)) => end,
+            };
+            // Since the DFAs in this crate have no look-around, a pattern
+            // that matches the empty string matches it everywhere. The
+            // leftmost match is therefore empty precisely when it ends where
+            // the search began.
+            if end == start {
+                // To ensure we make progress, start the next search one
+                // byte later. Don't accept empty matches immediately
+                // following a match.
+                self.next_start = end + 1;
+                if Some(end) == self.last_match {
+                    continue;
+                }
+            } else {
+                self.next_start = end;
+            }
+            self.last_match = Some(end);
+            return Some(Ok(end));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use regex::Regex;
+
+    /// A reader that returns at most one byte at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl<'a> io::Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn same_ends_as_find_iter() {
+        let text = "foo1 foofoo12 abcz ab foo".repeat(1000);
+        let text = text.as_bytes();
+        for pattern in &["foo[0-9]*", "[a-z]*", "ab|abcz", "z*", "x"] {
+            let re = Regex::new(pattern).unwrap();
+            let expected: Vec<u64> =
+                re.find_iter(text).map(|(_, e)| e as u64).collect();
+
+            let got: Vec<u64> = find_end_iter(re.forward(), text)
+                .map(|r| r.unwrap())
+                .collect();
+            assert_eq!(expected, got, "{}", pattern);
+
+            let got: Vec<u64> = find_end_iter(re.forward(), Trickle(text))
+                .map(|r| r.unwrap())
+                .collect();
+            assert_eq!(expected, got, "{}", pattern);
+        }
+    }
+}
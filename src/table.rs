@@ -0,0 +1,170 @@
+use std::ascii;
+use std::fmt;
+
+use classes::ByteClasses;
+use dfa::DFA;
+use state_id::StateID;
+
+/// Write a table of the given states of a DFA, one state per line.
+///
+/// Each line starts with a set of flags marking the dead state (`D`), the
+/// start state (`>`) and match states (`*`), followed by the state's
+/// identifier and its transitions. Transitions to the dead state are
+/// omitted, and the rest are grouped by their target, so that a state with
+/// many transitions to the same state takes up little space. Targets are
+/// ordered by the first byte that leads to them.
+///
+/// When the formatter's alternate flag is set, transitions are labeled with
+/// byte classes instead of bytes, and a legend listing the bytes in each
+/// class is written after the table.
+pub(crate) fn write<D, I, R>(
+    f: &mut fmt::Formatter,
+    name: &str,
+    dfa: &D,
+    states: I,
+    classes: &ByteClasses,
+) -> fmt::Result
+where
+    D: DFA,
+    I: IntoIterator<Item = (D::ID, R)>,
+    R: IntoIterator<Item = (u8, u8, D::ID)>,
+{
+    let states: Vec<(D::ID, R)> = states.into_iter().collect();
+    let width = states
+        .iter()
+        .map(|&(id, _)| id.to_usize().to_string().len())
+        .max()
+        .unwrap_or(1);
+    writeln!(
+        f,
+        "{}({} states, {} byte classes, start {})",
+        name,
+        states.len(),
+        classes.alphabet_len(),
+        dfa.start_state().to_usize(),
+    )?;
+    for (id, trans) in states {
+        let mut groups: Vec<(D::ID, Vec<String>)> = vec![];
+        for (start, end, next) in trans {
+            let label = if f.alternate() {
+                escape_classes(classes.get(start), classes.get(end))
+            } else {
+                escape_range(start, end)
+            };
+            match groups.iter().position(|&(to, _)| to == next) {
+                Some(i) => groups[i].1.push(label),
+                None => groups.push((next, vec![label])),
+            }
+        }
+        let groups: Vec<String> = groups
+            .into_iter()
+            .map(|(to, labels)| {
+                format!("{} => {}", labels.join(", "), to.to_usize())
+            })
+            .collect();
+        let line = format!(
+            "{}{}{} {:>width$}  {}",
+            if dfa.is_dead_state(id) { 'D' } else { ' ' },
+            if id == dfa.start_state() { '>' } else { ' ' },
+            if dfa.is_match_state(id) { '*' } else { ' ' },
+            id.to_usize(),
+            groups.join(" | "),
+            width = width,
+        );
+        writeln!(f, "{}", line.trim_end())?;
+    }
+    if f.alternate() {
+        writeln!(f, "byte classes:")?;
+        let mut start = 0;
+        for b in 0..256 {
+            let b = b as u8;
+            if b == 255 || classes.get(b) != classes.get(b + 1) {
+                let class = escape_classes(classes.get(b), classes.get(b));
+                writeln!(f, "  {}: {}", class, escape_range(start, b))?;
+                start = b.wrapping_add(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Format an inclusive range of byte classes.
+fn escape_classes(start: u8, end: u8) -> String {
+    if start == end {
+        format!("#{}", start)
+    } else {
+        format!("#{}-#{}", start, end)
+    }
+}
+
+/// Format an inclusive range of bytes.
+fn escape_range(start: u8, end: u8) -> String {
+    if start == end {
+        escape(start)
+    } else {
+        format!("{}-{}", escape(start), escape(end))
+    }
+}
+
+/// Format a byte using its ASCII representation when it is printable and an
+/// escape sequence otherwise.
+///
+/// Bytes that separate ranges and groups in a table are also escaped, so
+/// that every label can be read unambiguously.
+fn escape(b: u8) -> String {
+    match b {
+        b' ' => "\\x20".to_string(),
+        b'-' | b',' | b'|' => format!("\\{}", b as char),
+        _ => {
+            let escaped = ascii::escape_default(b).collect::<Vec<_>>();
+            String::from_utf8(escaped).unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dense;
+    use dfa::DFA;
+
+    #[test]
+    fn groups_transitions() {
+        let dfa = dense::Builder::new()
+            .anchored(true)
+            .premultiply(false)
+            .build("(?-u)[a-c\\-]x|[0-9 ]+")
+            .unwrap();
+        let start = dfa.start_state();
+        let letter = dfa.next_state(start, b'a');
+        let digit = dfa.next_state(start, b'0');
+        let end = dfa.next_state(letter, b'x');
+
+        let got = dfa.to_string();
+        assert!(got.starts_with(&format!(
+            "DenseDFA({} states, 11 byte classes, start {})\n",
+            dfa.state_count(),
+            start,
+        )), "{}", got);
+        assert!(got.contains("\nD   0\n"), "{}", got);
+        assert!(got.contains(&format!(
+            "\n >  {}  \\x20, 0-9 => {} | \\-, a-c => {}\n",
+            start, digit, letter,
+        )), "{}", got);
+        assert!(got.contains(&format!("\n  * {}\n", end)), "{}", got);
+        assert!(!got.contains("byte classes:"));
+
+        let got = format!("{:#}", dfa);
+        assert!(got.contains(&format!(
+            "\n >  {}  #1, #5 => {} | #3, #7 => {}\n",
+            start, digit, letter,
+        )), "{}", got);
+        let legend = "\nbyte classes:\n  #0: \\x00-\\x1f\n  #1: \\x20\n";
+        assert!(got.contains(legend), "{}", got);
+
+        let sparse = dfa.to_sparse().unwrap();
+        let got = sparse.to_string();
+        assert!(got.starts_with("SparseDFA("), "{}", got);
+        assert!(got.contains("  \\x20, 0-9 => "), "{}", got);
+        assert!(format!("{:#}", sparse).contains("  #1, #5 => "));
+    }
+}
@@ -0,0 +1,74 @@
+use regex_automata::{codegen, dense, DenseDFA, DFA, SparseDFA, U24};
+
+mod generated {
+    include!("../data/codegen/dense_u16.rs");
+    include!("../data/codegen/dense_u24.rs");
+    include!("../data/codegen/sparse_u16.rs");
+}
+
+const PATTERN: &str = "(?-u)foo[0-9]+bar";
+
+fn dense_u16() -> DenseDFA<Vec<u16>, u16> {
+    dense::Builder::new()
+        .minimize(true)
+        .build_with_size::<u16>(PATTERN)
+        .unwrap()
+}
+
+fn dense_u24() -> DenseDFA<Vec<U24>, U24> {
+    dense::Builder::new()
+        .minimize(true)
+        .premultiply(false)
+        .anchored(true)
+        .build_with_size::<U24>(PATTERN)
+        .unwrap()
+}
+
+fn sparse_u16() -> SparseDFA<Vec<u8>, u16> {
+    dense_u16().to_sparse_sized().unwrap()
+}
+
+// If the code generated for any DFA changes, then the files in
+// data/codegen should be regenerated by writing each DFA below to the file
+// it is compared with.
+#[test]
+fn codegen_is_up_to_date() {
+    let mut got = vec![];
+    codegen::write_dense("DENSE_U16", &dense_u16(), &mut got).unwrap();
+    let expected = include_str!("../data/codegen/dense_u16.rs");
+    assert_eq!(expected, String::from_utf8(got).unwrap());
+
+    let mut got = vec![];
+    codegen::write_dense("DENSE_U24", &dense_u24(), &mut got).unwrap();
+    let expected = include_str!("../data/codegen/dense_u24.rs");
+    assert_eq!(expected, String::from_utf8(got).unwrap());
+
+    let mut got = vec![];
+    codegen::write_sparse("SPARSE_U16", &sparse_u16(), &mut got).unwrap();
+    let expected = include_str!("../data/codegen/sparse_u16.rs");
+    assert_eq!(expected, String::from_utf8(got).unwrap());
+}
+
+#[test]
+fn codegen_search() {
+    let haystacks: &[&[u8]] =
+        &[b"foo123bar", b"xfoo1barfoo22bar", b"foobar", b"foo1ba", b""];
+    for &haystack in haystacks {
+        let expected = dense_u16().find(haystack);
+        assert_eq!(expected, generated::DENSE_U16.find(haystack));
+        assert_eq!(expected, generated::SPARSE_U16.find(haystack));
+        assert_eq!(
+            dense_u24().find(haystack),
+            generated::DENSE_U24.find(haystack),
+        );
+    }
+    assert_eq!(Some(9), generated::DENSE_U24.find(b"foo123bar"));
+    assert_eq!(Some(8), generated::SPARSE_U16.find(b"xfoo1barfoo22bar"));
+}
+
+#[test]
+fn codegen_rejects_invalid_names() {
+    let err = codegen::write_dense("not valid", &dense_u16(), vec![])
+        .unwrap_err();
+    assert_eq!(::std::io::ErrorKind::InvalidInput, err.kind());
+}
@@ -24,6 +24,7 @@ lazy_static! {
         load!(col, "fowler/nullsubexpr.toml");
         load!(col, "fowler/repetition.toml");
         load!(col, "fowler/repetition-long.toml");
+        load!(col, "alternation.toml");
         load!(col, "crazy.toml");
         load!(col, "flags.toml");
         load!(col, "iter.toml");
@@ -0,0 +1,91 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use regex_automata::{DenseDFA, Regex, SparseDFA};
+
+fn compile(name: &str, args: &[&str]) -> PathBuf {
+    let dir = env::temp_dir().join("regex-automata-compile").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    let output = Command::new(env!("CARGO_BIN_EXE_regex-automata-compile"))
+        .arg("--output")
+        .arg(&dir)
+        .arg("--name")
+        .arg(name)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    dir
+}
+
+#[test]
+fn compile_dense() {
+    let dir = compile(
+        "dense",
+        &["--state-size", "u16", "-i", "--minimize", "foo[0-9]+", "bar"],
+    );
+    for endian in &["littleendian", "bigendian"] {
+        let read = |kind: &str| -> DenseDFA<Vec<u16>, u16> {
+            let path = dir.join(format!("dense.{}.{}.dfa", kind, endian));
+            DenseDFA::from_bytes_any_endian(&fs::read(path).unwrap())
+                .unwrap()
+        };
+        let re = Regex::from_dfas(read("fwd"), read("rev"));
+        assert_eq!(Some((1, 6)), re.find(b"xFOO12bar"));
+        assert_eq!(Some((0, 3)), re.find(b"BAR"));
+    }
+}
+
+#[test]
+fn compile_sparse() {
+    let endian = if cfg!(target_endian = "little") { "little" } else { "big" };
+    let dir = compile(
+        "sparse",
+        &["--sparse", "--state-size", "u32", "--endian", endian, "[a-z]+"],
+    );
+    let read = |kind: &str| {
+        let path = dir.join(format!("sparse.{}.{}endian.dfa", kind, endian));
+        fs::read(path).unwrap()
+    };
+    let (fwd, rev) = (read("fwd"), read("rev"));
+    let re = Regex::from_dfas(
+        SparseDFA::<&[u8], u32>::try_from_bytes(&fwd).unwrap(),
+        SparseDFA::<&[u8], u32>::try_from_bytes(&rev).unwrap(),
+    );
+    assert_eq!(Some((2, 5)), re.find(b"12abc3"));
+    assert_eq!(2, fs::read_dir(&dir).unwrap().count());
+}
+
+#[test]
+fn compile_pattern_file() {
+    let file = env::temp_dir().join("regex-automata-compile-patterns.txt");
+    fs::write(&file, "foo [0-9]+ # digits\n\n   \nbar\n").unwrap();
+    let dir = compile(
+        "file",
+        &[
+            "-x",
+            "-f",
+            file.to_str().unwrap(),
+            "--state-size",
+            "u32",
+            "--endian",
+            "little",
+        ],
+    );
+    let read = |kind: &str| -> DenseDFA<Vec<u32>, u32> {
+        let path = dir.join(format!("file.{}.littleendian.dfa", kind));
+        DenseDFA::from_bytes_any_endian(&fs::read(path).unwrap()).unwrap()
+    };
+    let re = Regex::from_dfas(read("fwd"), read("rev"));
+    // Blank lines are skipped rather than compiled to empty patterns, and a
+    // trailing comment does not swallow the rest of the alternation.
+    assert_eq!(None, re.find(b"xyz"));
+    assert_eq!(Some((1, 6)), re.find(b"xfoo12"));
+    assert_eq!(Some((1, 4)), re.find(b"xbar"));
+}
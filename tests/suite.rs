@@ -1,4 +1,6 @@
-use regex_automata::{DenseDFA, Regex, RegexBuilder, SparseDFA};
+use std::num::NonZeroU16;
+
+use regex_automata::{dense, DenseDFA, Regex, RegexBuilder, SparseDFA, U24};
 
 use collection::{SUITE, RegexTester};
 
@@ -184,6 +186,174 @@ fn serialization_roundtrip() {
     tester.assert();
 }
 
+// Checks that a regex can be searched directly from its serialized bytes,
+// even when those bytes are not aligned. The forward and reverse DFAs are
+// serialized with different endianness, such that one of them is always
+// byte swapped while searching.
+#[test]
+fn unaligned_serialization_roundtrip() {
+    let mut builder = RegexBuilder::new();
+    builder.premultiply(true).byte_classes(true);
+
+    let mut tester = RegexTester::new().skip_expensive();
+    for test in SUITE.tests() {
+        let builder = builder.clone();
+        let re: Regex = match tester.build_regex(builder, test) {
+            None => continue,
+            Some(re) => re,
+        };
+
+        let fwd_bytes = unaligned(
+            &re.forward().to_u32().unwrap().to_bytes_big_endian().unwrap(),
+        );
+        let rev_bytes = unaligned(
+            &re.reverse().to_u32().unwrap().to_bytes_little_endian().unwrap(),
+        );
+        let fwd: dense::Unaligned<&[u8], u32> =
+            dense::Unaligned::from_bytes(&fwd_bytes[1..]);
+        let rev: dense::Unaligned<&[u8], u32> =
+            dense::Unaligned::from_bytes(&rev_bytes[1..]);
+        let re = Regex::from_dfas(fwd, rev);
+
+        tester.test(test, &re);
+    }
+    tester.assert();
+}
+
+// Checks that DFAs using three byte state identifiers can be serialized and
+// deserialized, both directly and after conversion to sparse DFAs.
+#[test]
+fn u24_serialization_roundtrip() {
+    let mut builder = RegexBuilder::new();
+    builder.premultiply(true).byte_classes(true);
+
+    let mut tester = RegexTester::new().skip_expensive();
+    for test in SUITE.tests() {
+        let builder = builder.clone();
+        let re: Regex = match tester.build_regex(builder, test) {
+            None => continue,
+            Some(re) => re,
+        };
+
+        let fwd_dfa = re.forward().to_sized::<U24>().unwrap();
+        let rev_dfa = re.reverse().to_sized::<U24>().unwrap();
+        let fwd_bytes = fwd_dfa.to_bytes_native_endian().unwrap();
+        let rev_bytes = rev_dfa.to_bytes_native_endian().unwrap();
+        let fwd: DenseDFA<&[U24], U24> = unsafe {
+            DenseDFA::from_bytes(&fwd_bytes)
+        };
+        let rev: DenseDFA<&[U24], U24> = unsafe {
+            DenseDFA::from_bytes(&rev_bytes)
+        };
+        tester.test(test, &Regex::from_dfas(fwd, rev));
+
+        let fwd_bytes = fwd_dfa
+            .to_sparse_sized::<U24>()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let rev_bytes = rev_dfa
+            .to_sparse_sized::<U24>()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let fwd: SparseDFA<&[u8], U24> = unsafe {
+            SparseDFA::from_bytes(&fwd_bytes)
+        };
+        let rev: SparseDFA<&[u8], U24> = unsafe {
+            SparseDFA::from_bytes(&rev_bytes)
+        };
+        tester.test(test, &Regex::from_dfas(fwd, rev));
+    }
+    tester.assert();
+}
+
+// Checks that DFAs using state identifiers that store each identifier plus
+// one search correctly, and that they can be serialized as sparse DFAs but
+// not borrowed as dense DFAs.
+#[test]
+fn nonzero_state_ids() {
+    let mut builder = RegexBuilder::new();
+    builder.minimize(true).premultiply(false).byte_classes(true);
+
+    let mut tester = RegexTester::new().skip_expensive();
+    for test in SUITE.tests() {
+        let builder = builder.clone();
+        let re: Regex = match tester.build_regex(builder, test) {
+            None => continue,
+            Some(re) => re,
+        };
+
+        let fwd_dfa = re.forward().to_sized::<NonZeroU16>().unwrap();
+        let rev_dfa = re.reverse().to_sized::<NonZeroU16>().unwrap();
+        let bytes = fwd_dfa.to_bytes_native_endian().unwrap();
+        assert!(
+            DenseDFA::<&[NonZeroU16], NonZeroU16>::try_from_bytes(&bytes)
+                .is_err()
+        );
+
+        let fwd_bytes = fwd_dfa
+            .to_sparse()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let rev_bytes = rev_dfa
+            .to_sparse()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let fwd: SparseDFA<&[u8], NonZeroU16> = unsafe {
+            SparseDFA::from_bytes(&fwd_bytes)
+        };
+        let rev: SparseDFA<&[u8], NonZeroU16> = unsafe {
+            SparseDFA::from_bytes(&rev_bytes)
+        };
+        tester.test(test, &Regex::from_dfas(fwd_dfa, rev_dfa));
+        tester.test(test, &Regex::from_dfas(fwd, rev));
+    }
+    tester.assert();
+}
+
+// Checks that searching a haystack split into two slices at every possible
+// position finds the same matches as searching the contiguous haystack.
+#[test]
+fn ring_find_iter() {
+    let builder = RegexBuilder::new();
+
+    let mut tester = RegexTester::new().skip_expensive();
+    for test in SUITE.tests() {
+        let builder = builder.clone();
+        let re: Regex = match tester.build_regex(builder, test) {
+            None => continue,
+            Some(re) => re,
+        };
+
+        let expected: Vec<(usize, usize)> =
+            re.find_iter(&test.input).collect();
+        for at in 0..test.input.len() + 1 {
+            let (head, tail) = test.input.split_at(at);
+            let got: Vec<(usize, usize)> =
+                re.find_ring_iter(head, tail).collect();
+            assert_eq!(expected, got, "{} split at {}", test.name, at);
+        }
+    }
+    tester.assert();
+}
+
+/// Copy the given bytes such that they start at index 1 of the returned
+/// buffer, so that they aren't aligned for any state identifier
+/// representation wider than a single byte.
+///
+/// This relies on the allocator returning an even address for the buffer,
+/// which every allocator in practice does but which isn't guaranteed for a
+/// `Vec<u8>`, so it is checked.
+fn unaligned(bytes: &[u8]) -> Vec<u8> {
+    let mut buf = vec![0; bytes.len() + 1];
+    buf[1..].copy_from_slice(bytes);
+    assert!(buf[1..].as_ptr() as usize % 2 != 0, "bytes are aligned");
+    buf
+}
+
 // A basic sanity test that checks we can serialize and then deserialize a
 // regex using sparse DFAs, and that the resulting regex can be used for
 // searching correctly.
@@ -224,3 +394,118 @@ fn sparse_serialization_roundtrip() {
     }
     tester.assert();
 }
+
+// Checks that every sparse DFA serialized by this crate passes validation
+// when deserialized from untrusted bytes, and that the validated regex is
+// still correct.
+#[test]
+fn sparse_verified_serialization_roundtrip() {
+    let mut builder = RegexBuilder::new();
+    builder.byte_classes(true);
+
+    let mut tester = RegexTester::new().skip_expensive();
+    for test in SUITE.tests() {
+        let builder = builder.clone();
+        let re: Regex = match tester.build_regex(builder, test) {
+            None => continue,
+            Some(re) => re,
+        };
+
+        let fwd_bytes = re
+            .forward()
+            .to_sparse()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let rev_bytes = re
+            .reverse()
+            .to_sparse()
+            .unwrap()
+            .to_bytes_native_endian()
+            .unwrap();
+        let fwd: SparseDFA<&[u8], usize> =
+            SparseDFA::try_from_bytes(&fwd_bytes).unwrap();
+        let rev: SparseDFA<&[u8], usize> =
+            SparseDFA::try_from_bytes(&rev_bytes).unwrap();
+        let re = Regex::from_dfas(fwd, rev);
+
+        tester.test(test, &re);
+    }
+    tester.assert();
+}
+
+// Checks that a regex survives a round trip through serde, both with dense
+// and sparse DFAs.
+#[cfg(feature = "serde")]
+#[test]
+fn serde_roundtrip() {
+    #[derive(Deserialize, Serialize)]
+    struct Config {
+        dense: Regex<DenseDFA<Vec<u16>, u16>>,
+        sparse: Regex<SparseDFA<Vec<u8>, u16>>,
+    }
+
+    let re = Regex::new("foo[0-9]+").unwrap();
+    let dense = Regex::from_dfas(
+        re.forward().to_u16().unwrap(),
+        re.reverse().to_u16().unwrap(),
+    );
+    let sparse = Regex::from_dfas(
+        re.forward().to_sparse_sized::<u16>().unwrap(),
+        re.reverse().to_sparse_sized::<u16>().unwrap(),
+    );
+    let config = toml::to_string(&Config { dense, sparse }).unwrap();
+    let config: Config = toml::from_str(&config).unwrap();
+    assert_eq!(Some((1, 9)), config.dense.find(b"zfoo12345"));
+    assert_eq!(Some((1, 9)), config.sparse.find(b"zfoo12345"));
+
+    let garbage = "dense = [[1, 2], [3]]\nsparse = [[], []]\n";
+    assert!(toml::from_str::<Config>(garbage).is_err());
+}
+
+static SUITE_RS: &[u8] = include_aligned_bytes!(u64, "suite.rs");
+
+// Checks that included bytes are aligned, both in a static and in an
+// expression, and that a dense DFA is only borrowed from aligned bytes.
+#[test]
+fn aligned_bytes() {
+    let tests_rs: &[u8] = include_aligned_bytes!(u32, "tests.rs");
+    assert_eq!(0, SUITE_RS.as_ptr() as usize % 8);
+    assert_eq!(0, tests_rs.as_ptr() as usize % 4);
+    assert_eq!(SUITE_RS, &include_bytes!("suite.rs")[..]);
+    assert_eq!(tests_rs, &include_bytes!("tests.rs")[..]);
+
+    let dfa = DenseDFA::new("foo[0-9]+").unwrap().to_u32().unwrap();
+    let bytes = dfa.to_bytes_native_endian().unwrap();
+    let buf = unaligned(&bytes);
+    assert!(DenseDFA::<&[u32], u32>::try_from_bytes(&buf[1..]).is_err());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mmap_open() {
+    use std::fs;
+
+    let dir = ::std::env::temp_dir().join("regex-automata-mmap");
+    fs::create_dir_all(&dir).unwrap();
+    let re = Regex::new("foo[0-9]+").unwrap();
+    let (fwd, rev) = (dir.join("fwd.dfa"), dir.join("rev.dfa"));
+    let bytes = re.forward().to_u16().unwrap().to_bytes_native_endian();
+    fs::write(&fwd, bytes.unwrap()).unwrap();
+    let bytes = re.reverse().to_u16().unwrap().to_bytes_native_endian();
+    fs::write(&rev, bytes.unwrap()).unwrap();
+
+    let fwd = unsafe { DenseDFA::<_, u16>::open(&fwd).unwrap() };
+    let rev = unsafe { DenseDFA::<_, u16>::open(&rev).unwrap() };
+    let re = Regex::from_dfas(fwd.as_ref(), rev.as_ref());
+    assert_eq!(Some((1, 9)), re.find(b"zfoo12345"));
+
+    // Files with an invalid header are rejected, as are missing files.
+    let garbage = dir.join("garbage.dfa");
+    fs::write(&garbage, b"rust-regex-automata-dfa\x00").unwrap();
+    assert!(unsafe { DenseDFA::<_, u16>::open(&garbage) }.is_err());
+    let u32_dfa = unsafe { DenseDFA::<_, u32>::open(dir.join("fwd.dfa")) };
+    assert!(u32_dfa.is_err());
+    let missing = unsafe { DenseDFA::<_, u16>::open(dir.join("missing")) };
+    assert!(missing.is_err());
+}
@@ -3,6 +3,7 @@
 #[macro_use]
 extern crate lazy_static;
 extern crate regex;
+#[macro_use]
 extern crate regex_automata;
 extern crate serde;
 extern crate serde_bytes;
@@ -10,7 +11,10 @@ extern crate serde_bytes;
 extern crate serde_derive;
 extern crate toml;
 
+mod codegen;
 mod collection;
+#[cfg(feature = "compile")]
+mod compile;
 mod regression;
 mod suite;
 mod unescape;